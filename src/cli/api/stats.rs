@@ -15,6 +15,9 @@ pub struct NodeStats {
     pub flow: Flow,
     pub pipelines: HashMap<String, PipelineStats>,
     pub reloads: Reloads,
+    /// If the flow metrics were estimated by tuistash, on Logstash versions without the flow API
+    #[serde(skip)]
+    pub estimated_flow: bool,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -0,0 +1,242 @@
+use crate::api::node::NodeInfo;
+use crate::api::stats::NodeStats;
+use crate::commands::analyze::analyzer::recent_value;
+use crate::commands::formatter::NumberFormatter;
+
+// Worker concurrency ratios used to classify the pipelines provisioning
+const SATURATED_WORKERS_RATIO: f64 = 0.9;
+const IDLE_WORKERS_RATIO: f64 = 0.3;
+// Suggested workers aim this concurrency ratio, leaving some room for spikes
+const TARGET_WORKERS_RATIO: f64 = 0.7;
+const BACKPRESSURE_THRESHOLD: f64 = 0.1;
+const HIGH_CPU_PERCENT: i64 = 80;
+const HIGH_HEAP_PERCENT: i64 = 85;
+const LOW_HEAP_PERCENT: i64 = 50;
+const MAX_SUGGESTED_BATCH_SIZE: i64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    UnderProvisioned,
+    OverProvisioned,
+    Balanced,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::UnderProvisioned => "UNDER-PROVISIONED",
+            Verdict::OverProvisioned => "OVER-PROVISIONED",
+            Verdict::Balanced => "BALANCED",
+            Verdict::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub setting: &'static str,
+    pub current: i64,
+    pub suggested: i64,
+}
+
+/// Tuning advice for a single pipeline, based on its workers usage and the node resources.
+#[derive(Debug, Clone)]
+pub struct Advice {
+    pub verdict: Verdict,
+    pub workers: i64,
+    pub batch_size: i64,
+    pub batch_delay: i64,
+    pub worker_concurrency: f64,
+    /// Fraction of the workers that are idle, from 0 to 1
+    pub headroom: f64,
+    pub available_processors: Option<i64>,
+    /// Number of workers of all the node pipelines
+    pub node_workers: i64,
+    pub cpu_percent: i64,
+    pub heap_used_percent: i64,
+    pub suggestions: Vec<Suggestion>,
+    pub reasons: Vec<String>,
+}
+
+/// Estimates the headroom of the pipeline workers, and suggests `pipeline.workers` and
+/// `pipeline.batch.size` values considering the CPU and heap available on the node.
+pub fn advise(pipeline: &str, node_info: &NodeInfo, node_stats: &NodeStats) -> Option<Advice> {
+    let pipeline_info = node_info.pipelines.as_ref()?.get(pipeline)?;
+    let pipeline_stats = node_stats.pipelines.get(pipeline)?;
+
+    let workers = if pipeline_info.workers > 0 {
+        pipeline_info.workers
+    } else {
+        node_stats.pipeline.workers
+    };
+
+    let batch_size = if pipeline_info.batch_size > 0 {
+        pipeline_info.batch_size
+    } else {
+        node_stats.pipeline.batch_size
+    };
+
+    let node_workers = node_info
+        .pipelines
+        .as_ref()?
+        .values()
+        .map(|p| p.workers.max(0))
+        .sum();
+
+    let worker_concurrency = recent_value(&pipeline_stats.flow.worker_concurrency);
+    let backpressure = recent_value(&pipeline_stats.flow.queue_backpressure);
+    let saturation = if workers > 0 {
+        worker_concurrency / workers as f64
+    } else {
+        0.0
+    };
+
+    let mut advice = Advice {
+        verdict: Verdict::Balanced,
+        workers,
+        batch_size,
+        batch_delay: pipeline_info.batch_delay,
+        worker_concurrency,
+        headroom: (1.0 - saturation).clamp(0.0, 1.0),
+        available_processors: node_info
+            .os
+            .as_ref()
+            .map(|os| os.available_processors)
+            .filter(|p| *p > 0),
+        node_workers,
+        cpu_percent: node_stats.process.cpu.percent,
+        heap_used_percent: node_stats.jvm.mem.heap_used_percent,
+        suggestions: vec![],
+        reasons: vec![],
+    };
+
+    if workers <= 0 || pipeline_stats.events.out == 0 {
+        advice.verdict = Verdict::Unknown;
+        advice
+            .reasons
+            .push("The pipeline hasn't processed events yet, there's not enough data".to_string());
+        return Some(advice);
+    }
+
+    let workers_reason = format!(
+        "Workers are {}% busy (worker concurrency {} of {} workers)",
+        (saturation * 100.0).strip_number_decimals(2),
+        worker_concurrency.strip_number_decimals(2),
+        workers
+    );
+
+    let cpu_bound = advice.cpu_percent >= HIGH_CPU_PERCENT;
+    let heap_pressure = advice.heap_used_percent >= HIGH_HEAP_PERCENT;
+    let suggested_workers = ((worker_concurrency / TARGET_WORKERS_RATIO).ceil() as i64).max(1);
+
+    if saturation >= SATURATED_WORKERS_RATIO {
+        advice.verdict = Verdict::UnderProvisioned;
+        advice.reasons.push(workers_reason);
+        if backpressure >= BACKPRESSURE_THRESHOLD {
+            advice.reasons.push(format!(
+                "Inputs are blocked by the queue (backpressure {})",
+                backpressure.strip_number_decimals(2)
+            ));
+        }
+
+        if cpu_bound {
+            advice.reasons.push(format!(
+                "The process CPU is at {}%, more workers would compete for the same CPU, consider optimizing the slowest plugins instead",
+                advice.cpu_percent
+            ));
+        } else {
+            // Workers are usually CPU bound, allowing twice the processors covers
+            // the time spent waiting on I/O by outputs and enrichment filters
+            let max_workers = advice
+                .available_processors
+                .map(|p| (p * 2 - (node_workers - workers)).max(workers))
+                .unwrap_or(i64::MAX);
+
+            let new_workers = suggested_workers.max(workers + 1).min(max_workers);
+            if new_workers > workers {
+                advice.suggestions.push(Suggestion {
+                    setting: "pipeline.workers",
+                    current: workers,
+                    suggested: new_workers,
+                });
+                advice.reasons.push(format!(
+                    "The process CPU is at {}%, there's room for {} worker(s) at {}% concurrency",
+                    advice.cpu_percent,
+                    new_workers,
+                    (TARGET_WORKERS_RATIO * 100.0) as i64
+                ));
+            } else if let Some(processors) = advice.available_processors {
+                advice.reasons.push(format!(
+                    "The node pipelines already have {} workers for {} processors",
+                    node_workers, processors
+                ));
+            }
+        }
+
+        if advice.available_processors.is_none() {
+            advice
+                .reasons
+                .push("The number of processors is unknown".to_string());
+        }
+
+        if advice.heap_used_percent < LOW_HEAP_PERCENT && batch_size < MAX_SUGGESTED_BATCH_SIZE {
+            advice.suggestions.push(Suggestion {
+                setting: "pipeline.batch.size",
+                current: batch_size,
+                suggested: (batch_size * 2).min(MAX_SUGGESTED_BATCH_SIZE),
+            });
+            advice.reasons.push(format!(
+                "The heap is {}% used, bigger batches reduce the per-event overhead of the outputs",
+                advice.heap_used_percent
+            ));
+        }
+    } else if saturation < IDLE_WORKERS_RATIO
+        && workers > 1
+        && backpressure < BACKPRESSURE_THRESHOLD
+    {
+        advice.verdict = Verdict::OverProvisioned;
+        advice.reasons.push(workers_reason);
+        advice.reasons.push(format!(
+            "Inputs aren't blocked by the queue (backpressure {})",
+            backpressure.strip_number_decimals(2)
+        ));
+
+        let new_workers = suggested_workers.min(workers - 1);
+        advice.suggestions.push(Suggestion {
+            setting: "pipeline.workers",
+            current: workers,
+            suggested: new_workers,
+        });
+        advice.reasons.push(format!(
+            "{} worker(s) would handle the current load at up to {}% concurrency",
+            new_workers,
+            (TARGET_WORKERS_RATIO * 100.0) as i64
+        ));
+    } else {
+        advice.reasons.push(workers_reason);
+    }
+
+    if heap_pressure {
+        let in_flight_events = workers * batch_size;
+        advice
+            .suggestions
+            .retain(|s| s.setting != "pipeline.batch.size");
+        if batch_size > 1 {
+            advice.suggestions.push(Suggestion {
+                setting: "pipeline.batch.size",
+                current: batch_size,
+                suggested: (batch_size / 2).max(1),
+            });
+        }
+        advice.reasons.push(format!(
+            "The heap is {}% used, {} in-flight events ({} workers x {} batch size) might be too many",
+            advice.heap_used_percent,
+            in_flight_events,
+            workers,
+            batch_size
+        ));
+    }
+
+    Some(advice)
+}
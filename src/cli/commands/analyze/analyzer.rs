@@ -0,0 +1,243 @@
+use std::cmp::Ordering;
+
+use crate::api::node::NodeInfo;
+use crate::api::stats::{FlowMetricValue, NodeStats, PipelineStats};
+use crate::commands::formatter::NumberFormatter;
+
+// Bottlenecks with a lower score are not reported
+const MIN_SCORE: f64 = 0.2;
+const SATURATED_WORKERS_RATIO: f64 = 0.9;
+const BACKPRESSURE_THRESHOLD: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    fn from_score(score: f64) -> Self {
+        if score >= 0.6 {
+            Severity::High
+        } else if score >= 0.35 {
+            Severity::Medium
+        } else {
+            Severity::Low
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Low => "LOW",
+            Severity::Medium => "MEDIUM",
+            Severity::High => "HIGH",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bottleneck {
+    pub pipeline: String,
+    /// Plugin ID, or `None` when the pipeline itself is the bottleneck
+    pub plugin_id: Option<String>,
+    pub plugin_name: Option<String>,
+    pub plugin_type: Option<String>,
+    pub score: f64,
+    pub severity: Severity,
+    pub reasons: Vec<String>,
+}
+
+impl Bottleneck {
+    pub fn label(&self) -> String {
+        match (&self.plugin_id, &self.plugin_name) {
+            (Some(id), Some(name)) if id != name => {
+                format!("{} > {} ({})", self.pipeline, name, id)
+            }
+            (Some(id), _) => format!("{} > {}", self.pipeline, id),
+            _ => format!("{} (pipeline)", self.pipeline),
+        }
+    }
+}
+
+/// Likely bottlenecks of the node pipelines, ranked by score.
+#[derive(Debug, Clone, Default)]
+pub struct Analysis {
+    pub bottlenecks: Vec<Bottleneck>,
+}
+
+impl Analysis {
+    pub fn plugin(&self, pipeline: &str, plugin_id: &str) -> Option<&Bottleneck> {
+        self.bottlenecks
+            .iter()
+            .find(|b| b.pipeline == pipeline && b.plugin_id.as_deref() == Some(plugin_id))
+    }
+
+    /// Returns the highest severity of all the pipeline bottlenecks, including its plugins.
+    pub fn pipeline_severity(&self, pipeline: &str) -> Option<Severity> {
+        self.bottlenecks
+            .iter()
+            .filter(|b| b.pipeline == pipeline)
+            .map(|b| b.severity)
+            .max()
+    }
+}
+
+/// Combines the pipelines queue backpressure, worker concurrency and the plugins
+/// worker utilization to rank the components that are likely limiting the throughput.
+pub fn analyze(node_info: &NodeInfo, node_stats: &NodeStats) -> Analysis {
+    let mut bottlenecks = vec![];
+
+    for (name, stats) in &node_stats.pipelines {
+        let workers = node_info
+            .pipelines
+            .as_ref()
+            .and_then(|p| p.get(name))
+            .map(|p| p.workers)
+            .filter(|w| *w > 0)
+            .unwrap_or(node_stats.pipeline.workers);
+
+        analyze_pipeline(name, stats, workers, &mut bottlenecks);
+    }
+
+    bottlenecks.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.label().cmp(&b.label()))
+    });
+
+    Analysis { bottlenecks }
+}
+
+fn analyze_pipeline(
+    name: &str,
+    stats: &PipelineStats,
+    workers: i64,
+    bottlenecks: &mut Vec<Bottleneck>,
+) {
+    let backpressure = recent_value(&stats.flow.queue_backpressure);
+    let concurrency = recent_value(&stats.flow.worker_concurrency);
+    let saturation = if workers > 0 {
+        concurrency / workers as f64
+    } else {
+        0.0
+    };
+
+    let workers_reason = format!(
+        "Workers are {}% busy (worker concurrency {} of {} workers)",
+        (saturation * 100.0).strip_number_decimals(2),
+        concurrency.strip_number_decimals(2),
+        workers
+    );
+    let backpressure_reason = format!(
+        "Inputs are blocked by the queue (backpressure {})",
+        backpressure.strip_number_decimals(2)
+    );
+
+    let is_saturated = saturation >= SATURATED_WORKERS_RATIO;
+    let is_backpressured = backpressure >= BACKPRESSURE_THRESHOLD;
+    let pressure = saturation.min(1.0).max(backpressure.min(1.0));
+
+    // Pipeline level findings
+    let mut reasons = vec![];
+    let score = if is_saturated {
+        reasons.push(workers_reason.to_string());
+        if is_backpressured {
+            reasons.push(backpressure_reason.to_string());
+        }
+        0.5 * saturation.min(1.0) + 0.5 * backpressure.min(1.0)
+    } else if is_backpressured {
+        reasons.push(backpressure_reason.to_string());
+        reasons.push(format!(
+            "{}, the queue is likely the bottleneck",
+            workers_reason
+        ));
+        0.5 * backpressure.min(1.0)
+    } else {
+        0.0
+    };
+
+    if score >= MIN_SCORE {
+        bottlenecks.push(Bottleneck {
+            pipeline: name.to_string(),
+            plugin_id: None,
+            plugin_name: None,
+            plugin_type: None,
+            score,
+            severity: Severity::from_score(score),
+            reasons,
+        });
+    }
+
+    // Plugins findings
+    let worker_plugins: Vec<_> = stats
+        .plugins
+        .all_with_type()
+        .into_iter()
+        .filter(|(_, (plugin_type, _))| plugin_type == "filter" || plugin_type == "output")
+        .filter_map(|(id, (plugin_type, plugin))| {
+            let flow = plugin.flow.as_ref()?;
+            let utilization = flow.worker_utilization.as_ref().map(recent_value)?;
+            let millis_per_event = flow.worker_millis_per_event.as_ref().map(recent_value);
+            Some((id, plugin_type, plugin, utilization, millis_per_event))
+        })
+        .collect();
+
+    let slowest_millis_per_event = worker_plugins
+        .iter()
+        .filter_map(|(_, _, _, _, m)| *m)
+        .fold(0.0, f64::max);
+
+    for (id, plugin_type, plugin, utilization, millis_per_event) in &worker_plugins {
+        let score = (utilization / 100.0).min(1.0) * (0.5 + 0.5 * pressure);
+        if score < MIN_SCORE {
+            continue;
+        }
+
+        let mut reasons = vec![format!(
+            "Uses {}% of the workers time",
+            utilization.strip_number_decimals(2)
+        )];
+
+        if let Some(millis_per_event) = millis_per_event {
+            let mut reason = format!(
+                "Takes {} ms per event",
+                millis_per_event.strip_number_decimals(3)
+            );
+            if worker_plugins.len() > 1 && *millis_per_event >= slowest_millis_per_event {
+                reason.push_str(", the slowest plugin of the pipeline");
+            }
+            reasons.push(reason);
+        }
+
+        if is_saturated {
+            reasons.push(workers_reason.to_string());
+        }
+
+        if is_backpressured {
+            reasons.push(backpressure_reason.to_string());
+        }
+
+        bottlenecks.push(Bottleneck {
+            pipeline: name.to_string(),
+            plugin_id: Some(id.to_string()),
+            plugin_name: plugin.name.clone(),
+            plugin_type: Some(plugin_type.to_string()),
+            score,
+            severity: Severity::from_score(score),
+            reasons,
+        });
+    }
+}
+
+// The last minute window is less noisy than the current value, but it's only
+// available after the pipeline has been running for a while.
+pub(crate) fn recent_value(metric: &FlowMetricValue) -> f64 {
+    let value = metric.last_1_minute.unwrap_or(metric.current);
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,84 @@
+use clap::Args;
+
+use crate::api::node::NodeInfoType;
+use crate::api::Client;
+use crate::commands::analyze::analyzer::{analyze, Analysis};
+use crate::commands::traits::RunnableCommand;
+use crate::commands::tui::data_fetcher::{DataFetcher, PathDataFetcher};
+use crate::config::Config;
+use crate::errors::AnyError;
+use crate::output::Output;
+
+#[derive(Args)]
+pub struct AnalyzeArgs {
+    /// Maximum number of bottlenecks to print
+    #[arg(long)]
+    pub top: Option<usize>,
+}
+
+pub struct AnalyzeCommand;
+
+impl RunnableCommand<AnalyzeArgs> for AnalyzeCommand {
+    fn run(&self, out: &mut Output, args: &AnalyzeArgs, config: &Config) -> Result<(), AnyError> {
+        let (node_info, node_stats) = match &config.diagnostic_path {
+            Some(path) => {
+                let node_data = PathDataFetcher::new(path.to_string())?.fetch_node_data(None)?;
+                (node_data.info, node_data.stats)
+            }
+            None => (
+                config.api.get_node_info(
+                    &[NodeInfoType::Pipelines],
+                    Some(Client::QUERY_NODE_INFO_GRAPH),
+                )?,
+                config.api.get_node_stats(None)?,
+            ),
+        };
+
+        let analysis = analyze(&node_info, &node_stats);
+        AnalyzeCommand::write(out, &analysis, args.top)
+    }
+}
+
+impl AnalyzeCommand {
+    fn write(out: &mut Output, analysis: &Analysis, top: Option<usize>) -> Result<(), AnyError> {
+        if analysis.bottlenecks.is_empty() {
+            writeln!(out.handle, "No bottlenecks found")?;
+            return Ok(());
+        }
+
+        let count = top
+            .unwrap_or(analysis.bottlenecks.len())
+            .min(analysis.bottlenecks.len());
+
+        writeln!(
+            out.handle,
+            "Found {} likely bottleneck(s), ranked by score:",
+            analysis.bottlenecks.len()
+        )?;
+
+        for (index, bottleneck) in analysis.bottlenecks.iter().take(count).enumerate() {
+            let plugin_type = bottleneck
+                .plugin_type
+                .as_ref()
+                .map(|t| format!(" [{}]", t))
+                .unwrap_or_default();
+
+            writeln!(out.handle)?;
+            writeln!(
+                out.handle,
+                "{}. {} {}{} - score {:.2}",
+                index + 1,
+                bottleneck.severity.as_str(),
+                bottleneck.label(),
+                plugin_type,
+                bottleneck.score
+            )?;
+
+            for reason in &bottleneck.reasons {
+                writeln!(out.handle, "   - {}", reason)?;
+            }
+        }
+
+        Ok(())
+    }
+}
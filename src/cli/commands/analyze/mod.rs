@@ -0,0 +1,3 @@
+pub(crate) mod advisor;
+pub(crate) mod analyzer;
+pub mod command;
@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use clap::Args;
+
+use crate::commands::check::expression::ThresholdExpression;
+use crate::config::Config;
+use crate::errors::{AnyError, TuiError};
+use crate::output::Output;
+use crate::ExitCode;
+
+#[derive(Args, Default)]
+pub struct CheckArgs {
+    /// Warning threshold expression, e.g. 'pipeline.*.flow.queue_backpressure>0.5'. Can be repeated
+    #[arg(long = "warn", short = 'w')]
+    pub warn: Vec<String>,
+
+    /// Critical threshold expression, e.g. 'jvm.mem.heap_used_percent>90'. Can be repeated
+    #[arg(long = "crit", short = 'c')]
+    pub crit: Vec<String>,
+}
+
+/// Nagios plugin statuses, ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Critical,
+    Unknown,
+}
+
+impl CheckStatus {
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            CheckStatus::Ok => 0,
+            CheckStatus::Warning => 1,
+            CheckStatus::Critical => 2,
+            CheckStatus::Unknown => 3,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "OK",
+            CheckStatus::Warning => "WARNING",
+            CheckStatus::Critical => "CRITICAL",
+            CheckStatus::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+struct PerfData {
+    label: String,
+    value: f64,
+    warn: Option<f64>,
+    crit: Option<f64>,
+}
+
+impl PerfData {
+    fn format(&self) -> String {
+        let threshold = |v: Option<f64>| v.map(|p| p.to_string()).unwrap_or_default();
+        format!(
+            "'{}'={};{};{}",
+            self.label,
+            self.value,
+            threshold(self.warn),
+            threshold(self.crit)
+        )
+    }
+}
+
+pub struct CheckCommand;
+
+impl CheckCommand {
+    pub fn run(
+        &self,
+        out: &mut Output,
+        args: &CheckArgs,
+        config: &Config,
+    ) -> Result<ExitCode, AnyError> {
+        let (status, message, perf_data) = match Self::check(args, config) {
+            Ok(value) => value,
+            Err(e) => (CheckStatus::Unknown, e.to_string(), vec![]),
+        };
+
+        let line = Self::format_line(status, &message, &perf_data);
+        out.handle.write_all(line.as_bytes())?;
+        out.handle.write_all(b"\n")?;
+
+        Ok(status.exit_code())
+    }
+
+    /// Prints an error raised outside of the check itself, e.g. while setting up the API
+    /// client, with the status Nagios expects for any failure.
+    pub fn report_error(err: &AnyError) -> ExitCode {
+        let status = CheckStatus::Unknown;
+        // The exit code must be kept even if the output is not writable
+        _ = writeln!(
+            io::stdout(),
+            "{}",
+            Self::format_line(status, &err.to_string(), &[])
+        );
+        status.exit_code()
+    }
+
+    fn format_line(status: CheckStatus, message: &str, perf_data: &[PerfData]) -> String {
+        let mut line = format!("LOGSTASH {} - {}", status.as_str(), message);
+        if !perf_data.is_empty() {
+            let perf_data: Vec<String> = perf_data.iter().map(|p| p.format()).collect();
+            line.push_str(&format!(" | {}", perf_data.join(" ")));
+        }
+
+        line
+    }
+
+    fn check(
+        args: &CheckArgs,
+        config: &Config,
+    ) -> Result<(CheckStatus, String, Vec<PerfData>), AnyError> {
+        if config.diagnostic_path.is_some() {
+            return Err(TuiError::from(
+                "The --diagnostic-path argument is not supported by the check command",
+            )
+            .into());
+        }
+
+        if args.warn.is_empty() && args.crit.is_empty() {
+            return Err(TuiError::from("No --warn or --crit thresholds were provided").into());
+        }
+
+        let warn = Self::parse_expressions(&args.warn)?;
+        let crit = Self::parse_expressions(&args.crit)?;
+
+        let node_stats = config.api.get_node_stats(None)?;
+        let node_stats = serde_json::to_value(node_stats)?;
+
+        let mut status = CheckStatus::Ok;
+        let mut problems: Vec<String> = vec![];
+        let mut problems_labels: HashSet<String> = HashSet::new();
+        let mut perf_data: Vec<PerfData> = vec![];
+
+        for (expressions, expressions_status) in [
+            (&crit, CheckStatus::Critical),
+            (&warn, CheckStatus::Warning),
+        ] {
+            for expression in expressions {
+                let metrics = expression.resolve(&node_stats);
+                if metrics.is_empty() {
+                    return Err(TuiError::from(
+                        format!("No metric found for {}", expression).as_str(),
+                    )
+                    .into());
+                }
+
+                for (label, value) in metrics {
+                    let index = match perf_data.iter().position(|p| p.label == label) {
+                        Some(index) => index,
+                        None => {
+                            perf_data.push(PerfData {
+                                label: label.to_string(),
+                                value,
+                                warn: None,
+                                crit: None,
+                            });
+                            perf_data.len() - 1
+                        }
+                    };
+
+                    let metric_perf_data = &mut perf_data[index];
+                    if expressions_status == CheckStatus::Critical {
+                        metric_perf_data.crit.get_or_insert(expression.threshold);
+                    } else {
+                        metric_perf_data.warn.get_or_insert(expression.threshold);
+                    }
+
+                    // Critical thresholds are evaluated first, so a metric is reported once
+                    // with its highest severity.
+                    if expression.operator.matches(value, expression.threshold)
+                        && problems_labels.insert(label.to_string())
+                    {
+                        status = status.max(expressions_status);
+                        problems.push(format!(
+                            "{} is {} ({}{})",
+                            label,
+                            value,
+                            expression.operator.as_str(),
+                            expression.threshold
+                        ));
+                    }
+                }
+            }
+        }
+
+        let message = if problems.is_empty() {
+            format!("{} metric(s) within thresholds", perf_data.len())
+        } else {
+            problems.join(", ")
+        };
+
+        Ok((status, message, perf_data))
+    }
+
+    fn parse_expressions(values: &[String]) -> Result<Vec<ThresholdExpression>, AnyError> {
+        let mut expressions = Vec::with_capacity(values.len());
+        for value in values {
+            expressions.push(ThresholdExpression::try_from(value.as_str())?);
+        }
+
+        Ok(expressions)
+    }
+}
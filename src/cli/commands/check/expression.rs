@@ -0,0 +1,175 @@
+use std::fmt;
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Operator {
+    // Two-chars operators must come first, so `>=` is not parsed as `>`
+    const ALL: &'static [(&'static str, Operator)] = &[
+        (">=", Operator::GreaterThanOrEqual),
+        ("<=", Operator::LessThanOrEqual),
+        ("==", Operator::Equal),
+        ("!=", Operator::NotEqual),
+        (">", Operator::GreaterThan),
+        ("<", Operator::LessThan),
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        Self::ALL
+            .iter()
+            .find(|(_, op)| op == self)
+            .map(|(symbol, _)| *symbol)
+            .unwrap_or_default()
+    }
+
+    pub fn matches(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Operator::GreaterThan => value > threshold,
+            Operator::GreaterThanOrEqual => value >= threshold,
+            Operator::LessThan => value < threshold,
+            Operator::LessThanOrEqual => value <= threshold,
+            Operator::Equal => value == threshold,
+            Operator::NotEqual => value != threshold,
+        }
+    }
+}
+
+/// A threshold expression such as `pipeline.*.flow.queue_backpressure>0.5`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdExpression {
+    pub path: Vec<String>,
+    pub operator: Operator,
+    pub threshold: f64,
+}
+
+impl fmt::Display for ThresholdExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.path.join("."),
+            self.operator.as_str(),
+            self.threshold
+        )
+    }
+}
+
+impl TryFrom<&str> for ThresholdExpression {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (position, symbol, operator) = Operator::ALL
+            .iter()
+            .filter_map(|(symbol, operator)| value.find(symbol).map(|i| (i, *symbol, *operator)))
+            .min_by_key(|(i, _, _)| *i)
+            .ok_or_else(|| format!("Invalid expression: {}! Missing operator", value))?;
+
+        let path = value[..position].trim();
+        if path.is_empty() {
+            return Err(format!(
+                "Invalid expression: {}! Missing metric path",
+                value
+            ));
+        }
+
+        let threshold = value[position + symbol.len()..]
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid expression: {}! Threshold must be a number", value))?;
+
+        Ok(ThresholdExpression {
+            path: path.split('.').map(|p| p.trim().to_string()).collect(),
+            operator,
+            threshold,
+        })
+    }
+}
+
+impl ThresholdExpression {
+    /// Resolves the expression path against the node stats, returning every
+    /// matching metric as a `(label, value)` pair. Wildcards (`*`) expand over
+    /// object keys and over arrays of objects (using their `id` as label).
+    /// Flow metrics resolve to their `current` value unless a window such as
+    /// `last_1_minute` is explicitly provided.
+    pub fn resolve(&self, node_stats: &Value) -> Vec<(String, f64)> {
+        let mut path = self.path.clone();
+
+        // `pipeline.<name>` reads better than `pipelines.<name>`, but the former
+        // is also the pipelines default settings object.
+        if path.len() > 1 && path[0] == "pipeline" {
+            let is_setting = node_stats
+                .get("pipeline")
+                .is_some_and(|p| p.get(&path[1]).is_some());
+            if !is_setting {
+                path[0] = "pipelines".to_string();
+            }
+        }
+
+        let mut results = vec![];
+        Self::resolve_path(node_stats, &path, vec![], &mut results);
+        results
+    }
+
+    fn resolve_path(
+        value: &Value,
+        path: &[String],
+        label: Vec<String>,
+        results: &mut Vec<(String, f64)>,
+    ) {
+        let Some((segment, remaining)) = path.split_first() else {
+            if let Some(number) = Self::as_number(value) {
+                results.push((label.join("."), number));
+            }
+            return;
+        };
+
+        let children: Vec<(String, &Value)> = match value {
+            Value::Object(map) => {
+                if segment == "*" {
+                    map.iter().map(|(k, v)| (k.to_string(), v)).collect()
+                } else {
+                    map.get(segment)
+                        .map(|v| vec![(segment.to_string(), v)])
+                        .unwrap_or_default()
+                }
+            }
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let id = v
+                        .get("id")
+                        .and_then(|id| id.as_str())
+                        .map(|id| id.to_string())
+                        .unwrap_or(i.to_string());
+                    (id, v)
+                })
+                .filter(|(id, _)| segment == "*" || id == segment)
+                .collect(),
+            _ => vec![],
+        };
+
+        for (key, child) in children {
+            let mut child_label = label.clone();
+            child_label.push(key);
+            Self::resolve_path(child, remaining, child_label, results);
+        }
+    }
+
+    fn as_number(value: &Value) -> Option<f64> {
+        match value {
+            Value::Number(number) => number.as_f64(),
+            Value::Object(map) => map.get("current").and_then(|p| p.as_f64()),
+            _ => None,
+        }
+    }
+}
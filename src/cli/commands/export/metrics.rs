@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+
+use crate::api::node::{NodeInfo, NodeInfoType, Vertex};
+use crate::api::stats::{FlowMetricValue, NodeStats, Plugin};
+use crate::api::Client;
+use crate::errors::AnyError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Gauge,
+    Counter,
+}
+
+impl MetricKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Gauge => "gauge",
+            MetricKind::Counter => "counter",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub labels: Vec<(&'static str, String)>,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub kind: MetricKind,
+    pub samples: Vec<Sample>,
+}
+
+/// Ordered set of metrics, grouping samples by metric name.
+#[derive(Debug, Default)]
+pub struct MetricSet {
+    metrics: Vec<Metric>,
+    index: HashMap<&'static str, usize>,
+}
+
+impl MetricSet {
+    pub fn metrics(&self) -> &[Metric] {
+        &self.metrics
+    }
+
+    pub fn gauge(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        labels: Vec<(&'static str, String)>,
+        value: f64,
+    ) {
+        self.add(name, help, MetricKind::Gauge, labels, value);
+    }
+
+    pub fn counter(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        labels: Vec<(&'static str, String)>,
+        value: f64,
+    ) {
+        self.add(name, help, MetricKind::Counter, labels, value);
+    }
+
+    fn add(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        kind: MetricKind,
+        labels: Vec<(&'static str, String)>,
+        value: f64,
+    ) {
+        let index = *self.index.entry(name).or_insert_with(|| {
+            self.metrics.push(Metric {
+                name,
+                help,
+                kind,
+                samples: vec![],
+            });
+            self.metrics.len() - 1
+        });
+
+        self.metrics[index].samples.push(Sample { labels, value });
+    }
+}
+
+pub(crate) fn fetch_node_data(client: &Client) -> Result<(NodeInfo, NodeStats), AnyError> {
+    let node_info = client.get_node_info(
+        &[NodeInfoType::Pipelines],
+        Some(Client::QUERY_NODE_INFO_GRAPH),
+    )?;
+    let node_stats = client.get_node_stats(None)?;
+    Ok((node_info, node_stats))
+}
+
+pub(crate) fn collect(node_info: &NodeInfo, node_stats: &NodeStats) -> MetricSet {
+    let mut set = MetricSet::default();
+
+    set.gauge(
+        "logstash_node_info",
+        "Logstash node information",
+        vec![
+            ("id", node_info.node.id.to_string()),
+            ("name", node_info.node.name.to_string()),
+            ("version", node_info.node.version.to_string()),
+        ],
+        1.0,
+    );
+
+    collect_node_metrics(&mut set, node_stats);
+    collect_flow_metrics(&mut set, node_stats);
+
+    let mut pipelines: Vec<_> = node_stats.pipelines.iter().collect();
+    pipelines.sort_by_key(|(name, _)| name.as_str());
+
+    for (pipeline_name, pipeline_stats) in pipelines {
+        let pipeline_info = node_info
+            .pipelines
+            .as_ref()
+            .and_then(|p| p.get(pipeline_name));
+        let pipeline_label = || vec![("pipeline", pipeline_name.to_string())];
+
+        if let Some(info) = pipeline_info {
+            set.gauge(
+                "logstash_pipeline_workers",
+                "Number of pipeline workers",
+                pipeline_label(),
+                info.workers as f64,
+            );
+            set.gauge(
+                "logstash_pipeline_batch_size",
+                "Pipeline batch size",
+                pipeline_label(),
+                info.batch_size as f64,
+            );
+        }
+
+        let events = &pipeline_stats.events;
+        set.counter(
+            "logstash_pipeline_events_in_total",
+            "Events received by the pipeline",
+            pipeline_label(),
+            events.r#in as f64,
+        );
+        set.counter(
+            "logstash_pipeline_events_filtered_total",
+            "Events filtered by the pipeline",
+            pipeline_label(),
+            events.filtered as f64,
+        );
+        set.counter(
+            "logstash_pipeline_events_out_total",
+            "Events sent out by the pipeline",
+            pipeline_label(),
+            events.out as f64,
+        );
+        set.counter(
+            "logstash_pipeline_events_duration_millis_total",
+            "Time spent processing events in the pipeline",
+            pipeline_label(),
+            events.duration_in_millis as f64,
+        );
+        set.counter(
+            "logstash_pipeline_events_queue_push_duration_millis_total",
+            "Time spent pushing events into the pipeline queue",
+            pipeline_label(),
+            events.queue_push_duration_in_millis as f64,
+        );
+        set.counter(
+            "logstash_pipeline_reloads_successes_total",
+            "Successful pipeline reloads",
+            pipeline_label(),
+            pipeline_stats.reloads.successes as f64,
+        );
+        set.counter(
+            "logstash_pipeline_reloads_failures_total",
+            "Failed pipeline reloads",
+            pipeline_label(),
+            pipeline_stats.reloads.failures as f64,
+        );
+
+        let flow = &pipeline_stats.flow;
+        for (name, help, metric) in [
+            (
+                "logstash_pipeline_flow_input_throughput",
+                "Pipeline input throughput (events/s)",
+                &flow.input_throughput,
+            ),
+            (
+                "logstash_pipeline_flow_filter_throughput",
+                "Pipeline filter throughput (events/s)",
+                &flow.filter_throughput,
+            ),
+            (
+                "logstash_pipeline_flow_output_throughput",
+                "Pipeline output throughput (events/s)",
+                &flow.output_throughput,
+            ),
+            (
+                "logstash_pipeline_flow_queue_backpressure",
+                "Pipeline queue backpressure",
+                &flow.queue_backpressure,
+            ),
+            (
+                "logstash_pipeline_flow_worker_concurrency",
+                "Pipeline worker concurrency",
+                &flow.worker_concurrency,
+            ),
+            (
+                "logstash_pipeline_flow_queue_persisted_growth_bytes",
+                "Pipeline persisted queue growth (bytes/s)",
+                &flow.queue_persisted_growth_bytes,
+            ),
+            (
+                "logstash_pipeline_flow_queue_persisted_growth_events",
+                "Pipeline persisted queue growth (events/s)",
+                &flow.queue_persisted_growth_events,
+            ),
+        ] {
+            set.gauge(name, help, pipeline_label(), metric.current);
+        }
+
+        let queue = &pipeline_stats.queue;
+        let queue_labels = || {
+            vec![
+                ("pipeline", pipeline_name.to_string()),
+                ("type", queue.r#type.to_string()),
+            ]
+        };
+        set.gauge(
+            "logstash_pipeline_queue_events",
+            "Events in the pipeline queue",
+            queue_labels(),
+            queue.events_count.max(queue.events) as f64,
+        );
+        set.gauge(
+            "logstash_pipeline_queue_size_bytes",
+            "Pipeline queue size",
+            queue_labels(),
+            queue.capacity.queue_size_in_bytes as f64,
+        );
+        set.gauge(
+            "logstash_pipeline_queue_max_size_bytes",
+            "Pipeline queue max size",
+            queue_labels(),
+            queue.capacity.max_queue_size_in_bytes as f64,
+        );
+
+        let vertices: HashMap<&str, &Vertex> = pipeline_info
+            .map(|p| {
+                p.graph
+                    .graph
+                    .vertices
+                    .iter()
+                    .map(|v| (v.id.as_str(), v))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut plugins: Vec<(String, (String, &Plugin))> =
+            pipeline_stats.plugins.all_with_type().into_iter().collect();
+        plugins.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (id, (plugin_type, plugin)) in plugins {
+            let plugin_labels = || {
+                vec![
+                    ("pipeline", pipeline_name.to_string()),
+                    ("plugin_id", id.to_string()),
+                    ("plugin_type", plugin_type.to_string()),
+                    (
+                        "plugin",
+                        plugin_display_name(&id, plugin, vertices.get(id.as_str())),
+                    ),
+                ]
+            };
+
+            set.counter(
+                "logstash_plugin_events_in_total",
+                "Events received by the plugin",
+                plugin_labels(),
+                plugin.events.r#in as f64,
+            );
+            set.counter(
+                "logstash_plugin_events_out_total",
+                "Events sent out by the plugin",
+                plugin_labels(),
+                plugin.events.out as f64,
+            );
+            set.counter(
+                "logstash_plugin_events_duration_millis_total",
+                "Time spent by the plugin processing events",
+                plugin_labels(),
+                plugin.events.duration_in_millis as f64,
+            );
+
+            if let Some(flow) = &plugin.flow {
+                for (name, help, metric) in [
+                    (
+                        "logstash_plugin_flow_throughput",
+                        "Plugin throughput (events/s)",
+                        &flow.throughput,
+                    ),
+                    (
+                        "logstash_plugin_flow_worker_utilization",
+                        "Plugin worker utilization (%)",
+                        &flow.worker_utilization,
+                    ),
+                    (
+                        "logstash_plugin_flow_worker_millis_per_event",
+                        "Plugin worker millis per event",
+                        &flow.worker_millis_per_event,
+                    ),
+                ] {
+                    if let Some(metric) = metric {
+                        set.gauge(name, help, plugin_labels(), metric.current);
+                    }
+                }
+            }
+        }
+    }
+
+    set
+}
+
+fn collect_node_metrics(set: &mut MetricSet, node_stats: &NodeStats) {
+    let events = &node_stats.events;
+    set.counter(
+        "logstash_node_events_in_total",
+        "Events received by the node",
+        vec![],
+        events.r#in as f64,
+    );
+    set.counter(
+        "logstash_node_events_filtered_total",
+        "Events filtered by the node",
+        vec![],
+        events.filtered as f64,
+    );
+    set.counter(
+        "logstash_node_events_out_total",
+        "Events sent out by the node",
+        vec![],
+        events.out as f64,
+    );
+    set.counter(
+        "logstash_node_reloads_successes_total",
+        "Successful reloads",
+        vec![],
+        node_stats.reloads.successes as f64,
+    );
+    set.counter(
+        "logstash_node_reloads_failures_total",
+        "Failed reloads",
+        vec![],
+        node_stats.reloads.failures as f64,
+    );
+
+    let jvm = &node_stats.jvm;
+    set.gauge(
+        "logstash_jvm_heap_used_bytes",
+        "JVM heap used",
+        vec![],
+        jvm.mem.heap_used_in_bytes as f64,
+    );
+    set.gauge(
+        "logstash_jvm_heap_committed_bytes",
+        "JVM heap committed",
+        vec![],
+        jvm.mem.heap_committed_in_bytes as f64,
+    );
+    set.gauge(
+        "logstash_jvm_heap_max_bytes",
+        "JVM heap max",
+        vec![],
+        jvm.mem.heap_max_in_bytes as f64,
+    );
+    set.gauge(
+        "logstash_jvm_heap_used_percent",
+        "JVM heap used percentage",
+        vec![],
+        jvm.mem.heap_used_percent as f64,
+    );
+    set.gauge(
+        "logstash_jvm_non_heap_used_bytes",
+        "JVM non heap used",
+        vec![],
+        jvm.mem.non_heap_used_in_bytes as f64,
+    );
+    set.gauge(
+        "logstash_jvm_non_heap_committed_bytes",
+        "JVM non heap committed",
+        vec![],
+        jvm.mem.non_heap_committed_in_bytes as f64,
+    );
+    set.gauge(
+        "logstash_jvm_threads",
+        "JVM live threads",
+        vec![],
+        jvm.threads.count as f64,
+    );
+    set.gauge(
+        "logstash_jvm_uptime_millis",
+        "JVM uptime",
+        vec![],
+        jvm.uptime_in_millis as f64,
+    );
+
+    let process = &node_stats.process;
+    set.gauge(
+        "logstash_process_cpu_percent",
+        "Process CPU usage",
+        vec![],
+        process.cpu.percent as f64,
+    );
+    set.counter(
+        "logstash_process_cpu_millis_total",
+        "Process CPU time",
+        vec![],
+        process.cpu.total_in_millis as f64,
+    );
+    set.gauge(
+        "logstash_process_open_file_descriptors",
+        "Process open file descriptors",
+        vec![],
+        process.open_file_descriptors as f64,
+    );
+    set.gauge(
+        "logstash_process_max_file_descriptors",
+        "Process max file descriptors",
+        vec![],
+        process.max_file_descriptors as f64,
+    );
+}
+
+fn collect_flow_metrics(set: &mut MetricSet, node_stats: &NodeStats) {
+    let flow = &node_stats.flow;
+    let metrics: [(&'static str, &'static str, &FlowMetricValue); 5] = [
+        (
+            "logstash_node_flow_input_throughput",
+            "Node input throughput (events/s)",
+            &flow.input_throughput,
+        ),
+        (
+            "logstash_node_flow_filter_throughput",
+            "Node filter throughput (events/s)",
+            &flow.filter_throughput,
+        ),
+        (
+            "logstash_node_flow_output_throughput",
+            "Node output throughput (events/s)",
+            &flow.output_throughput,
+        ),
+        (
+            "logstash_node_flow_queue_backpressure",
+            "Node queue backpressure",
+            &flow.queue_backpressure,
+        ),
+        (
+            "logstash_node_flow_worker_concurrency",
+            "Node worker concurrency",
+            &flow.worker_concurrency,
+        ),
+    ];
+
+    for (name, help, metric) in metrics {
+        set.gauge(name, help, vec![], metric.current);
+    }
+}
+
+fn plugin_display_name(id: &str, plugin: &Plugin, vertex: Option<&&Vertex>) -> String {
+    if let Some(vertex) = vertex {
+        if !vertex.config_name.is_empty() {
+            return vertex.config_name.to_string();
+        }
+    }
+
+    plugin.name.as_deref().unwrap_or(id).to_string()
+}
@@ -0,0 +1,3 @@
+mod metrics;
+pub mod otlp;
+pub mod prometheus;
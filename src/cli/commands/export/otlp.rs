@@ -0,0 +1,381 @@
+use std::collections::{HashMap, VecDeque};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::Args;
+use serde_json::{json, Value};
+use ureq::{Agent, AgentBuilder};
+
+use crate::api::node::Node;
+use crate::api::stats::NodeStats;
+use crate::commands::export::metrics::{collect, fetch_node_data, MetricKind, MetricSet, Sample};
+use crate::commands::traits::RunnableCommand;
+use crate::config::Config;
+use crate::errors::{AnyError, TuiError};
+use crate::output::Output;
+
+const MAX_PENDING_REQUESTS: usize = 100;
+const RETRY_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+// OTLP AggregationTemporality.AGGREGATION_TEMPORALITY_CUMULATIVE
+const AGGREGATION_TEMPORALITY_CUMULATIVE: i32 = 2;
+
+#[derive(Args)]
+pub struct OtlpArgs {
+    /// OTLP/HTTP metrics endpoint
+    #[arg(long, default_value = "http://localhost:4318/v1/metrics")]
+    pub endpoint: String,
+
+    /// Extra request header, e.g. 'Authorization=Bearer <token>'. Can be repeated
+    #[arg(long = "header")]
+    pub headers: Vec<String>,
+
+    /// Polling and pushing interval in seconds
+    #[arg(default_value_t = 10, short = 'i', long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+
+    /// Maximum number of data points sent per request
+    #[arg(long, default_value_t = 1000)]
+    pub max_batch_size: usize,
+
+    /// Maximum number of retries of a failed request
+    #[arg(long, default_value_t = 3)]
+    pub max_retries: u32,
+}
+
+pub struct OtlpCommand;
+
+impl RunnableCommand<OtlpArgs> for OtlpCommand {
+    fn run(&self, out: &mut Output, args: &OtlpArgs, config: &Config) -> Result<(), AnyError> {
+        if config.diagnostic_path.is_some() {
+            return Err(TuiError::from(
+                "The --diagnostic-path argument is not supported by the export command",
+            )
+            .into());
+        }
+
+        if args.max_batch_size == 0 {
+            return Err(TuiError::from("The --max-batch-size must be greater than 0").into());
+        }
+
+        let headers = OtlpCommand::parse_headers(&args.headers)?;
+        let exporter = OtlpExporter {
+            agent: AgentBuilder::new()
+                .user_agent(format!("tuistash/{}", env!("CARGO_PKG_VERSION")).as_str())
+                .timeout(Duration::from_secs(10))
+                .build(),
+            endpoint: args.endpoint.to_string(),
+            headers,
+            max_retries: args.max_retries,
+        };
+
+        let interval = Duration::from_secs(args.interval);
+        let mut pending: VecDeque<Value> = VecDeque::new();
+        let mut start_times = StartTimes::default();
+
+        writeln!(
+            out.handle,
+            "Pushing OTLP metrics to {} every {}s",
+            args.endpoint, args.interval
+        )?;
+        out.handle.flush()?;
+
+        loop {
+            match fetch_node_data(&config.api) {
+                Ok((node_info, node_stats)) => {
+                    let now = unix_nanos(SystemTime::now());
+                    start_times.update(&node_stats, now);
+
+                    let metrics = collect(&node_info, &node_stats);
+                    for request in create_export_requests(
+                        &node_info.node,
+                        &metrics,
+                        &start_times,
+                        now,
+                        args.max_batch_size,
+                    ) {
+                        if pending.len() >= MAX_PENDING_REQUESTS {
+                            pending.pop_front();
+                        }
+                        pending.push_back(request);
+                    }
+                }
+                Err(e) => {
+                    writeln!(out.handle, "Failed to fetch the Logstash metrics: {}", e)?;
+                }
+            }
+
+            // Oldest requests are sent first. A request that still fails after all retries
+            // stays pending until the next interval.
+            while let Some(request) = pending.front() {
+                match exporter.push(request) {
+                    Ok(_) => {
+                        pending.pop_front();
+                    }
+                    Err(ExportError::Retryable(e)) => {
+                        writeln!(
+                            out.handle,
+                            "Failed to push metrics, {} request(s) pending: {}",
+                            pending.len(),
+                            e
+                        )?;
+                        break;
+                    }
+                    Err(ExportError::Rejected(e)) => {
+                        writeln!(out.handle, "Metrics rejected by the collector: {}", e)?;
+                        pending.pop_front();
+                    }
+                }
+            }
+
+            out.handle.flush()?;
+            thread::sleep(interval);
+        }
+    }
+}
+
+impl OtlpCommand {
+    fn parse_headers(values: &[String]) -> Result<Vec<(String, String)>, AnyError> {
+        let mut headers = Vec::with_capacity(values.len());
+        for value in values {
+            match value.split_once('=') {
+                Some((name, value)) if !name.trim().is_empty() => {
+                    headers.push((name.trim().to_string(), value.trim().to_string()));
+                }
+                _ => {
+                    return Err(
+                        TuiError::from(format!("Invalid header: {}!", value).as_str()).into(),
+                    );
+                }
+            }
+        }
+
+        Ok(headers)
+    }
+}
+
+enum ExportError {
+    Retryable(String),
+    Rejected(String),
+}
+
+struct OtlpExporter {
+    agent: Agent,
+    endpoint: String,
+    headers: Vec<(String, String)>,
+    max_retries: u32,
+}
+
+impl OtlpExporter {
+    fn push(&self, request: &Value) -> Result<(), ExportError> {
+        let body = request.to_string();
+        let mut backoff = RETRY_INITIAL_BACKOFF;
+        let mut attempt = 0;
+
+        loop {
+            let mut http_request = self
+                .agent
+                .post(&self.endpoint)
+                .set("Content-Type", "application/json");
+
+            for (name, value) in &self.headers {
+                http_request = http_request.set(name, value);
+            }
+
+            let error = match http_request.send_string(&body) {
+                Ok(_) => return Ok(()),
+                Err(ureq::Error::Status(code, response)) => {
+                    let message =
+                        format!("{}: {}", code, response.into_string().unwrap_or_default());
+                    // https://opentelemetry.io/docs/specs/otlp/#retryable-response-codes
+                    if !matches!(code, 429 | 502 | 503 | 504) {
+                        return Err(ExportError::Rejected(message));
+                    }
+                    message
+                }
+                Err(e) => e.to_string(),
+            };
+
+            if attempt >= self.max_retries {
+                return Err(ExportError::Retryable(error));
+            }
+
+            attempt += 1;
+            thread::sleep(backoff);
+            backoff *= 2;
+        }
+    }
+}
+
+/// Start times of the cumulative series. They are kept between pushes, and only move
+/// forward when the node restarts or a pipeline is reloaded, so the backends can tell
+/// the counters resets apart.
+#[derive(Default)]
+struct StartTimes {
+    node: Option<u128>,
+    uptime_in_millis: u64,
+    last_time: u128,
+    /// Pipeline ephemeral ID and start time, by pipeline name
+    pipelines: HashMap<String, (Option<String>, u128)>,
+}
+
+impl StartTimes {
+    fn update(&mut self, node_stats: &NodeStats, now: u128) {
+        let uptime_in_millis = node_stats.jvm.uptime_in_millis;
+        let node_start = match self.node {
+            Some(start) if uptime_in_millis >= self.uptime_in_millis => start,
+            _ => {
+                let start = now.saturating_sub(Duration::from_millis(uptime_in_millis).as_nanos());
+                self.node = Some(start);
+                self.pipelines.clear();
+                start
+            }
+        };
+
+        self.pipelines
+            .retain(|name, _| node_stats.pipelines.contains_key(name));
+
+        for (name, pipeline_stats) in &node_stats.pipelines {
+            let ephemeral_id = &pipeline_stats.ephemeral_id;
+            match self.pipelines.get(name) {
+                Some((id, _)) if id == ephemeral_id => {}
+                // Reloaded or created since the previous push, or unknown since the node start
+                _ => {
+                    self.pipelines.insert(
+                        name.to_string(),
+                        (ephemeral_id.clone(), self.last_time.max(node_start)),
+                    );
+                }
+            }
+        }
+
+        self.uptime_in_millis = uptime_in_millis;
+        self.last_time = now;
+    }
+
+    fn get(&self, sample: &Sample) -> u128 {
+        sample
+            .labels
+            .iter()
+            .find(|(key, _)| *key == "pipeline")
+            .and_then(|(_, name)| self.pipelines.get(name))
+            .map(|(_, start)| *start)
+            .or(self.node)
+            .unwrap_or_default()
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default()
+}
+
+/// Converts the metrics into OTLP/HTTP JSON export requests, each one holding
+/// at most `max_batch_size` data points.
+fn create_export_requests(
+    node: &Node,
+    metrics: &MetricSet,
+    start_times: &StartTimes,
+    time_unix_nano: u128,
+    max_batch_size: usize,
+) -> Vec<Value> {
+    let mut requests = vec![];
+    let mut batch: Vec<(Value, MetricKind, Vec<Value>)> = vec![];
+    let mut batch_index: HashMap<&str, usize> = HashMap::new();
+    let mut batch_size = 0;
+
+    for metric in metrics.metrics() {
+        for sample in &metric.samples {
+            // JSON can't represent non-finite numbers
+            if !sample.value.is_finite() {
+                continue;
+            }
+
+            let attributes: Vec<Value> = sample
+                .labels
+                .iter()
+                .map(|(key, value)| json!({"key": key, "value": {"stringValue": value}}))
+                .collect();
+
+            let mut data_point = json!({
+                "attributes": attributes,
+                "timeUnixNano": time_unix_nano.to_string(),
+                "asDouble": sample.value,
+            });
+
+            let name = match metric.kind {
+                MetricKind::Gauge => metric.name,
+                MetricKind::Counter => {
+                    data_point["startTimeUnixNano"] = json!(start_times.get(sample).to_string());
+                    metric.name.trim_end_matches("_total")
+                }
+            };
+
+            let index = *batch_index.entry(metric.name).or_insert_with(|| {
+                batch.push((
+                    json!({"name": name, "description": metric.help}),
+                    metric.kind,
+                    Vec::new(),
+                ));
+                batch.len() - 1
+            });
+
+            batch[index].2.push(data_point);
+            batch_size += 1;
+
+            if batch_size >= max_batch_size {
+                requests.push(create_export_request(node, batch));
+                batch = vec![];
+                batch_index.clear();
+                batch_size = 0;
+            }
+        }
+    }
+
+    if batch_size > 0 {
+        requests.push(create_export_request(node, batch));
+    }
+
+    requests
+}
+
+fn create_export_request(node: &Node, batch: Vec<(Value, MetricKind, Vec<Value>)>) -> Value {
+    let otlp_metrics: Vec<Value> = batch
+        .into_iter()
+        .map(|(mut metric, kind, data_points)| {
+            match kind {
+                MetricKind::Gauge => {
+                    metric["gauge"] = json!({"dataPoints": data_points});
+                }
+                MetricKind::Counter => {
+                    metric["sum"] = json!({
+                        "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                        "isMonotonic": true,
+                        "dataPoints": data_points,
+                    });
+                }
+            }
+            metric
+        })
+        .collect();
+
+    json!({
+        "resourceMetrics": [{
+            "resource": {
+                "attributes": [
+                    {"key": "service.name", "value": {"stringValue": "logstash"}},
+                    {"key": "service.instance.id", "value": {"stringValue": node.id}},
+                    {"key": "service.version", "value": {"stringValue": node.version}},
+                    {"key": "logstash.node.id", "value": {"stringValue": node.id}},
+                    {"key": "logstash.node.name", "value": {"stringValue": node.name}},
+                    {"key": "logstash.node.version", "value": {"stringValue": node.version}},
+                ]
+            },
+            "scopeMetrics": [{
+                "scope": {"name": "tuistash", "version": env!("CARGO_PKG_VERSION")},
+                "metrics": otlp_metrics,
+            }]
+        }]
+    })
+}
@@ -0,0 +1,182 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
+
+use clap::Args;
+
+use crate::api::Client;
+use crate::commands::export::metrics::{collect, fetch_node_data, MetricSet};
+use crate::commands::traits::RunnableCommand;
+use crate::config::Config;
+use crate::errors::{AnyError, TuiError};
+use crate::output::Output;
+
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Args)]
+pub struct PrometheusArgs {
+    /// Address the metrics endpoint listens on
+    #[arg(long, default_value = "127.0.0.1:9198")]
+    pub listen: String,
+
+    /// Polling interval in seconds
+    #[arg(default_value_t = 5, short = 'i', long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+}
+
+pub struct PrometheusCommand;
+
+impl RunnableCommand<PrometheusArgs> for PrometheusCommand {
+    fn run(
+        &self,
+        out: &mut Output,
+        args: &PrometheusArgs,
+        config: &Config,
+    ) -> Result<(), AnyError> {
+        if config.diagnostic_path.is_some() {
+            return Err(TuiError::from(
+                "The --diagnostic-path argument is not supported by the export command",
+            )
+            .into());
+        }
+
+        let listener = TcpListener::bind(&args.listen)?;
+        let metrics = Arc::new(RwLock::new(format_metrics(&MetricSet::default(), false)));
+
+        start_polling(
+            config.api.clone(),
+            Duration::from_secs(args.interval),
+            metrics.clone(),
+        );
+
+        writeln!(
+            out.handle,
+            "Serving Prometheus metrics on http://{}{}",
+            listener.local_addr()?,
+            METRICS_PATH
+        )?;
+        out.handle.flush()?;
+
+        // Scrapes are served one at a time, the response is a copy of the latest poll
+        for stream in listener.incoming().flatten() {
+            _ = handle_connection(stream, &metrics);
+        }
+
+        Ok(())
+    }
+}
+
+fn start_polling(client: Client, interval: Duration, metrics: Arc<RwLock<String>>) {
+    thread::Builder::new()
+        .name("prometheus-exporter-poller".to_string())
+        .spawn(move || loop {
+            let formatted = match fetch_node_data(&client) {
+                Ok((node_info, node_stats)) => {
+                    format_metrics(&collect(&node_info, &node_stats), true)
+                }
+                Err(_) => format_metrics(&MetricSet::default(), false),
+            };
+
+            *metrics.write().unwrap() = formatted;
+            thread::sleep(interval);
+        })
+        .unwrap();
+}
+
+fn handle_connection(mut stream: TcpStream, metrics: &RwLock<String>) -> Result<(), AnyError> {
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Drain the request headers
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+
+    let (status, content_type, body) = if method != "GET" {
+        ("405 Method Not Allowed", "text/plain", "".to_string())
+    } else if path == METRICS_PATH {
+        ("200 OK", CONTENT_TYPE, metrics.read().unwrap().clone())
+    } else {
+        ("404 Not Found", "text/plain", "".to_string())
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Formats the metrics using the Prometheus text exposition format.
+fn format_metrics(set: &MetricSet, up: bool) -> String {
+    let mut output = String::new();
+    output.push_str("# HELP logstash_up Whether the last Logstash API poll succeeded\n");
+    output.push_str("# TYPE logstash_up gauge\n");
+    output.push_str(&format!("logstash_up {}\n", if up { 1 } else { 0 }));
+
+    for metric in set.metrics() {
+        output.push_str(&format!("# HELP {} {}\n", metric.name, metric.help));
+        output.push_str(&format!(
+            "# TYPE {} {}\n",
+            metric.name,
+            metric.kind.as_str()
+        ));
+
+        for sample in &metric.samples {
+            output.push_str(metric.name);
+            if !sample.labels.is_empty() {
+                let labels: Vec<String> = sample
+                    .labels
+                    .iter()
+                    .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+                    .collect();
+                output.push_str(&format!("{{{}}}", labels.join(",")));
+            }
+            output.push_str(&format!(" {}\n", format_value(sample.value)));
+        }
+    }
+
+    output
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
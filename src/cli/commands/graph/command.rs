@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use clap::Args;
+
+use crate::api::node::{NodeInfoType, Vertex};
+use crate::api::stats::PipelineStats;
+use crate::api::Client;
+use crate::commands::formatter::NumberFormatter;
+use crate::commands::graph::output::{GraphEdge, GraphNode, OutputFormat};
+use crate::commands::traits::RunnableCommand;
+use crate::commands::tui::pipelines::graph::PipelineGraph;
+use crate::config::Config;
+use crate::errors::{AnyError, TuiError};
+use crate::output::Output;
+
+#[derive(Args)]
+pub struct GraphArgs {
+    /// Name of the pipeline
+    #[arg(long)]
+    pub pipeline: String,
+
+    /// Valid values are 'dot', 'mermaid'
+    #[arg(short)]
+    pub output: Option<String>,
+
+    /// Annotates the vertices with their current throughput and worker utilization
+    #[arg(long, default_value_t = false)]
+    pub stats: bool,
+}
+
+pub struct GraphCommand;
+
+impl RunnableCommand<GraphArgs> for GraphCommand {
+    fn run(&self, out: &mut Output, args: &GraphArgs, config: &Config) -> Result<(), AnyError> {
+        let output_format = match &args.output {
+            None => OutputFormat::Dot,
+            Some(value) => OutputFormat::try_from(value.as_ref())?,
+        };
+
+        if config.diagnostic_path.is_some() {
+            return Err(TuiError::from(
+                "The --diagnostic-path argument is not supported by the get command",
+            )
+            .into());
+        }
+
+        let node_info = config.api.get_node_info(
+            &[NodeInfoType::Pipelines],
+            Some(Client::QUERY_NODE_INFO_GRAPH),
+        )?;
+
+        let pipeline_info = node_info
+            .pipelines
+            .as_ref()
+            .and_then(|p| p.get(&args.pipeline))
+            .ok_or_else(|| {
+                TuiError::from(format!("Pipeline {} not found", args.pipeline).as_str())
+            })?;
+
+        if pipeline_info.graph.graph.vertices.is_empty() {
+            return Err(TuiError::from(
+                "The pipeline graph is not available, it requires Logstash 7.3 or later",
+            )
+            .into());
+        }
+
+        let pipeline_stats = if args.stats {
+            let mut node_stats = config.api.get_node_stats(None)?;
+            node_stats.pipelines.remove(&args.pipeline)
+        } else {
+            None
+        };
+
+        let graph = PipelineGraph::from(&pipeline_info.graph.graph);
+        let (nodes, edges) = GraphCommand::create_nodes_edges(&graph, pipeline_stats.as_ref());
+
+        let formatted = output_format
+            .new_formatter()
+            .format(&args.pipeline, &nodes, &edges);
+
+        out.handle.write_all(formatted.as_bytes())?;
+        out.handle.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+impl GraphCommand {
+    /// Walks the graph from its heads, so the nodes are listed in the same order
+    /// they're declared on the pipeline configuration.
+    fn create_nodes_edges(
+        graph: &PipelineGraph,
+        stats: Option<&PipelineStats>,
+    ) -> (Vec<GraphNode>, Vec<GraphEdge>) {
+        let mut ordered: Vec<&str> = vec![];
+        let mut stack: Vec<&str> = graph.heads.iter().rev().copied().collect();
+        while let Some(vertex_id) = stack.pop() {
+            if ordered.contains(&vertex_id) || !graph.data.contains_key(vertex_id) {
+                continue;
+            }
+
+            ordered.push(vertex_id);
+            if let Some(neighbours) = graph.vertices.get(vertex_id) {
+                for edge in neighbours.iter().rev() {
+                    stack.push(edge.vertex_id);
+                }
+            }
+        }
+
+        // Vertices that aren't reachable from any input
+        let mut unreachable: Vec<&str> = graph
+            .data
+            .keys()
+            .filter(|id| !ordered.contains(id))
+            .copied()
+            .collect();
+        unreachable.sort();
+        ordered.extend(unreachable);
+
+        let node_ids: HashMap<&str, String> = ordered
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, format!("v{}", i)))
+            .collect();
+
+        let mut nodes = Vec::with_capacity(ordered.len());
+        let mut edges = vec![];
+
+        for vertex_id in &ordered {
+            let vertex = graph.data[vertex_id].value;
+            nodes.push(GraphNode {
+                id: node_ids[vertex_id].to_string(),
+                label: Self::vertex_label(vertex),
+                r#type: vertex.r#type.to_string(),
+                plugin_type: vertex.plugin_type.to_string(),
+                stats: stats.and_then(|s| Self::vertex_stats(vertex, s)),
+            });
+
+            for edge in graph.vertices.get(vertex_id).into_iter().flatten() {
+                let Some(to) = node_ids.get(edge.vertex_id) else {
+                    continue;
+                };
+
+                let label = match edge.when {
+                    Some(true) => Some(vertex.condition.to_string()),
+                    Some(false) => Some("else".to_string()),
+                    None => None,
+                };
+
+                edges.push(GraphEdge {
+                    from: node_ids[vertex_id].to_string(),
+                    to: to.to_string(),
+                    label,
+                });
+            }
+        }
+
+        (nodes, edges)
+    }
+
+    fn vertex_label(vertex: &Vertex) -> String {
+        match vertex.r#type.as_str() {
+            "if" => "if".to_string(),
+            "queue" => "queue".to_string(),
+            _ => {
+                if vertex.explicit_id {
+                    format!("{}\n({})", vertex.config_name, vertex.id)
+                } else {
+                    vertex.config_name.to_string()
+                }
+            }
+        }
+    }
+
+    fn vertex_stats(vertex: &Vertex, stats: &PipelineStats) -> Option<String> {
+        if vertex.r#type == "queue" {
+            let backpressure = stats.flow.queue_backpressure.current;
+            return Some(format!(
+                "backpressure: {}",
+                backpressure.strip_number_decimals(2)
+            ));
+        }
+
+        let flow = stats.plugins.get(&vertex.id)?.flow.as_ref()?;
+        if vertex.plugin_type == "input" {
+            return flow
+                .throughput
+                .as_ref()
+                .map(|t| format!("{} e/s", t.current.format_number().trim()));
+        }
+
+        let mut values = vec![];
+        if let Some(worker_utilization) = &flow.worker_utilization {
+            values.push(format!(
+                "{}% worker utilization",
+                worker_utilization.current.strip_number_decimals(2)
+            ));
+        }
+        if let Some(worker_millis_per_event) = &flow.worker_millis_per_event {
+            values.push(format!(
+                "{} ms/e",
+                worker_millis_per_event.current.strip_number_decimals(3)
+            ));
+        }
+
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.join(", "))
+        }
+    }
+}
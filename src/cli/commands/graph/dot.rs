@@ -0,0 +1,57 @@
+use crate::commands::graph::output::{GraphEdge, GraphFormatter, GraphNode};
+
+pub(crate) struct DotFormatter;
+
+impl GraphFormatter for DotFormatter {
+    fn format(&self, pipeline: &str, nodes: &[GraphNode], edges: &[GraphEdge]) -> String {
+        let mut lines = vec![
+            format!("digraph \"{}\" {{", escape(pipeline)),
+            "  rankdir=LR;".to_string(),
+            "  node [fontname=\"Helvetica\", fontsize=10];".to_string(),
+            "  edge [fontname=\"Helvetica\", fontsize=9];".to_string(),
+        ];
+
+        for node in nodes {
+            let (shape, color) = match (node.r#type.as_str(), node.plugin_type.as_str()) {
+                ("if", _) => ("diamond", "#f4cccc"),
+                ("queue", _) => ("cylinder", "#d9d9d9"),
+                (_, "input") => ("box", "#cfe2f3"),
+                (_, "filter") => ("box", "#fff2cc"),
+                (_, "output") => ("box", "#d9ead3"),
+                _ => ("box", "#ffffff"),
+            };
+
+            let mut label = escape(&node.label);
+            if let Some(stats) = &node.stats {
+                label.push_str(&format!("\\n{}", escape(stats)));
+            }
+
+            lines.push(format!(
+                "  {} [label=\"{}\", shape={}, style=filled, fillcolor=\"{}\"];",
+                node.id, label, shape, color
+            ));
+        }
+
+        for edge in edges {
+            match &edge.label {
+                Some(label) => lines.push(format!(
+                    "  {} -> {} [label=\"{}\"];",
+                    edge.from,
+                    edge.to,
+                    escape(label)
+                )),
+                None => lines.push(format!("  {} -> {};", edge.from, edge.to)),
+            }
+        }
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
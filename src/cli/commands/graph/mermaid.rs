@@ -0,0 +1,63 @@
+use crate::commands::graph::output::{GraphEdge, GraphFormatter, GraphNode};
+
+pub(crate) struct MermaidFormatter;
+
+impl GraphFormatter for MermaidFormatter {
+    fn format(&self, pipeline: &str, nodes: &[GraphNode], edges: &[GraphEdge]) -> String {
+        let mut lines = vec![
+            "---".to_string(),
+            format!("title: {}", yaml_string(pipeline)),
+            "---".to_string(),
+            "flowchart LR".to_string(),
+        ];
+
+        for node in nodes {
+            let mut label = escape(&node.label);
+            if let Some(stats) = &node.stats {
+                label.push_str(&format!("<br/><small>{}</small>", escape(stats)));
+            }
+
+            let node_text = match node.r#type.as_str() {
+                "if" => format!("{}{{\"{}\"}}", node.id, label),
+                "queue" => format!("{}[(\"{}\")]", node.id, label),
+                _ => format!("{}[\"{}\"]", node.id, label),
+            };
+
+            lines.push(format!("    {}", node_text));
+        }
+
+        for edge in edges {
+            match &edge.label {
+                Some(label) => lines.push(format!(
+                    "    {} -->|\"{}\"| {}",
+                    edge.from,
+                    escape(label),
+                    edge.to
+                )),
+                None => lines.push(format!("    {} --> {}", edge.from, edge.to)),
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+        .replace('\n', "<br/>")
+}
+
+/// Double-quoted YAML scalar, so the front matter stays valid whatever the pipeline id is.
+fn yaml_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+
+    format!("\"{}\"", escaped)
+}
@@ -0,0 +1,4 @@
+pub mod command;
+mod dot;
+mod mermaid;
+mod output;
@@ -0,0 +1,47 @@
+use crate::commands::graph::dot::DotFormatter;
+use crate::commands::graph::mermaid::MermaidFormatter;
+
+pub(crate) struct GraphNode {
+    pub id: String,
+    pub label: String,
+    pub r#type: String,
+    pub plugin_type: String,
+    pub stats: Option<String>,
+}
+
+pub(crate) struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub label: Option<String>,
+}
+
+pub trait GraphFormatter {
+    fn format(&self, pipeline: &str, nodes: &[GraphNode], edges: &[GraphEdge]) -> String;
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum OutputFormat {
+    Dot,
+    Mermaid,
+}
+
+impl TryFrom<&str> for OutputFormat {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "dot" => Ok(OutputFormat::Dot),
+            "mermaid" => Ok(OutputFormat::Mermaid),
+            _ => Err(format!("Invalid output format: {}!", value)),
+        }
+    }
+}
+
+impl OutputFormat {
+    pub fn new_formatter(&self) -> Box<dyn GraphFormatter> {
+        match self {
+            OutputFormat::Dot => Box::new(DotFormatter {}),
+            OutputFormat::Mermaid => Box::new(MermaidFormatter {}),
+        }
+    }
+}
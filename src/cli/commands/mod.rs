@@ -1,16 +1,30 @@
 use clap::Subcommand;
 
+use crate::commands::analyze::command::{AnalyzeArgs, AnalyzeCommand};
+use crate::commands::check::command::{CheckArgs, CheckCommand};
+use crate::commands::export::otlp::{OtlpArgs, OtlpCommand};
+use crate::commands::export::prometheus::{PrometheusArgs, PrometheusCommand};
+use crate::commands::graph::command::{GraphArgs, GraphCommand};
 use crate::commands::node::command::{NodeArgs, NodeCommand};
 use crate::commands::traits::RunnableCommand;
 use crate::commands::tui::command::{TuiArgs, TuiCommand};
+use crate::commands::view_config::command::{ViewConfigArgs, ViewConfigCommand};
+use crate::commands::watch::command::{WatchArgs, WatchCommand};
 use crate::config::Config;
 use crate::errors::AnyError;
 use crate::output::Output;
+use crate::ExitCode;
 
+mod analyze;
+mod check;
+mod export;
 mod formatter;
+mod graph;
 mod node;
 pub mod traits;
 mod tui;
+mod view_config;
+mod watch;
 
 #[derive(Subcommand)]
 pub enum Command {
@@ -19,12 +33,33 @@ pub enum Command {
     Get(GetCommands),
     /// Logstash TUI
     Tui(TuiArgs),
+    /// Checks the node stats against thresholds, following the Nagios plugin conventions
+    Check(CheckArgs),
+    /// Export the Logstash metrics to other monitoring systems
+    #[command(subcommand)]
+    Export(ExportCommands),
+    /// Displays the pipelines graphs of Logstash configuration files, without a running Logstash
+    ViewConfig(ViewConfigArgs),
+    /// Ranks the components that are likely limiting the pipelines throughput
+    Analyze(AnalyzeArgs),
+    /// Watches the node and captures the hot threads when the CPU usage spikes
+    Watch(WatchArgs),
 }
 
 #[derive(Subcommand)]
 pub enum GetCommands {
     /// Prints the current Logstash node information
     Node(NodeArgs),
+    /// Prints the pipeline graph as a Graphviz DOT or Mermaid diagram
+    Graph(GraphArgs),
+}
+
+#[derive(Subcommand)]
+pub enum ExportCommands {
+    /// Serves the Logstash metrics using the Prometheus text exposition format
+    Prometheus(PrometheusArgs),
+    /// Pushes the Logstash metrics to an OpenTelemetry collector using OTLP/HTTP (JSON)
+    Otlp(OtlpArgs),
 }
 
 impl Command {
@@ -32,12 +67,34 @@ impl Command {
         TuiCommand.run(out, &TuiArgs::default(), config)
     }
 
-    pub fn execute(&self, out: &mut Output, config: &Config) -> Result<(), AnyError> {
+    /// Prints the error that interrupted the command, returning the process exit code.
+    pub fn report_error(command: Option<&Command>, err: &AnyError) -> ExitCode {
+        match command {
+            Some(Command::Check(_)) => CheckCommand::report_error(err),
+            _ => {
+                println!("{}", err);
+                1
+            }
+        }
+    }
+
+    pub fn execute(&self, out: &mut Output, config: &Config) -> Result<ExitCode, AnyError> {
         match &self {
             Command::Get(subcommand) => match subcommand {
-                GetCommands::Node(args) => NodeCommand.run(out, args, config),
+                GetCommands::Node(args) => NodeCommand.run(out, args, config).map(|_| 0),
+                GetCommands::Graph(args) => GraphCommand.run(out, args, config).map(|_| 0),
+            },
+            Command::Tui(args) => TuiCommand.run(out, args, config).map(|_| 0),
+            Command::Check(args) => CheckCommand.run(out, args, config),
+            Command::Export(subcommand) => match subcommand {
+                ExportCommands::Prometheus(args) => {
+                    PrometheusCommand.run(out, args, config).map(|_| 0)
+                }
+                ExportCommands::Otlp(args) => OtlpCommand.run(out, args, config).map(|_| 0),
             },
-            Command::Tui(args) => TuiCommand.run(out, args, config),
+            Command::ViewConfig(args) => ViewConfigCommand.run(out, args, config).map(|_| 0),
+            Command::Analyze(args) => AnalyzeCommand.run(out, args, config).map(|_| 0),
+            Command::Watch(args) => WatchCommand.run(out, args, config).map(|_| 0),
         }
     }
 }
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use time::format_description::well_known::Rfc3339;
+use ureq::{Agent, AgentBuilder};
+
+use crate::commands::tui::alerts::state::Alert;
+use crate::errors::{AnyError, TuiError};
+
+type HookKey = (String, Vec<String>);
+
+/// Notifies the alerts state changes by running a local command and/or posting
+/// a JSON payload to a webhook. Hooks run on a background thread, so slow commands
+/// or endpoints don't block the UI.
+pub struct AlertHooks {
+    min_interval: Duration,
+    // Last state (firing or not) notified for each alert, and when
+    notified: HashMap<HookKey, (bool, Instant)>,
+    sender: Sender<Value>,
+    last_error: Arc<Mutex<Option<String>>>,
+}
+
+impl AlertHooks {
+    pub fn new(
+        command: Option<String>,
+        webhook: Option<String>,
+        min_interval: Duration,
+    ) -> Result<Self, AnyError> {
+        if let Some(url) = &webhook {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(
+                    TuiError::from(format!("Invalid alert webhook URL: {}", url).as_str()).into(),
+                );
+            }
+        }
+
+        let (sender, receiver) = channel::<Value>();
+        let last_error = Arc::new(Mutex::new(None));
+        let thread_last_error = Arc::clone(&last_error);
+        let agent = AgentBuilder::new()
+            .user_agent(format!("tuistash/{}", env!("CARGO_PKG_VERSION")).as_str())
+            .timeout(Duration::from_secs(10))
+            .build();
+
+        thread::Builder::new()
+            .name("alert-hooks".to_string())
+            .spawn(move || {
+                while let Ok(payload) = receiver.recv() {
+                    let mut errors = vec![];
+                    if let Some(command) = &command {
+                        if let Err(e) = run_command(command, &payload) {
+                            errors.push(format!("command: {}", e));
+                        }
+                    }
+
+                    if let Some(url) = &webhook {
+                        if let Err(e) = post_webhook(&agent, url, &payload) {
+                            errors.push(format!("webhook: {}", e));
+                        }
+                    }
+
+                    *thread_last_error.lock().unwrap() = if errors.is_empty() {
+                        None
+                    } else {
+                        Some(errors.join(", "))
+                    };
+                }
+            })?;
+
+        Ok(AlertHooks {
+            min_interval,
+            notified: HashMap::new(),
+            sender,
+            last_error,
+        })
+    }
+
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().unwrap().clone()
+    }
+
+    /// Sends the alerts that changed state since they were last notified. A state is only
+    /// notified once, and an alert isn't notified more than once within `min_interval`,
+    /// so flapping alerts are delivered with their latest state when the interval expires.
+    pub fn notify(&mut self, alerts: &[Alert], conditions: &HashMap<&str, String>, node: Value) {
+        let now = Instant::now();
+
+        // Alerts are sorted with the firing ones first, so resolved entries of the
+        // same rule and metric are ignored while it's firing again.
+        let mut latest: HashMap<HookKey, &Alert> = HashMap::new();
+        for alert in alerts {
+            latest
+                .entry((alert.rule.to_string(), alert.metric.clone()))
+                .or_insert(alert);
+        }
+
+        for (key, alert) in latest {
+            let firing = !alert.resolved;
+            let should_notify = match self.notified.get(&key) {
+                None => firing,
+                Some((notified_firing, notified_at)) => {
+                    *notified_firing != firing
+                        && now.duration_since(*notified_at) >= self.min_interval
+                }
+            };
+
+            if !should_notify {
+                continue;
+            }
+
+            let payload = json!({
+                "status": if firing { "firing" } else { "resolved" },
+                "rule": alert.rule,
+                "severity": alert.severity.as_str().to_lowercase(),
+                "condition": conditions.get(alert.rule.as_str()),
+                "metric": alert.metric_label(),
+                "value": alert.value,
+                "first_seen": alert.first_seen.format(&Rfc3339).unwrap_or_default(),
+                "last_seen": alert.last_seen.format(&Rfc3339).unwrap_or_default(),
+                "node": node,
+            });
+
+            if self.sender.send(payload).is_ok() {
+                self.notified.insert(key, (firing, now));
+            }
+        }
+    }
+}
+
+fn run_command(command: &str, payload: &Value) -> Result<(), AnyError> {
+    let (shell, shell_arg) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+
+    let env_value = |name: &str| {
+        payload[name]
+            .as_str()
+            .map(|v| v.to_string())
+            .unwrap_or(payload[name].to_string())
+    };
+
+    // The command output is discarded, as it would break the terminal UI
+    let mut child = Command::new(shell)
+        .arg(shell_arg)
+        .arg(command)
+        .env("TUISTASH_ALERT_STATUS", env_value("status"))
+        .env("TUISTASH_ALERT_RULE", env_value("rule"))
+        .env("TUISTASH_ALERT_SEVERITY", env_value("severity"))
+        .env("TUISTASH_ALERT_METRIC", env_value("metric"))
+        .env("TUISTASH_ALERT_VALUE", env_value("value"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // Commands are not required to read the payload
+        _ = stdin.write_all(payload.to_string().as_bytes());
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(TuiError::from(format!("exited with {}", status).as_str()).into());
+    }
+
+    Ok(())
+}
+
+fn post_webhook(agent: &Agent, url: &str, payload: &Value) -> Result<(), AnyError> {
+    match agent
+        .post(url)
+        .set("Content-Type", "application/json")
+        .send_string(&payload.to_string())
+    {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, _)) => {
+            Err(TuiError::from(format!("status code {}", code).as_str()).into())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
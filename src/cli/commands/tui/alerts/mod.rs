@@ -0,0 +1,4 @@
+pub(crate) mod hooks;
+pub(crate) mod rules;
+pub(crate) mod state;
+pub(crate) mod ui;
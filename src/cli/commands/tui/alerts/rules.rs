@@ -0,0 +1,243 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::commands::check::expression::{Operator, ThresholdExpression};
+use crate::errors::{AnyError, TuiError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertSeverity {
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertSeverity::Warning => "WARNING",
+            AlertSeverity::Critical => "CRITICAL",
+        }
+    }
+}
+
+impl TryFrom<&str> for AlertSeverity {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "warning" | "warn" => Ok(AlertSeverity::Warning),
+            "critical" | "crit" => Ok(AlertSeverity::Critical),
+            _ => Err(format!("Invalid alert severity: {}!", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertCondition {
+    Threshold { operator: Operator, threshold: f64 },
+    Increased,
+}
+
+/// A rule such as `high_heap critical: heap_used_percent > 85 for 60s`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    pub name: String,
+    pub severity: AlertSeverity,
+    /// Metric path, matched against the end of the node stats paths
+    pub metric: Vec<String>,
+    pub condition: AlertCondition,
+    /// How long a threshold must be crossed before firing, or how long an
+    /// `increased` alert keeps firing after the last increase
+    pub duration: Option<Duration>,
+}
+
+impl AlertRule {
+    pub fn condition_text(&self) -> String {
+        let mut text = match &self.condition {
+            AlertCondition::Threshold {
+                operator,
+                threshold,
+            } => format!(
+                "{} {} {}",
+                self.metric.join("."),
+                operator.as_str(),
+                threshold
+            ),
+            AlertCondition::Increased => format!("{} increased", self.metric.join(".")),
+        };
+
+        if let Some(duration) = self.duration {
+            text.push_str(&format!(" for {}", humantime::format_duration(duration)));
+        }
+
+        text
+    }
+
+    /// Returns the path and value of every node stats metric matching the rule metric.
+    /// Flow metrics resolve to their `current` value unless a window is provided.
+    pub fn resolve(&self, node_stats: &Value) -> Vec<(Vec<String>, f64)> {
+        let mut results = vec![];
+        self.resolve_value(node_stats, &mut vec![], &mut results);
+        results
+    }
+
+    fn resolve_value(
+        &self,
+        value: &Value,
+        path: &mut Vec<String>,
+        results: &mut Vec<(Vec<String>, f64)>,
+    ) {
+        if self.matches_path(path) {
+            if let Some(number) = as_number(value) {
+                results.push((path.clone(), number));
+                return;
+            }
+        }
+
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    path.push(key.to_string());
+                    self.resolve_value(child, path, results);
+                    path.pop();
+                }
+            }
+            // Plugins are listed as arrays, and are identified by their `id`
+            Value::Array(items) => {
+                for (i, child) in items.iter().enumerate() {
+                    let id = child
+                        .get("id")
+                        .and_then(|id| id.as_str())
+                        .map(|id| id.to_string())
+                        .unwrap_or(i.to_string());
+                    path.push(id);
+                    self.resolve_value(child, path, results);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn matches_path(&self, path: &[String]) -> bool {
+        path.len() >= self.metric.len()
+            && path[path.len() - self.metric.len()..]
+                .iter()
+                .zip(&self.metric)
+                .all(|(segment, expected)| expected == "*" || segment == expected)
+    }
+}
+
+impl TryFrom<&str> for AlertRule {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (head, condition) = value
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid alert rule: {}! Missing rule name", value))?;
+
+        let mut head = head.split_whitespace();
+        let name = head
+            .next()
+            .ok_or_else(|| format!("Invalid alert rule: {}! Missing rule name", value))?
+            .to_string();
+
+        let severity = match head.next() {
+            Some(severity) => AlertSeverity::try_from(severity)?,
+            None => AlertSeverity::Warning,
+        };
+
+        if head.next().is_some() {
+            return Err(format!(
+                "Invalid alert rule: {}! Rule names can't contain spaces",
+                value
+            ));
+        }
+
+        let (condition, duration) = match condition.rsplit_once(" for ") {
+            Some((condition, duration)) => {
+                let duration = humantime::parse_duration(duration.trim()).map_err(|_| {
+                    format!(
+                        "Invalid alert rule: {}! Invalid duration '{}'",
+                        value,
+                        duration.trim()
+                    )
+                })?;
+                (condition.trim(), Some(duration))
+            }
+            None => (condition.trim(), None),
+        };
+
+        let (metric, condition) = match condition.strip_suffix(" increased") {
+            Some(metric) => (
+                metric.trim().split('.').map(|p| p.to_string()).collect(),
+                AlertCondition::Increased,
+            ),
+            None => {
+                let expression = ThresholdExpression::try_from(condition)?;
+                (
+                    expression.path,
+                    AlertCondition::Threshold {
+                        operator: expression.operator,
+                        threshold: expression.threshold,
+                    },
+                )
+            }
+        };
+
+        Ok(AlertRule {
+            name,
+            severity,
+            metric,
+            condition,
+            duration,
+        })
+    }
+}
+
+/// Loads the alert rules file, one rule per line. Empty lines and lines starting
+/// with `#` are ignored.
+pub fn load_rules(path: &Path) -> Result<Vec<AlertRule>, AnyError> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        TuiError::from(format!("Failed to read the alert rules {}: {}", path.display(), e).as_str())
+    })?;
+
+    let mut rules = vec![];
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let rule = AlertRule::try_from(line).map_err(|e| {
+            TuiError::from(format!("{} ({}:{})", e, path.display(), index + 1).as_str())
+        })?;
+
+        if rules.iter().any(|r: &AlertRule| r.name == rule.name) {
+            return Err(TuiError::from(
+                format!(
+                    "Duplicate alert rule name '{}' ({}:{})",
+                    rule.name,
+                    path.display(),
+                    index + 1
+                )
+                .as_str(),
+            )
+            .into());
+        }
+
+        rules.push(rule);
+    }
+
+    Ok(rules)
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::Object(map) => map.get("current").and_then(|p| p.as_f64()),
+        _ => None,
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+use time::OffsetDateTime;
+
+use crate::commands::tui::alerts::hooks::AlertHooks;
+use crate::commands::tui::alerts::rules::{AlertCondition, AlertRule, AlertSeverity};
+use crate::commands::tui::app::{AppData, LiveData};
+use crate::commands::tui::events::EventsListener;
+use crate::commands::tui::widgets::StatefulTable;
+
+// Resolved alerts are kept on the list until this limit is reached
+const MAX_RESOLVED_ALERTS: usize = 100;
+
+pub struct Alert {
+    pub rule: String,
+    pub severity: AlertSeverity,
+    pub metric: Vec<String>,
+    pub value: f64,
+    pub first_seen: OffsetDateTime,
+    pub last_seen: OffsetDateTime,
+    pub resolved: bool,
+}
+
+impl Alert {
+    pub fn metric_label(&self) -> String {
+        self.metric.join(".")
+    }
+
+    fn pipeline(&self) -> Option<&str> {
+        match self.metric.as_slice() {
+            [root, pipeline, ..] if root == "pipelines" => Some(pipeline),
+            _ => None,
+        }
+    }
+
+    fn plugin(&self) -> Option<(&str, &str)> {
+        match self.metric.as_slice() {
+            [root, pipeline, plugins, _, plugin_id, ..]
+                if root == "pipelines" && plugins == "plugins" =>
+            {
+                Some((pipeline, plugin_id))
+            }
+            _ => None,
+        }
+    }
+}
+
+type AlertKey = (usize, Vec<String>);
+
+pub struct AlertsState {
+    pub rules: Vec<AlertRule>,
+    pub bell: bool,
+    pub hooks: Option<AlertHooks>,
+    pub alerts_table: StatefulTable<Alert>,
+    // Threshold rules: when the condition was first met
+    pending_since: HashMap<AlertKey, Instant>,
+    // Increased rules: last values and when they last increased
+    last_values: HashMap<AlertKey, f64>,
+    last_increased: HashMap<AlertKey, Instant>,
+}
+
+impl AlertsState {
+    pub fn new(rules: Vec<AlertRule>, bell: bool, hooks: Option<AlertHooks>) -> Self {
+        AlertsState {
+            rules,
+            bell,
+            hooks,
+            alerts_table: StatefulTable::new(),
+            pending_since: HashMap::new(),
+            last_values: HashMap::new(),
+            last_increased: HashMap::new(),
+        }
+    }
+
+    pub fn firing(&self) -> impl Iterator<Item = &Alert> {
+        self.alerts_table.items.iter().filter(|a| !a.resolved)
+    }
+
+    /// Highest severity of the firing alerts whose metric belongs to the pipeline.
+    pub fn pipeline_severity(&self, pipeline: &str) -> Option<AlertSeverity> {
+        self.firing()
+            .filter(|a| a.pipeline() == Some(pipeline))
+            .map(|a| a.severity)
+            .max()
+    }
+
+    pub fn plugin_severity(&self, pipeline: &str, plugin_id: &str) -> Option<AlertSeverity> {
+        self.firing()
+            .filter(|a| a.plugin() == Some((pipeline, plugin_id)))
+            .map(|a| a.severity)
+            .max()
+    }
+
+    /// Highest severity of the firing alerts that don't belong to any pipeline.
+    pub fn node_severity(&self) -> Option<AlertSeverity> {
+        self.firing()
+            .filter(|a| a.pipeline().is_none())
+            .map(|a| a.severity)
+            .max()
+    }
+
+    fn evaluate(&mut self, live_data: &LiveData) {
+        let Ok(node_stats) = serde_json::to_value(live_data.node_stats) else {
+            return;
+        };
+
+        let now = Instant::now();
+        let now_time = OffsetDateTime::now_local().unwrap_or(OffsetDateTime::now_utc());
+        let mut firing: HashSet<AlertKey> = HashSet::new();
+        let mut new_alerts = false;
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            for (metric, value) in rule.resolve(&node_stats) {
+                let key = (index, metric.clone());
+                let is_firing = match &rule.condition {
+                    AlertCondition::Threshold {
+                        operator,
+                        threshold,
+                    } => {
+                        if operator.matches(value, *threshold) {
+                            let since = self.pending_since.entry(key.clone()).or_insert(now);
+                            now.duration_since(*since) >= rule.duration.unwrap_or_default()
+                        } else {
+                            self.pending_since.remove(&key);
+                            false
+                        }
+                    }
+                    AlertCondition::Increased => {
+                        let last_value = self.last_values.insert(key.clone(), value);
+                        if last_value.is_some_and(|last| value > last) {
+                            self.last_increased.insert(key.clone(), now);
+                            true
+                        } else {
+                            self.last_increased.get(&key).is_some_and(|last| {
+                                now.duration_since(*last) < rule.duration.unwrap_or(Duration::ZERO)
+                            })
+                        }
+                    }
+                };
+
+                if !is_firing {
+                    continue;
+                }
+
+                match self
+                    .alerts_table
+                    .items
+                    .iter_mut()
+                    .find(|a| !a.resolved && a.rule == rule.name && a.metric == metric)
+                {
+                    Some(alert) => {
+                        alert.value = value;
+                        alert.last_seen = now_time;
+                    }
+                    None => {
+                        new_alerts = true;
+                        self.alerts_table.items.push(Alert {
+                            rule: rule.name.to_string(),
+                            severity: rule.severity,
+                            metric: metric.clone(),
+                            value,
+                            first_seen: now_time,
+                            last_seen: now_time,
+                            resolved: false,
+                        });
+                    }
+                }
+
+                firing.insert(key);
+            }
+        }
+
+        for alert in self.alerts_table.items.iter_mut().filter(|a| !a.resolved) {
+            let index = self.rules.iter().position(|r| r.name == alert.rule);
+            if !index.is_some_and(|i| firing.contains(&(i, alert.metric.clone()))) {
+                alert.resolved = true;
+            }
+        }
+
+        self.sort_alerts();
+
+        if let Some(hooks) = &mut self.hooks {
+            let conditions = self
+                .rules
+                .iter()
+                .map(|r| (r.name.as_str(), r.condition_text()))
+                .collect();
+
+            let node = json!({
+                "id": live_data.node_info.node.id,
+                "name": live_data.node_info.node.name,
+                "host": live_data.node_info.node.host,
+            });
+
+            hooks.notify(&self.alerts_table.items, &conditions, node);
+        }
+
+        if new_alerts && self.bell {
+            let mut stdout = io::stdout();
+            _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+        }
+    }
+
+    /// Firing alerts come first, by severity, then the most recently seen ones.
+    fn sort_alerts(&mut self) {
+        let selected = self
+            .alerts_table
+            .selected_item()
+            .map(|a| (a.rule.to_string(), a.metric.clone(), a.first_seen));
+
+        let items = &mut self.alerts_table.items;
+        items.sort_by(|a, b| {
+            a.resolved
+                .cmp(&b.resolved)
+                .then_with(|| b.severity.cmp(&a.severity))
+                .then_with(|| b.last_seen.cmp(&a.last_seen))
+                .then_with(|| a.rule.cmp(&b.rule))
+                .then_with(|| a.metric.cmp(&b.metric))
+        });
+
+        let firing_count = items.iter().filter(|a| !a.resolved).count();
+        items.truncate(firing_count + MAX_RESOLVED_ALERTS);
+
+        if let Some((rule, metric, first_seen)) = selected {
+            let index = items
+                .iter()
+                .position(|a| a.rule == rule && a.metric == metric && a.first_seen == first_seen);
+            self.alerts_table.select(index);
+        }
+    }
+}
+
+impl EventsListener for AlertsState {
+    // Rules are evaluated against the live data only
+    fn update(&mut self, _: &AppData) {}
+
+    fn live_update(&mut self, live_data: &LiveData) {
+        self.evaluate(live_data);
+    }
+
+    fn reset(&mut self) {
+        self.pending_since.clear();
+        self.last_values.clear();
+        self.last_increased.clear();
+    }
+
+    fn on_up(&mut self, _: &AppData) {
+        self.alerts_table.previous();
+    }
+
+    fn on_down(&mut self, _: &AppData) {
+        self.alerts_table.next();
+    }
+}
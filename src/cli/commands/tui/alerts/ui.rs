@@ -0,0 +1,196 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap};
+use ratatui::Frame;
+use time::format_description;
+
+use crate::commands::tui::alerts::rules::AlertSeverity;
+use crate::commands::tui::app::App;
+use crate::commands::tui::widgets::{
+    alert_marker, TABLE_HEADER_CELL_STYLE, TABLE_HEADER_ROW_STYLE, TABLE_SELECTED_ROW_STYLE,
+    TABLE_SELECTED_ROW_SYMBOL,
+};
+
+pub(crate) fn draw_alerts_tab(f: &mut Frame, app: &mut App, area: Rect) {
+    if app.alerts_state.rules.is_empty() {
+        f.render_widget(
+            Paragraph::new(
+                "No alert rules configured, use the --alert-rules option to set a rules file",
+            )
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().borders(Borders::ALL).title("Alerts"))
+            .wrap(Wrap { trim: true }),
+            area,
+        );
+        return;
+    }
+
+    let chunks = Layout::default()
+        .constraints(vec![Constraint::Percentage(70), Constraint::Percentage(30)])
+        .direction(Direction::Vertical)
+        .split(area);
+
+    draw_alerts_table(f, app, chunks[0]);
+    draw_rules_table(f, app, chunks[1]);
+}
+
+fn draw_alerts_table(f: &mut Frame, app: &mut App, area: Rect) {
+    let format = format_description::parse("[hour]:[minute]:[second]").unwrap();
+    let rows: Vec<Row> = app
+        .alerts_state
+        .alerts_table
+        .items
+        .iter()
+        .map(|alert| {
+            let (state, state_style) = if alert.resolved {
+                ("RESOLVED", Style::default().fg(Color::DarkGray))
+            } else {
+                (
+                    "FIRING",
+                    Style::default().fg(severity_color(alert.severity)),
+                )
+            };
+
+            Row::new(vec![
+                Cell::from(Span::styled(state, state_style)),
+                Cell::from(Span::styled(
+                    alert.severity.as_str(),
+                    Style::default().fg(severity_color(alert.severity)),
+                )),
+                Cell::from(alert.rule.to_string()),
+                Cell::from(alert.metric_label()),
+                Cell::from(alert.value.to_string()),
+                Cell::from(alert.first_seen.format(&format).unwrap_or_default()),
+                Cell::from(alert.last_seen.format(&format).unwrap_or_default()),
+            ])
+        })
+        .collect();
+
+    let headers = [
+        "State",
+        "Severity",
+        "Rule",
+        "Metric",
+        "Value",
+        "First seen",
+        "Last seen",
+    ];
+    let header_cells = headers
+        .iter()
+        .map(|h| Cell::from(*h).style(TABLE_HEADER_CELL_STYLE));
+
+    let header = Row::new(header_cells)
+        .style(TABLE_HEADER_ROW_STYLE)
+        .height(1);
+
+    let widths = vec![
+        Constraint::Length(8),      // State
+        Constraint::Length(8),      // Severity
+        Constraint::Percentage(15), // Rule
+        Constraint::Percentage(45), // Metric
+        Constraint::Percentage(10), // Value
+        Constraint::Length(10),     // First seen
+        Constraint::Length(10),     // Last seen
+    ];
+
+    let firing_count = app.alerts_state.firing().count();
+    let mut title = vec![Span::raw(format!(
+        "Alerts({}/{})",
+        firing_count,
+        app.alerts_state.alerts_table.items.len()
+    ))];
+    if let Some(severity) = app.alerts_state.firing().map(|a| a.severity).max() {
+        title.push(alert_marker(severity));
+    }
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Line::from(title)),
+        )
+        .column_spacing(2)
+        .row_highlight_style(TABLE_SELECTED_ROW_STYLE)
+        .highlight_symbol(TABLE_SELECTED_ROW_SYMBOL);
+
+    f.render_stateful_widget(table, area, &mut app.alerts_state.alerts_table.state);
+}
+
+fn draw_rules_table(f: &mut Frame, app: &App, area: Rect) {
+    let rows: Vec<Row> = app
+        .alerts_state
+        .rules
+        .iter()
+        .map(|rule| {
+            let firing = app
+                .alerts_state
+                .firing()
+                .filter(|a| a.rule == rule.name)
+                .count();
+
+            Row::new(vec![
+                Cell::from(rule.name.to_string()),
+                Cell::from(Span::styled(
+                    rule.severity.as_str(),
+                    Style::default().fg(severity_color(rule.severity)),
+                )),
+                Cell::from(rule.condition_text()),
+                Cell::from(firing.to_string()),
+            ])
+        })
+        .collect();
+
+    let headers = ["Name", "Severity", "Condition", "Firing"];
+    let header_cells = headers
+        .iter()
+        .map(|h| Cell::from(*h).style(TABLE_HEADER_CELL_STYLE));
+
+    let header = Row::new(header_cells)
+        .style(TABLE_HEADER_ROW_STYLE)
+        .height(1);
+
+    let widths = vec![
+        Constraint::Percentage(20), // Name
+        Constraint::Length(8),      // Severity
+        Constraint::Percentage(60), // Condition
+        Constraint::Length(6),      // Firing
+    ];
+
+    let mut title = vec![Span::raw(format!(
+        "Rules({})",
+        app.alerts_state.rules.len()
+    ))];
+    if app.alerts_state.bell {
+        title.push(Span::styled(
+            " - bell on",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    if let Some(error) = app.alerts_state.hooks.as_ref().and_then(|h| h.last_error()) {
+        title.push(Span::styled(
+            format!(" - hooks failed: {}", error),
+            Style::default().fg(Color::Red),
+        ));
+    }
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Line::from(title)),
+        )
+        .column_spacing(2);
+
+    f.render_widget(table, area);
+}
+
+fn severity_color(severity: AlertSeverity) -> Color {
+    match severity {
+        AlertSeverity::Warning => Color::LightYellow,
+        AlertSeverity::Critical => Color::Red,
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
 use std::sync::mpsc::RecvTimeoutError;
 use std::sync::mpsc::{channel, Sender};
 use std::sync::{Arc, RwLock};
@@ -8,24 +10,63 @@ use std::time::Duration;
 use crate::api::hot_threads::NodeHotThreads;
 use crate::api::node::NodeInfo;
 use crate::api::stats::NodeStats;
+use crate::commands::tui::alerts::state::AlertsState;
+use crate::commands::tui::charts::ChartOptions;
 use crate::commands::tui::data_decorator;
 use crate::commands::tui::data_fetcher::{DataFetcher, NodeData};
+use crate::commands::tui::derived_metrics::DerivedMetrics;
 use crate::commands::tui::events::EventsListener;
 use crate::commands::tui::flows::state::FlowsState;
+use crate::commands::tui::history::ChartsHistoryStore;
 use crate::commands::tui::node::state::NodeState;
+use crate::commands::tui::now_local_unix_timestamp;
 use crate::commands::tui::pipelines::state::PipelinesState;
+use crate::commands::tui::plugins::state::PluginsState;
+use crate::commands::tui::search::Search;
 use crate::commands::tui::shared_state::SharedState;
 use crate::commands::tui::threads::state::ThreadsState;
 use crate::commands::tui::widgets::TabsState;
+use crate::commands::watch::capture::HotThreadsCapture;
 use crate::errors::AnyError;
 use crossterm::event::{KeyCode, KeyEvent};
 
+type FetchedData = (NodeInfo, NodeStats, Option<NodeHotThreads>);
+
+// Snapshots fetched while paused are buffered until this limit is reached
+const MAX_PAUSED_SNAPSHOTS: usize = 300;
+
+// Same as the charts default number of data points, so every point has a snapshot
+const MAX_TIMELINE_SNAPSHOTS: usize = 120;
+
+/// Data displayed at a point of the timeline. The node info rarely changes, so it's shared
+/// by the snapshots until it does. The stats are shared with the applied data, and only
+/// the latest vertices rates are kept.
+struct TimelineSnapshot {
+    timestamp: i64,
+    node_info: Arc<NodeInfo>,
+    node_stats: Arc<NodeStats>,
+    hot_threads: Option<NodeHotThreads>,
+    derived_metrics: DerivedMetrics,
+}
+
+/// Latest fetched data, regardless of the data being displayed.
+pub(crate) struct LiveData<'a> {
+    pub node_info: &'a NodeInfo,
+    pub node_stats: &'a NodeStats,
+    pub hot_threads: Option<&'a NodeHotThreads>,
+}
+
 pub(crate) struct AppData {
     errored: bool,
     last_error_message: Option<String>,
-    node_info: Option<NodeInfo>,
-    node_stats: Option<NodeStats>,
+    node_info: Option<Arc<NodeInfo>>,
+    node_stats: Option<Arc<NodeStats>>,
     hot_threads: Option<NodeHotThreads>,
+    derived_metrics: DerivedMetrics,
+    paused: bool,
+    paused_snapshots: VecDeque<FetchedData>,
+    timeline: VecDeque<TimelineSnapshot>,
+    timeline_cursor: Option<usize>,
 }
 
 impl AppData {
@@ -36,12 +77,20 @@ impl AppData {
             node_stats: None,
             node_info: None,
             hot_threads: None,
+            derived_metrics: DerivedMetrics::default(),
+            paused: false,
+            paused_snapshots: VecDeque::new(),
+            timeline: VecDeque::new(),
+            timeline_cursor: None,
         }
     }
 
     fn reset(&mut self) {
         self.node_info = None;
         self.node_stats = None;
+        self.derived_metrics = DerivedMetrics::default();
+        self.timeline.clear();
+        self.timeline_cursor = None;
     }
 
     fn handle_error(&mut self, error: &AnyError) {
@@ -53,8 +102,9 @@ impl AppData {
     fn fetch_and_set(&mut self, data_fetcher: &dyn DataFetcher) {
         if let Ok(mut node_data) = data_fetcher.fetch_node_data(None) {
             data_decorator::decorate(&mut node_data.info, &mut node_data.stats);
-            self.node_info = Some(node_data.info);
-            self.node_stats = Some(node_data.stats);
+            self.derive_metrics(&node_data.info, &mut node_data.stats);
+            self.set_node_info(node_data.info);
+            self.node_stats = Some(Arc::new(node_data.stats));
         }
 
         if let Ok(hot_threads) = data_fetcher.fetch_hot_threads(None) {
@@ -62,9 +112,89 @@ impl AppData {
         }
     }
 
+    /// Applies the fetched data, or buffers it while paused.
+    fn receive(&mut self, values: FetchedData) {
+        if self.paused {
+            self.paused_snapshots.push_back(values);
+            if self.paused_snapshots.len() > MAX_PAUSED_SNAPSHOTS {
+                self.paused_snapshots.pop_front();
+            }
+            return;
+        }
+
+        self.apply(values);
+    }
+
+    fn apply(&mut self, values: FetchedData) {
+        let (node_info, mut node_stats, hot_threads) = values;
+        self.derive_metrics(&node_info, &mut node_stats);
+        self.set_node_info(node_info);
+        self.node_stats = Some(Arc::new(node_stats));
+        self.hot_threads = hot_threads;
+        self.errored = false;
+        self.last_error_message = None;
+    }
+
+    fn set_node_info(&mut self, node_info: NodeInfo) {
+        if self.node_info.as_deref() != Some(&node_info) {
+            self.node_info = Some(Arc::new(node_info));
+        }
+    }
+
+    fn derive_metrics(&mut self, node_info: &NodeInfo, node_stats: &mut NodeStats) {
+        self.derived_metrics.update(node_stats);
+        if !data_decorator::is_flow_api_available(&node_info.node.version) {
+            self.derived_metrics
+                .estimate_flow_metrics(node_info, node_stats);
+        }
+    }
+
+    /// Keeps a copy of the current data on the timeline, using the same timestamp the
+    /// charts data points get when the listeners are updated.
+    fn record_timeline_snapshot(&mut self) {
+        let (Some(node_info), Some(node_stats)) = (&self.node_info, &self.node_stats) else {
+            return;
+        };
+
+        self.timeline.push_back(TimelineSnapshot {
+            timestamp: now_local_unix_timestamp(),
+            node_info: Arc::clone(node_info),
+            node_stats: Arc::clone(node_stats),
+            hot_threads: self.hot_threads.clone(),
+            derived_metrics: self.derived_metrics.snapshot(),
+        });
+
+        if self.timeline.len() > MAX_TIMELINE_SNAPSHOTS {
+            self.timeline.pop_front();
+        }
+    }
+
+    /// The newest snapshot buffered while paused, or the applied data.
+    fn live_data(&self) -> Option<LiveData<'_>> {
+        match self.paused_snapshots.back() {
+            Some((node_info, node_stats, hot_threads)) => Some(LiveData {
+                node_info,
+                node_stats,
+                hot_threads: hot_threads.as_ref(),
+            }),
+            None => match (&self.node_info, &self.node_stats) {
+                (Some(node_info), Some(node_stats)) => Some(LiveData {
+                    node_info,
+                    node_stats,
+                    hot_threads: self.hot_threads.as_ref(),
+                }),
+                _ => None,
+            },
+        }
+    }
+
+    fn timeline_snapshot(&self) -> Option<&TimelineSnapshot> {
+        self.timeline_cursor.and_then(|i| self.timeline.get(i))
+    }
+
     fn get_fetched_data(
         data_fetcher: &dyn DataFetcher,
-        data_tx: Sender<(NodeInfo, NodeStats, Option<NodeHotThreads>)>,
+        data_tx: Sender<FetchedData>,
         error_tx: Sender<AnyError>,
     ) {
         let mut node_data: NodeData = match data_fetcher.fetch_node_data(None) {
@@ -93,15 +223,37 @@ impl AppData {
     }
 
     pub(crate) fn node_info(&self) -> Option<&NodeInfo> {
-        self.node_info.as_ref()
+        match self.timeline_snapshot() {
+            Some(snapshot) => Some(&snapshot.node_info),
+            None => self.node_info.as_deref(),
+        }
     }
 
     pub(crate) fn node_stats(&self) -> Option<&NodeStats> {
-        self.node_stats.as_ref()
+        match self.timeline_snapshot() {
+            Some(snapshot) => Some(&snapshot.node_stats),
+            None => self.node_stats.as_deref(),
+        }
     }
 
     pub(crate) fn hot_threads(&self) -> Option<&NodeHotThreads> {
-        self.hot_threads.as_ref()
+        match self.timeline_snapshot() {
+            Some(snapshot) => snapshot.hot_threads.as_ref(),
+            None => self.hot_threads.as_ref(),
+        }
+    }
+
+    /// Rates derived from the counters of the last sampling interval.
+    pub(crate) fn derived_metrics(&self) -> &DerivedMetrics {
+        match self.timeline_snapshot() {
+            Some(snapshot) => &snapshot.derived_metrics,
+            None => &self.derived_metrics,
+        }
+    }
+
+    /// Timestamp of the snapshot selected on the timeline, if it's not displaying the latest data.
+    pub(crate) fn timeline_timestamp(&self) -> Option<i64> {
+        self.timeline_snapshot().map(|s| s.timestamp)
     }
 
     pub(crate) fn errored(&self) -> bool {
@@ -111,6 +263,14 @@ impl AppData {
     pub(crate) fn last_error_message(&self) -> &Option<String> {
         &self.last_error_message
     }
+
+    pub(crate) fn paused(&self) -> bool {
+        self.paused
+    }
+
+    pub(crate) fn paused_snapshots_count(&self) -> usize {
+        self.paused_snapshots.len()
+    }
 }
 
 pub(crate) struct App<'a> {
@@ -123,9 +283,16 @@ pub(crate) struct App<'a> {
     pub pipelines_state: PipelinesState<'a>,
     pub flows_state: FlowsState,
     pub threads_state: ThreadsState,
+    pub alerts_state: AlertsState,
+    pub plugins_state: PluginsState,
     pub data: Arc<RwLock<AppData>>,
     pub host: String,
     pub sampling_interval: Option<Duration>,
+    /// Charts range, and timestamp selected on the timeline, refreshed before every draw
+    pub chart_options: ChartOptions,
+    /// Non-fatal startup warning, displayed on the footer
+    pub status_message: Option<String>,
+    charts_history: Option<ChartsHistoryStore>,
 }
 
 impl<'a> App<'a> {
@@ -133,21 +300,36 @@ impl<'a> App<'a> {
     pub const TAB_FLOWS: usize = 1;
     pub const TAB_THREADS: usize = 2;
     pub const TAB_NODE: usize = 3;
-
-    pub fn new(title: String, host: String, sampling_interval: Option<Duration>) -> App<'a> {
+    pub const TAB_ALERTS: usize = 4;
+    pub const TAB_PLUGINS: usize = 5;
+
+    pub fn new(
+        title: String,
+        host: String,
+        sampling_interval: Option<Duration>,
+        config_root: Option<PathBuf>,
+        alerts_state: AlertsState,
+        capture: Option<HotThreadsCapture>,
+        charts_history: Option<ChartsHistoryStore>,
+    ) -> App<'a> {
         App {
             title,
             sampling_interval,
             show_help: false,
             should_quit: false,
             tabs: TabsState::new(),
-            pipelines_state: PipelinesState::new(),
+            pipelines_state: PipelinesState::new(config_root),
             node_state: NodeState::new(),
             data: Arc::new(RwLock::new(AppData::new())),
             host,
             shared_state: SharedState::new(),
             flows_state: FlowsState::new(),
-            threads_state: ThreadsState::new(),
+            threads_state: ThreadsState::new(capture),
+            alerts_state,
+            plugins_state: PluginsState::new(),
+            chart_options: ChartOptions::default(),
+            status_message: None,
+            charts_history,
         }
     }
 
@@ -162,6 +344,14 @@ impl<'a> App<'a> {
 
     pub fn handle_key_event(&mut self, key: KeyEvent) {
         let selected_tab = &self.tabs.index.clone();
+        // The typed query must not trigger the shortcuts
+        if self.selected_tab_search().is_some_and(|s| s.editing) {
+            self.trigger_tab_event(selected_tab, |app_data, listener| {
+                listener.on_other(key, app_data);
+            });
+            return;
+        }
+
         match key.code {
             KeyCode::Left => {
                 self.trigger_tab_event(selected_tab, |app_data, listener| {
@@ -193,6 +383,10 @@ impl<'a> App<'a> {
                 self.trigger_tab_event(selected_tab, |app_data, listener| {
                     listener.on_enter(app_data);
                 });
+
+                if let Some((pipeline, vertex_id)) = self.plugins_state.open_requested.take() {
+                    self.open_pipeline_vertex(&pipeline, &vertex_id);
+                }
             }
             _ => {
                 self.trigger_tab_event(selected_tab, |app_data, listener| {
@@ -205,7 +399,7 @@ impl<'a> App<'a> {
     pub fn on_key(&mut self, c: char) {
         match c.to_lowercase().to_string().as_str() {
             "q" => {
-                self.on_esc();
+                self.should_quit = true;
             }
             "h" => {
                 let visible = !self.show_help;
@@ -217,26 +411,181 @@ impl<'a> App<'a> {
             "f" => {
                 self.select_tab(Self::TAB_FLOWS);
             }
+            // Cycles the search matches instead
+            "n" if self.selected_tab_search().is_some_and(|s| s.is_active()) => {}
             "n" => {
                 self.select_tab(Self::TAB_NODE);
             }
             "t" => {
                 self.select_tab(Self::TAB_THREADS);
             }
+            "e" => {
+                self.select_tab(Self::TAB_ALERTS);
+            }
+            "i" => {
+                self.select_tab(Self::TAB_PLUGINS);
+            }
+            " " => {
+                self.toggle_pause();
+            }
+            "," => {
+                self.step_back();
+            }
+            "r" => {
+                self.chart_options.range = self.chart_options.range.next();
+            }
+            "." => {
+                self.step_forward();
+            }
             _ => {}
         }
     }
 
+    /// Clears the selected tab search, or quits when there's none.
     pub fn on_esc(&mut self) {
+        if self
+            .selected_tab_search()
+            .is_some_and(|s| s.editing || s.is_active())
+        {
+            let key = KeyEvent::from(KeyCode::Esc);
+            self.trigger_tab_event(&self.tabs.index.clone(), |app_data, listener| {
+                listener.on_other(key, app_data);
+            });
+            return;
+        }
+
         self.should_quit = true;
     }
 
+    fn selected_tab_search(&self) -> Option<&Search> {
+        let listener: Option<&dyn EventsListener> = match self.tabs.index {
+            Self::TAB_PIPELINES => Some(&self.pipelines_state),
+            Self::TAB_FLOWS => Some(&self.flows_state),
+            Self::TAB_THREADS => Some(&self.threads_state),
+            Self::TAB_PLUGINS => Some(&self.plugins_state),
+            _ => None,
+        };
+
+        listener.and_then(|l| l.search())
+    }
+
+    /// Switches to the pipelines tab, selecting the pipeline vertex.
+    fn open_pipeline_vertex(&mut self, pipeline: &str, vertex_id: &str) {
+        self.select_tab(Self::TAB_PIPELINES);
+        self.pipelines_state
+            .select_vertex(pipeline, vertex_id, &self.data.read().unwrap());
+    }
+
+    /// Stops applying the fetched data, so the charts and tables stay still. The data
+    /// fetched meanwhile is buffered, and can be stepped through with `step_forward`.
+    /// On resume, only the latest buffered snapshot is applied, as the charts timestamps
+    /// are set when the data is applied.
+    pub fn toggle_pause(&mut self) {
+        if self.sampling_interval.is_none() {
+            return;
+        }
+
+        let resumed = {
+            let mut data = self.data.write().unwrap();
+            data.paused = !data.paused;
+            if data.paused {
+                false
+            } else {
+                data.timeline_cursor = None;
+                if let Some(latest) = data.paused_snapshots.pop_back() {
+                    data.apply(latest);
+                }
+                data.paused_snapshots.clear();
+                true
+            }
+        };
+
+        if resumed {
+            self.on_tick();
+        }
+    }
+
+    /// Moves the timeline cursor to the previous snapshot, pausing the live data.
+    pub fn step_back(&mut self) {
+        if self.sampling_interval.is_none() {
+            return;
+        }
+
+        let moved = {
+            let mut data = self.data.write().unwrap();
+            data.paused = true;
+            let cursor = match data.timeline_cursor {
+                Some(cursor) => cursor.checked_sub(1),
+                // The latest snapshot is the data being displayed
+                None => data.timeline.len().checked_sub(2),
+            };
+
+            let moved = cursor.is_some() && cursor != data.timeline_cursor;
+            if moved {
+                data.timeline_cursor = cursor;
+            }
+            moved
+        };
+
+        if moved {
+            self.trigger_timeline_changed();
+        }
+    }
+
+    /// Moves the timeline cursor to the next snapshot, or applies the oldest buffered
+    /// snapshot when it's already displaying the latest data.
+    pub fn step_forward(&mut self) {
+        let moved_cursor = {
+            let mut data = self.data.write().unwrap();
+            match data.timeline_cursor {
+                Some(cursor) => {
+                    data.timeline_cursor = if cursor + 2 < data.timeline.len() {
+                        Some(cursor + 1)
+                    } else {
+                        None
+                    };
+                    true
+                }
+                None => false,
+            }
+        };
+
+        if moved_cursor {
+            self.trigger_timeline_changed();
+            return;
+        }
+
+        let stepped = {
+            let mut data = self.data.write().unwrap();
+            match data.paused_snapshots.pop_front() {
+                Some(snapshot) if data.paused => {
+                    data.apply(snapshot);
+                    data.record_timeline_snapshot();
+                    true
+                }
+                _ => false,
+            }
+        };
+
+        if stepped {
+            self.trigger_states_event(|listener, app_data| {
+                listener.update(app_data);
+            });
+        }
+    }
+
+    fn trigger_timeline_changed(&mut self) {
+        self.trigger_states_event(|listener, app_data| {
+            listener.timeline_changed(app_data);
+        });
+    }
+
     pub fn set_data(&mut self, data_fetcher: &dyn DataFetcher) {
         self.data.write().unwrap().fetch_and_set(data_fetcher);
     }
 
     pub fn start_reading_data(&self, data_fetcher: Box<dyn DataFetcher>, interval: Duration) {
-        let (data_tx, data_rx) = channel::<(NodeInfo, NodeStats, Option<NodeHotThreads>)>();
+        let (data_tx, data_rx) = channel::<FetchedData>();
         let (error_tx, error_rx) = channel::<AnyError>();
 
         thread::Builder::new()
@@ -252,12 +601,7 @@ impl<'a> App<'a> {
             .name("app-data-fetched-data-receiver".to_string())
             .spawn(move || loop {
                 if let Ok(values) = data_rx.recv() {
-                    let mut data = data.write().unwrap();
-                    data.node_info = Some(values.0);
-                    data.node_stats = Some(values.1);
-                    data.hot_threads = values.2;
-                    data.errored = false;
-                    data.last_error_message = None;
+                    data.write().unwrap().receive(values);
                 }
                 sleep(interval);
             })
@@ -268,7 +612,11 @@ impl<'a> App<'a> {
             .name("app-data-fetch-api-errors".to_string())
             .spawn(move || loop {
                 if let Ok(values) = error_rx.recv() {
-                    data.write().unwrap().handle_error(&values);
+                    let mut data = data.write().unwrap();
+                    // Keeps the paused data on the screen
+                    if !data.paused {
+                        data.handle_error(&values);
+                    }
                 }
                 sleep(interval);
             })
@@ -291,16 +639,92 @@ impl<'a> App<'a> {
     }
 
     pub fn on_tick(&mut self) {
-        {
-            if self.data.read().unwrap().errored {
+        let (paused, errored) = {
+            let data = self.data.read().unwrap();
+            (data.paused, data.errored)
+        };
+
+        // Pausing freezes only the displayed data
+        if !errored {
+            self.trigger_live_update();
+        }
+
+        if !paused {
+            if errored {
                 self.reset();
                 return;
             }
+
+            self.data.write().unwrap().record_timeline_snapshot();
+
+            self.trigger_states_event(|listener, app_data| {
+                listener.update(app_data);
+            });
         }
 
-        self.trigger_states_event(|listener, app_data| {
-            listener.update(app_data);
-        });
+        if self
+            .charts_history
+            .as_ref()
+            .is_some_and(|history| history.should_save())
+        {
+            self.save_charts_history();
+        }
+    }
+
+    fn current_node_id(&self) -> Option<String> {
+        self.data
+            .read()
+            .unwrap()
+            .node_info()
+            .map(|info| info.node.id.to_string())
+    }
+
+    /// Restores the charts series saved by a previous session connected to the same node.
+    pub fn load_charts_history(&mut self) {
+        let Some(node_id) = self.current_node_id() else {
+            return;
+        };
+
+        let Some(history) = self
+            .charts_history
+            .as_ref()
+            .and_then(|store| store.load(&node_id))
+        else {
+            return;
+        };
+
+        self.node_state = history.node;
+        self.shared_state
+            .restore_pipelines_chart_states(history.pipelines);
+    }
+
+    pub fn save_charts_history(&mut self) {
+        let Some(node_id) = self.current_node_id() else {
+            return;
+        };
+
+        if let Some(store) = &mut self.charts_history {
+            // Failing to persist the history must not interrupt the monitoring
+            let _ = store.save(
+                &node_id,
+                &self.node_state,
+                self.shared_state.pipelines_chart_states(),
+            );
+        }
+    }
+
+    fn trigger_live_update(&mut self) {
+        let data = self.data.read().unwrap();
+        let Some(live_data) = data.live_data() else {
+            return;
+        };
+
+        let listeners: Vec<&mut dyn EventsListener> =
+            vec![&mut self.threads_state, &mut self.alerts_state];
+
+        for listener in listeners {
+            listener.live_update(&live_data);
+        }
     }
 
     fn trigger_states_event(&mut self, func: impl Fn(&mut dyn EventsListener, &AppData)) {
@@ -310,6 +734,8 @@ impl<'a> App<'a> {
             &mut self.flows_state,
             &mut self.node_state,
             &mut self.threads_state,
+            &mut self.alerts_state,
+            &mut self.plugins_state,
         ];
 
         for listener in listeners {
@@ -335,6 +761,8 @@ impl<'a> App<'a> {
             Self::TAB_NODE => Some(&mut self.node_state),
             Self::TAB_FLOWS => Some(&mut self.flows_state),
             Self::TAB_THREADS => Some(&mut self.threads_state),
+            Self::TAB_ALERTS => Some(&mut self.alerts_state),
+            Self::TAB_PLUGINS => Some(&mut self.plugins_state),
             _ => None,
         };
 
@@ -1,8 +1,14 @@
+use crate::commands::tui::alerts::hooks::AlertHooks;
+use crate::commands::tui::alerts::rules::load_rules;
+use crate::commands::tui::alerts::state::AlertsState;
 use crate::commands::tui::app::App;
-use crate::commands::tui::data_fetcher::{ApiDataFetcher, PathDataFetcher};
+use crate::commands::tui::command::TuiArgs;
+use crate::commands::tui::data_fetcher::{ApiDataFetcher, DataFetcher, PathDataFetcher};
+use crate::commands::tui::history::ChartsHistoryStore;
 use crate::commands::tui::ui;
+use crate::commands::watch::capture::HotThreadsCapture;
 use crate::config::Config;
-use crate::errors::AnyError;
+use crate::errors::{AnyError, TuiError};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
@@ -15,47 +21,117 @@ use ratatui::{
 use std::ops::Add;
 use std::{
     io,
+    io::Stdout,
     time::{Duration, Instant},
 };
 
 const APP_TITLE: &str = "Logstash";
 
-pub fn run(interval: Duration, config: &Config) -> Result<(), AnyError> {
-    enable_raw_mode()?;
-
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+pub fn run(interval: Duration, args: &TuiArgs, config: &Config) -> Result<(), AnyError> {
+    if let Some(path) = &config.diagnostic_path {
+        if args.capture.capture_dir.is_some() {
+            return Err(TuiError::from(
+                "The --capture-dir argument is not supported with the --diagnostic-path argument",
+            )
+            .into());
+        }
 
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-    terminal.clear()?;
+        let file_data_fetcher = PathDataFetcher::new(path.to_string())?;
+        return run_with_static_data(path.to_string(), &file_data_fetcher, args);
+    }
 
-    if let Some(path) = &config.diagnostic_path {
-        let path = path.to_string();
-        match PathDataFetcher::new(path.to_string()) {
-            Ok(file_data_fetcher) => {
-                let mut app = App::new(APP_TITLE.to_string(), path, None);
-                app.set_data(&file_data_fetcher);
-                run_app(&mut terminal, app)?;
-            }
-            Err(err) => {
-                return Err(err);
-            }
-        };
+    let alerts_state = new_alerts_state(args)?;
+    let capture = HotThreadsCapture::new(&args.capture)?;
+    let (charts_history, status_message) = if args.no_charts_history {
+        (None, None)
     } else {
-        let app = App::new(
+        // The charts history is optional, failing to set it up must not prevent monitoring
+        match ChartsHistoryStore::new(
+            args.charts_history_dir.clone(),
+            Duration::from_secs(args.charts_history_retention),
+        ) {
+            Ok(store) => (store, None),
+            Err(e) => (None, Some(format!("{}, charts history disabled", e))),
+        }
+    };
+    with_terminal(|terminal| {
+        let mut app = App::new(
             APP_TITLE.to_string(),
             config.api.base_url().to_string(),
             Some(interval),
+            args.config_root.clone(),
+            alerts_state,
+            capture,
+            charts_history,
         );
+        app.status_message = status_message;
 
         let fetcher = ApiDataFetcher::new(config.api.clone());
         fetcher.start_polling(interval);
 
         app.start_reading_data(Box::new(fetcher), interval);
-        run_app(&mut terminal, app)?;
+        run_app(terminal, app)?;
+        Ok(())
+    })
+}
+
+/// Runs the app with data that is fetched only once, and never refreshed.
+pub fn run_with_static_data(
+    source: String,
+    data_fetcher: &dyn DataFetcher,
+    args: &TuiArgs,
+) -> Result<(), AnyError> {
+    let alerts_state = new_alerts_state(args)?;
+    with_terminal(|terminal| {
+        let mut app = App::new(
+            APP_TITLE.to_string(),
+            source,
+            None,
+            args.config_root.clone(),
+            alerts_state,
+            None,
+            None,
+        );
+        app.set_data(data_fetcher);
+        run_app(terminal, app)?;
+        Ok(())
+    })
+}
+
+fn new_alerts_state(args: &TuiArgs) -> Result<AlertsState, AnyError> {
+    let rules = match &args.alert_rules {
+        Some(path) => load_rules(path)?,
+        None => vec![],
     };
 
+    let hooks = if args.alert_command.is_some() || args.alert_webhook.is_some() {
+        Some(AlertHooks::new(
+            args.alert_command.clone(),
+            args.alert_webhook.clone(),
+            Duration::from_secs(args.alert_hooks_interval),
+        )?)
+    } else {
+        None
+    };
+
+    Ok(AlertsState::new(rules, args.alert_bell, hooks))
+}
+
+fn with_terminal<F>(f: F) -> Result<(), AnyError>
+where
+    F: FnOnce(&mut Terminal<CrosstermBackend<Stdout>>) -> Result<(), AnyError>,
+{
+    enable_raw_mode()?;
+
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+    terminal.clear()?;
+
+    let result = f(&mut terminal);
+
     disable_raw_mode()?;
 
     execute!(
@@ -65,11 +141,12 @@ pub fn run(interval: Duration, config: &Config) -> Result<(), AnyError> {
     )?;
 
     terminal.show_cursor()?;
-    Ok(())
+    result
 }
 
 fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
     app.wait_node_data();
+    app.load_charts_history();
     app.on_tick();
 
     let mut last_tick = Instant::now();
@@ -98,6 +175,7 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<(
             app.on_tick();
         }
         if app.should_quit {
+            app.save_charts_history();
             return Ok(());
         }
 
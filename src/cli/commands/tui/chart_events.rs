@@ -0,0 +1,122 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::commands::tui::app::AppData;
+use crate::commands::tui::now_local_unix_timestamp;
+
+const MAX_CHART_EVENTS: usize = 100;
+// Same as the longest charts range, older events are never displayed
+const MAX_CHART_EVENTS_AGE_SECONDS: i64 = 6 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChartEventKind {
+    Restart,
+    Reload,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ChartEvent {
+    pub timestamp: i64,
+    pub kind: ChartEventKind,
+    /// Reloaded pipeline, or `None` for node events
+    pub pipeline: Option<String>,
+    pub label: String,
+}
+
+#[derive(Default)]
+struct PipelineMarks {
+    ephemeral_id: Option<String>,
+    reloads_successes: i64,
+    reloads_failures: i64,
+}
+
+/// Detects the node restarts and the pipelines reloads by comparing the fetched
+/// stats with the previous ones, so they can be marked on the charts.
+#[derive(Default)]
+pub(crate) struct ChartEvents {
+    events: VecDeque<ChartEvent>,
+    node_ephemeral_id: Option<String>,
+    jvm_uptime_in_millis: Option<u64>,
+    pipelines: HashMap<String, PipelineMarks>,
+}
+
+impl ChartEvents {
+    pub fn events(&self) -> &VecDeque<ChartEvent> {
+        &self.events
+    }
+
+    pub fn update(&mut self, app_data: &AppData) {
+        let (Some(node_info), Some(node_stats)) = (app_data.node_info(), app_data.node_stats())
+        else {
+            return;
+        };
+
+        let timestamp = now_local_unix_timestamp();
+        let ephemeral_id_changed = self
+            .node_ephemeral_id
+            .as_ref()
+            .is_some_and(|id| *id != node_info.node.ephemeral_id);
+        let uptime_reset = self
+            .jvm_uptime_in_millis
+            .is_some_and(|uptime| node_stats.jvm.uptime_in_millis < uptime);
+
+        self.node_ephemeral_id = Some(node_info.node.ephemeral_id.to_string());
+        self.jvm_uptime_in_millis = Some(node_stats.jvm.uptime_in_millis);
+
+        let restarted = ephemeral_id_changed || uptime_reset;
+        if restarted {
+            self.push(ChartEvent {
+                timestamp,
+                kind: ChartEventKind::Restart,
+                pipeline: None,
+                label: "Restart".to_string(),
+            });
+        }
+
+        self.pipelines
+            .retain(|name, _| node_stats.pipelines.contains_key(name));
+
+        for (name, pipeline_stats) in &node_stats.pipelines {
+            let current = PipelineMarks {
+                ephemeral_id: pipeline_stats.ephemeral_id.clone(),
+                reloads_successes: pipeline_stats.reloads.successes,
+                reloads_failures: pipeline_stats.reloads.failures,
+            };
+
+            let Some(previous) = self.pipelines.insert(name.to_string(), current) else {
+                continue;
+            };
+
+            // Restarts also change the pipelines ephemeral ids and reset their counters
+            if restarted {
+                continue;
+            }
+
+            let label = if pipeline_stats.reloads.failures > previous.reloads_failures {
+                format!("Reload failed {}", name)
+            } else if pipeline_stats.reloads.successes > previous.reloads_successes
+                || (previous.ephemeral_id.is_some()
+                    && previous.ephemeral_id != pipeline_stats.ephemeral_id)
+            {
+                format!("Reload {}", name)
+            } else {
+                continue;
+            };
+
+            self.push(ChartEvent {
+                timestamp,
+                kind: ChartEventKind::Reload,
+                pipeline: Some(name.to_string()),
+                label,
+            });
+        }
+    }
+
+    fn push(&mut self, event: ChartEvent) {
+        let oldest_timestamp = event.timestamp - MAX_CHART_EVENTS_AGE_SECONDS;
+        self.events.retain(|e| e.timestamp >= oldest_timestamp);
+        if self.events.len() >= MAX_CHART_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+}
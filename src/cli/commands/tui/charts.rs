@@ -1,5 +1,10 @@
+use crate::commands::tui::chart_events::{ChartEvent, ChartEventKind};
 use humansize::{format_size_i, DECIMAL};
+use ratatui::style::{Color, Style};
+use ratatui::symbols;
 use ratatui::text::Span;
+use ratatui::widgets::{Dataset, GraphType};
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use time::{format_description, OffsetDateTime, UtcOffset};
 
@@ -44,19 +49,336 @@ pub(crate) fn create_chart_percentage_label_spans<'a>(label_values: Vec<f64>) ->
         .collect()
 }
 
-pub trait ChartDataPoint {
+/// Options shared by every chart, selected on the app level.
+#[derive(Default, Clone)]
+pub(crate) struct ChartOptions {
+    pub range: ChartRange,
+    /// Timestamp selected on the timeline, if it's not displaying the latest data
+    pub timeline_timestamp: Option<i64>,
+    /// Node restarts and pipelines reloads marked on the charts
+    pub events: Vec<ChartEvent>,
+}
+
+impl ChartOptions {
+    /// Options for a pipeline chart, keeping only the node events and the pipeline reloads.
+    pub fn for_pipeline(&self, pipeline: &str) -> ChartOptions {
+        ChartOptions {
+            events: self
+                .events
+                .iter()
+                .filter(|e| e.pipeline.as_ref().is_none_or(|p| p == pipeline))
+                .cloned()
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChartRange {
+    #[default]
+    TwoMinutes,
+    FifteenMinutes,
+    OneHour,
+    SixHours,
+}
+
+impl ChartRange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChartRange::TwoMinutes => "2m",
+            ChartRange::FifteenMinutes => "15m",
+            ChartRange::OneHour => "1h",
+            ChartRange::SixHours => "6h",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ChartRange::TwoMinutes => ChartRange::FifteenMinutes,
+            ChartRange::FifteenMinutes => ChartRange::OneHour,
+            ChartRange::OneHour => ChartRange::SixHours,
+            ChartRange::SixHours => ChartRange::TwoMinutes,
+        }
+    }
+
+    fn seconds(&self) -> i64 {
+        match self {
+            ChartRange::TwoMinutes => 2 * 60,
+            ChartRange::FifteenMinutes => 15 * 60,
+            ChartRange::OneHour => 60 * 60,
+            ChartRange::SixHours => 6 * 60 * 60,
+        }
+    }
+
+    /// Rollup level used to display the range, or `None` for the raw data points.
+    fn rollup_level(&self) -> Option<usize> {
+        match self {
+            ChartRange::TwoMinutes => None,
+            ChartRange::FifteenMinutes => Some(0),
+            ChartRange::OneHour => Some(1),
+            ChartRange::SixHours => Some(2),
+        }
+    }
+}
+
+// Bucket size in seconds, and range, of every rollup level
+const ROLLUP_LEVELS: [(i64, ChartRange); 3] = [
+    (10, ChartRange::FifteenMinutes),
+    (30, ChartRange::OneHour),
+    (180, ChartRange::SixHours),
+];
+
+/// Vertical line data points marking the timeline selected timestamp, if it's within the chart bounds.
+pub(crate) fn timeline_marker_data(
+    timestamp: Option<i64>,
+    view: &ChartView<impl ChartDataPoint>,
+) -> Vec<(f64, f64)> {
+    let Some(timestamp) = timestamp.map(|t| t as f64) else {
+        return vec![];
+    };
+
+    let x_bounds = view.x_axis_bounds();
+    if timestamp < x_bounds[0] || timestamp > x_bounds[1] {
+        return vec![];
+    }
+
+    let y_bounds = view.y_axis_bounds();
+    vec![(timestamp, y_bounds[0]), (timestamp, y_bounds[1])]
+}
+
+pub(crate) fn timeline_marker_dataset(data: &[(f64, f64)]) -> Dataset<'_> {
+    Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::White))
+        .data(data)
+}
+
+// Number of points drawn on every event marker vertical line
+const EVENT_MARKER_POINTS: usize = 100;
+
+/// Vertical lines marking the node restarts and pipelines reloads within the chart bounds,
+/// labeled on the chart legend with the latest event of each kind.
+pub(crate) struct ChartEventsMarkers {
+    markers: Vec<(ChartEventKind, String, ChartSeries)>,
+}
+
+impl ChartEventsMarkers {
+    pub fn new(events: &[ChartEvent], view: &ChartView<impl ChartDataPoint>) -> Self {
+        let x_bounds = view.x_axis_bounds();
+        let y_bounds = view.y_axis_bounds();
+        let step = (y_bounds[1] - y_bounds[0]) / EVENT_MARKER_POINTS as f64;
+
+        let mut markers = vec![];
+        for kind in [ChartEventKind::Restart, ChartEventKind::Reload] {
+            let visible_events: Vec<&ChartEvent> = events
+                .iter()
+                .filter(|e| e.kind == kind)
+                .filter(|e| {
+                    let timestamp = e.timestamp as f64;
+                    timestamp >= x_bounds[0] && timestamp <= x_bounds[1]
+                })
+                .collect();
+
+            let Some(latest) = visible_events.last() else {
+                continue;
+            };
+
+            let mut label = format!(
+                "{} {}",
+                latest.label,
+                format_event_timestamp(latest.timestamp)
+            );
+            if visible_events.len() > 1 {
+                label.push_str(format!(" (+{})", visible_events.len() - 1).as_str());
+            }
+
+            let data = visible_events
+                .iter()
+                .flat_map(|e| {
+                    (0..=EVENT_MARKER_POINTS)
+                        .map(move |i| (e.timestamp as f64, y_bounds[0] + step * i as f64))
+                })
+                .collect();
+
+            markers.push((kind, label, data));
+        }
+
+        ChartEventsMarkers { markers }
+    }
+
+    pub fn datasets(&self) -> Vec<Dataset<'_>> {
+        self.markers
+            .iter()
+            .map(|(kind, label, data)| {
+                let color = match kind {
+                    ChartEventKind::Restart => Color::Red,
+                    ChartEventKind::Reload => Color::Cyan,
+                };
+
+                Dataset::default()
+                    .name(label.as_str())
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Scatter)
+                    .style(Style::default().fg(color))
+                    .data(data)
+            })
+            .collect()
+    }
+}
+
+fn format_event_timestamp(timestamp: i64) -> String {
+    let format = format_description::parse("[hour]:[minute]:[second]").unwrap();
+    OffsetDateTime::from_unix_timestamp(timestamp)
+        .unwrap()
+        .to_offset(UtcOffset::current_local_offset().unwrap())
+        .format(&format)
+        .unwrap()
+}
+
+/// Dataset drawing the min or max values of the rolled up data points.
+pub(crate) fn rollup_envelope_dataset(data: &[(f64, f64)]) -> Dataset<'_> {
+    Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::DarkGray))
+        .data(data)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Aggregation {
+    Min,
+    Avg,
+    Max,
+}
+
+impl Aggregation {
+    pub fn apply(&self, values: impl Iterator<Item = f64>) -> f64 {
+        match self {
+            Aggregation::Min => values.fold(f64::MAX, f64::min),
+            Aggregation::Max => values.fold(f64::MIN, f64::max),
+            Aggregation::Avg => {
+                let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+                if count == 0 {
+                    0.0
+                } else {
+                    sum / count as f64
+                }
+            }
+        }
+    }
+}
+
+pub trait ChartDataPoint: Clone {
     fn y_axis_bounds(&self) -> [f64; 2];
     fn x_axis_bounds(&self) -> [f64; 2];
+    /// Combines the points values into a single data point at the given timestamp.
+    fn aggregate(points: &[Self], aggregation: Aggregation, timestamp: i64) -> Self;
+}
+
+#[derive(Serialize, Deserialize)]
+struct Rollup<Y> {
+    min: Y,
+    avg: Y,
+    max: Y,
 }
 
+impl<Y> Rollup<Y>
+where
+    Y: ChartDataPoint,
+{
+    fn new(points: &[Y], timestamp: i64) -> Self {
+        Rollup {
+            min: Y::aggregate(points, Aggregation::Min, timestamp),
+            avg: Y::aggregate(points, Aggregation::Avg, timestamp),
+            max: Y::aggregate(points, Aggregation::Max, timestamp),
+        }
+    }
+}
+
+/// Data points rolled up into fixed size time buckets, keeping only the buckets
+/// needed to cover its range.
+#[derive(Serialize, Deserialize)]
+struct RollupLevel<Y> {
+    bucket_seconds: i64,
+    max_buckets: usize,
+    buckets: VecDeque<Rollup<Y>>,
+    pending: Vec<Y>,
+    pending_bucket: i64,
+}
+
+impl<Y> RollupLevel<Y>
+where
+    Y: ChartDataPoint,
+{
+    fn new(bucket_seconds: i64, range: ChartRange) -> Self {
+        RollupLevel {
+            bucket_seconds,
+            max_buckets: (range.seconds() / bucket_seconds) as usize + 1,
+            buckets: VecDeque::new(),
+            pending: vec![],
+            pending_bucket: 0,
+        }
+    }
+
+    fn push(&mut self, value: &Y) {
+        let timestamp = value.x_axis_bounds()[0] as i64;
+        let bucket = timestamp - timestamp.rem_euclid(self.bucket_seconds);
+        if bucket != self.pending_bucket && !self.pending.is_empty() {
+            self.buckets
+                .push_front(Rollup::new(&self.pending, self.pending_bucket));
+            self.pending.clear();
+            if self.buckets.len() > self.max_buckets {
+                self.buckets.pop_back();
+            }
+        }
+
+        self.pending_bucket = bucket;
+        self.pending.push(value.clone());
+    }
+
+    /// Rollups from the newest to the oldest, including the bucket in progress.
+    fn rollups(&self) -> impl Iterator<Item = Rollup<Y>> + '_ {
+        let pending = if self.pending.is_empty() {
+            None
+        } else {
+            Some(Rollup::new(&self.pending, self.pending_bucket))
+        };
+
+        pending
+            .into_iter()
+            .chain(self.buckets.iter().map(|r| Rollup {
+                min: r.min.clone(),
+                avg: r.avg.clone(),
+                max: r.max.clone(),
+            }))
+    }
+
+    fn clear(&mut self) {
+        self.buckets.clear();
+        self.pending.clear();
+    }
+
+    fn discard_older_than(&mut self, timestamp: i64) {
+        self.buckets
+            .retain(|r| r.avg.x_axis_bounds()[0] as i64 >= timestamp);
+        self.pending
+            .retain(|p| p.x_axis_bounds()[0] as i64 >= timestamp);
+    }
+}
+
+/// Chart history, keeping the most recent raw data points, and min/avg/max rollups of
+/// the older ones, so long ranges can be displayed with a bounded memory usage.
+#[derive(Serialize, Deserialize)]
 pub struct TimestampChartState<Y>
 where
     Y: ChartDataPoint,
 {
+    /// Raw data points, from the newest to the oldest
     pub data_points: VecDeque<Y>,
     pub max_data_points: Option<usize>,
-    x_axis_bounds: [f64; 2],
-    y_axis_bounds: [f64; 2],
+    rollup_levels: Vec<RollupLevel<Y>>,
     min_x_value: Option<f64>,
     min_y_value: Option<f64>,
 }
@@ -82,8 +404,10 @@ where
         TimestampChartState {
             data_points: VecDeque::new(),
             max_data_points,
-            x_axis_bounds: [0.0, 0.0],
-            y_axis_bounds: [0.0, 0.0],
+            rollup_levels: ROLLUP_LEVELS
+                .iter()
+                .map(|(bucket_seconds, range)| RollupLevel::new(*bucket_seconds, *range))
+                .collect(),
             min_x_value,
             min_y_value,
         }
@@ -97,44 +421,155 @@ where
         Self::with_min_bounds(max_data_points, Some(0.0), Some(0.0))
     }
 
-    fn update_bounds(bounds: &mut [f64; 2], value_bounds: [f64; 2]) {
-        if value_bounds[0] < bounds[0] {
-            bounds[0] = value_bounds[0];
+    pub fn push(&mut self, value: Y) {
+        for level in &mut self.rollup_levels {
+            level.push(&value);
         }
 
-        if value_bounds[1] > bounds[1] {
-            bounds[1] = value_bounds[1];
+        self.data_points.push_front(value);
+
+        if let Some(max_data_points) = self.max_data_points {
+            if self.data_points.len() > max_data_points {
+                self.data_points.pop_back();
+            }
         }
     }
 
-    pub fn push(&mut self, value: Y) {
-        let value_y_bounds = value.y_axis_bounds();
-        let value_x_bounds = value.x_axis_bounds();
-
-        let inclusive_y_bounds = [
-            f64::max(self.min_y_value.unwrap_or(0.0), value_y_bounds[0] - 1.0),
-            value_y_bounds[1] + 1.0,
-        ];
-        let inclusive_x_bounds = [
-            f64::max(self.min_x_value.unwrap_or(0.0), value_x_bounds[0] - 1.0),
-            value_x_bounds[1] + 1.0,
-        ];
-
-        if self.data_points.is_empty() {
-            self.x_axis_bounds = inclusive_x_bounds;
-            self.y_axis_bounds = inclusive_y_bounds;
-        } else {
-            Self::update_bounds(&mut self.x_axis_bounds, inclusive_x_bounds);
-            Self::update_bounds(&mut self.y_axis_bounds, inclusive_y_bounds);
+    /// Returns the data points to display for the range. Short ranges display the raw
+    /// data points, and longer ones the rolled up averages, with their min and max values.
+    pub fn view(&self, range: ChartRange) -> ChartView<Y> {
+        let newest = self
+            .data_points
+            .front()
+            .map(|p| p.x_axis_bounds()[1] as i64)
+            .unwrap_or_default();
+        let oldest = newest - range.seconds();
+        let in_range = |p: &Y| p.x_axis_bounds()[0] as i64 >= oldest;
+
+        let (data_points, min_data_points, max_data_points) =
+            match range.rollup_level().and_then(|i| self.rollup_levels.get(i)) {
+                Some(level) => {
+                    let mut data_points = vec![];
+                    let mut min_data_points = vec![];
+                    let mut max_data_points = vec![];
+                    for rollup in level.rollups().take_while(|r| in_range(&r.avg)) {
+                        data_points.push(rollup.avg);
+                        min_data_points.push(rollup.min);
+                        max_data_points.push(rollup.max);
+                    }
+                    (data_points, min_data_points, max_data_points)
+                }
+                None => (
+                    self.data_points
+                        .iter()
+                        .take_while(|p| in_range(p))
+                        .cloned()
+                        .collect(),
+                    vec![],
+                    vec![],
+                ),
+            };
+
+        ChartView::new(
+            data_points,
+            min_data_points,
+            max_data_points,
+            self.min_x_value,
+            self.min_y_value,
+        )
+    }
+
+    pub fn reset(&mut self) {
+        self.data_points.clear();
+        for level in &mut self.rollup_levels {
+            level.clear();
         }
+    }
 
-        self.data_points.push_front(value);
+    pub fn is_empty(&self) -> bool {
+        self.data_points.is_empty()
+    }
 
-        if let Some(max_data_points) = self.max_data_points {
-            if self.data_points.len() >= max_data_points {
-                self.data_points.pop_back();
-                if let Some(newest) = self.data_points.back() {
-                    self.x_axis_bounds[0] = newest.x_axis_bounds()[0];
+    pub fn discard_older_than(&mut self, timestamp: i64) {
+        self.data_points
+            .retain(|p| p.x_axis_bounds()[0] as i64 >= timestamp);
+        for level in &mut self.rollup_levels {
+            level.discard_older_than(timestamp);
+        }
+    }
+}
+
+pub(crate) type ChartSeries = Vec<(f64, f64)>;
+
+/// Data points of a chart range, with the axis bounds fitting them.
+pub struct ChartView<Y> {
+    /// Data points, or rolled up averages, from the newest to the oldest
+    pub data_points: Vec<Y>,
+    /// Rolled up min and max values, empty when displaying raw data points
+    pub min_data_points: Vec<Y>,
+    pub max_data_points: Vec<Y>,
+    x_axis_bounds: [f64; 2],
+    y_axis_bounds: [f64; 2],
+    min_x_value: Option<f64>,
+    min_y_value: Option<f64>,
+}
+
+impl<Y> ChartView<Y>
+where
+    Y: ChartDataPoint,
+{
+    fn new(
+        data_points: Vec<Y>,
+        min_data_points: Vec<Y>,
+        max_data_points: Vec<Y>,
+        min_x_value: Option<f64>,
+        min_y_value: Option<f64>,
+    ) -> Self {
+        let mut x_axis_bounds: Option<[f64; 2]> = None;
+        let mut y_axis_bounds: Option<[f64; 2]> = None;
+
+        for value in data_points
+            .iter()
+            .chain(min_data_points.iter())
+            .chain(max_data_points.iter())
+        {
+            let value_y_bounds = value.y_axis_bounds();
+            let value_x_bounds = value.x_axis_bounds();
+
+            let inclusive_y_bounds = [
+                f64::max(min_y_value.unwrap_or(0.0), value_y_bounds[0] - 1.0),
+                value_y_bounds[1] + 1.0,
+            ];
+            let inclusive_x_bounds = [
+                f64::max(min_x_value.unwrap_or(0.0), value_x_bounds[0] - 1.0),
+                value_x_bounds[1] + 1.0,
+            ];
+
+            Self::update_bounds(&mut x_axis_bounds, inclusive_x_bounds);
+            Self::update_bounds(&mut y_axis_bounds, inclusive_y_bounds);
+        }
+
+        ChartView {
+            data_points,
+            min_data_points,
+            max_data_points,
+            x_axis_bounds: x_axis_bounds.unwrap_or([0.0, 0.0]),
+            y_axis_bounds: y_axis_bounds.unwrap_or([0.0, 0.0]),
+            min_x_value,
+            min_y_value,
+        }
+    }
+
+    fn update_bounds(bounds: &mut Option<[f64; 2]>, value_bounds: [f64; 2]) {
+        match bounds {
+            None => *bounds = Some(value_bounds),
+            Some(bounds) => {
+                if value_bounds[0] < bounds[0] {
+                    bounds[0] = value_bounds[0];
+                }
+
+                if value_bounds[1] > bounds[1] {
+                    bounds[1] = value_bounds[1];
                 }
             }
         }
@@ -149,39 +584,24 @@ where
     }
 
     pub fn x_axis_labels_values(&self, count: usize) -> Vec<f64> {
-        let mut values: Vec<f64> = Vec::with_capacity(count + 1);
-
-        let pieces = f64::max(
-            self.min_x_value.map(|p| p + 1.0).unwrap_or(1.0),
-            (self.x_axis_bounds[1] - self.x_axis_bounds[0]) / (count as f64),
-        );
-
-        let mut previous: Option<f64> = None;
-        for i in 0..(count - 1) {
-            let next = self.x_axis_bounds[0] + (pieces * (i as f64));
-            if previous.is_some_and(|p| p.signum() != next.signum()) {
-                values.push(0.0);
-            }
-
-            previous = Some(next);
-            values.push(next);
-        }
-
-        values.push(self.x_axis_bounds[1]);
-        values
+        Self::labels_values(&self.x_axis_bounds, self.min_x_value, count)
     }
 
     pub fn y_axis_labels_values(&self, count: usize) -> Vec<f64> {
+        Self::labels_values(&self.y_axis_bounds, self.min_y_value, count)
+    }
+
+    fn labels_values(bounds: &[f64; 2], min_value: Option<f64>, count: usize) -> Vec<f64> {
         let mut values: Vec<f64> = Vec::with_capacity(count + 1);
 
         let pieces = f64::max(
-            self.min_y_value.map(|p| p + 1.0).unwrap_or(1.0),
-            (self.y_axis_bounds[1] - self.y_axis_bounds[0]) / (count as f64),
+            min_value.map(|p| p + 1.0).unwrap_or(1.0),
+            (bounds[1] - bounds[0]) / (count as f64),
         );
 
         let mut previous: Option<f64> = None;
         for i in 0..(count - 1) {
-            let next = self.y_axis_bounds[0] + (pieces * (i as f64));
+            let next = bounds[0] + (pieces * (i as f64));
             if previous.is_some_and(|p| p.signum() != next.signum()) {
                 values.push(0.0);
             }
@@ -190,17 +610,28 @@ where
             values.push(next);
         }
 
-        values.push(self.y_axis_bounds[1]);
+        values.push(bounds[1]);
         values
     }
 
-    pub fn reset(&mut self) {
-        self.data_points.clear();
-        self.x_axis_bounds = [0.0, 0.00];
-        self.y_axis_bounds = [0.0, 0.00];
+    /// Chart data of the values returned by `value`, from the oldest to the newest point.
+    pub fn series(&self, value: impl Fn(&Y) -> f64) -> ChartSeries {
+        Self::points_series(&self.data_points, &value)
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.data_points.is_empty()
+    /// Chart data of the rolled up min and max values, empty when displaying raw data points.
+    pub fn envelope_series(&self, value: impl Fn(&Y) -> f64) -> (ChartSeries, ChartSeries) {
+        (
+            Self::points_series(&self.min_data_points, &value),
+            Self::points_series(&self.max_data_points, &value),
+        )
+    }
+
+    fn points_series(points: &[Y], value: &impl Fn(&Y) -> f64) -> ChartSeries {
+        points
+            .iter()
+            .rev()
+            .map(|p| (p.x_axis_bounds()[0], value(p)))
+            .collect()
     }
 }
@@ -1,9 +1,11 @@
+use std::path::PathBuf;
 use std::time::Duration;
 
 use clap::Args;
 
 use crate::commands::traits::RunnableCommand;
 use crate::commands::tui::backend::run;
+use crate::commands::watch::capture::CaptureArgs;
 use crate::config::Config;
 use crate::errors::AnyError;
 use crate::output::Output;
@@ -13,11 +15,65 @@ pub struct TuiArgs {
     /// Refresh interval in seconds
     #[arg(default_value_t = 1, short = 'i', long)]
     pub interval: u64,
+
+    /// Directory containing a copy of the pipelines configuration files, used to display
+    /// the vertices source when tuistash isn't running on the Logstash host
+    #[arg(long)]
+    pub config_root: Option<PathBuf>,
+
+    /// File containing the alert rules, one per line, e.g. 'high_heap critical: heap_used_percent > 85 for 60s'
+    #[arg(long)]
+    pub alert_rules: Option<PathBuf>,
+
+    /// Ring the terminal bell when an alert starts firing
+    #[arg(long)]
+    pub alert_bell: bool,
+
+    /// Command executed when an alert fires or resolves. The alert is passed as JSON on the
+    /// stdin, and as TUISTASH_ALERT_* environment variables
+    #[arg(long)]
+    pub alert_command: Option<String>,
+
+    /// URL that receives a JSON POST request when an alert fires or resolves
+    #[arg(long)]
+    pub alert_webhook: Option<String>,
+
+    /// Minimum interval in seconds between notifications of the same alert
+    #[arg(long, default_value_t = 60)]
+    pub alert_hooks_interval: u64,
+
+    #[command(flatten)]
+    pub capture: CaptureArgs,
+
+    /// Directory where the charts history of each node is saved and restored from.
+    /// Defaults to $XDG_STATE_HOME/tuistash, or ~/.local/state/tuistash
+    #[arg(long)]
+    pub charts_history_dir: Option<PathBuf>,
+
+    /// Maximum age in seconds of the charts history data restored on start
+    #[arg(long, default_value_t = 21600)]
+    pub charts_history_retention: u64,
+
+    /// Do not save nor restore the charts history
+    #[arg(long)]
+    pub no_charts_history: bool,
 }
 
 impl Default for TuiArgs {
     fn default() -> Self {
-        TuiArgs { interval: 1 }
+        TuiArgs {
+            interval: 1,
+            config_root: None,
+            alert_rules: None,
+            alert_bell: false,
+            alert_command: None,
+            alert_webhook: None,
+            alert_hooks_interval: 60,
+            capture: CaptureArgs::default(),
+            charts_history_dir: None,
+            charts_history_retention: 21600,
+            no_charts_history: false,
+        }
     }
 }
 
@@ -26,7 +82,7 @@ pub struct TuiCommand;
 impl RunnableCommand<TuiArgs> for TuiCommand {
     fn run(&self, _: &mut Output, args: &TuiArgs, config: &Config) -> Result<(), AnyError> {
         let tick_rate = Duration::from_secs(args.interval);
-        if let Err(e) = run(tick_rate, config) {
+        if let Err(e) = run(tick_rate, args, config) {
             println!("{}", e);
         }
 
@@ -40,6 +40,24 @@ fn is_graph_api_available(version: &str) -> bool {
     false
 }
 
+// Flow metrics API is available on version >= 8.5.0
+pub(crate) fn is_flow_api_available(version: &str) -> bool {
+    let version: Vec<&str> = version.split('.').collect();
+    if let Some(major) = version.first() {
+        if let Ok(major) = major.parse::<u32>() {
+            if major != 8 {
+                return major > 8;
+            } else if let Some(minor) = version.get(1) {
+                if let Ok(minor) = minor.parse::<u32>() {
+                    return minor >= 5;
+                }
+            }
+        }
+    }
+
+    false
+}
+
 fn decorate_node_stats(node_stats: &mut NodeStats) {
     for stats in &mut node_stats.pipelines.values_mut() {
         if stats.vertices.is_empty() {
@@ -52,7 +52,7 @@ impl ApiDataFetcher {
             .name("api-data-fetcher-node-data".to_string())
             .spawn(move || loop {
                 let node_info = match client.get_node_info(
-                    &[NodeInfoType::Pipelines],
+                    &[NodeInfoType::Pipelines, NodeInfoType::Os],
                     Some(Client::QUERY_NODE_INFO_GRAPH),
                 ) {
                     Ok(value) => value,
@@ -322,3 +322,29 @@ impl DataFetcher for PathDataFetcher {
         self.parse_hot_threads_human_file()
     }
 }
+
+/// Serves the same node data on every fetch, used to render pipelines that
+/// aren't running, such as the ones parsed from configuration files.
+pub(crate) struct StaticDataFetcher {
+    info: NodeInfo,
+    stats: NodeStats,
+}
+
+impl StaticDataFetcher {
+    pub fn new(info: NodeInfo, stats: NodeStats) -> StaticDataFetcher {
+        StaticDataFetcher { info, stats }
+    }
+}
+
+impl DataFetcher for StaticDataFetcher {
+    fn fetch_node_data(&self, _timeout: Option<Duration>) -> Result<NodeData, AnyError> {
+        Ok(NodeData {
+            info: self.info.clone(),
+            stats: self.stats.clone(),
+        })
+    }
+
+    fn fetch_hot_threads(&self, _timeout: Option<Duration>) -> Result<NodeHotThreads, AnyError> {
+        Ok(NodeHotThreads::default())
+    }
+}
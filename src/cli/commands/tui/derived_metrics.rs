@@ -0,0 +1,407 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::api::node::NodeInfo;
+use crate::api::stats::{Events, FlowMetricValue, NodeStats, NodeStatsVertex, Plugin, PluginFlow};
+
+// Number of intervals kept to display the recent rates trends
+const MAX_RATES_HISTORY: usize = 12;
+
+/// Cumulative counters of the node, a pipeline or a vertex.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+struct Counters {
+    events_in: i64,
+    events_filtered: i64,
+    events_out: i64,
+    duration_in_millis: u64,
+    queue_push_duration_in_millis: u64,
+}
+
+impl Counters {
+    fn decreased(&self, previous: &Counters) -> bool {
+        self.events_in < previous.events_in
+            || self.events_filtered < previous.events_filtered
+            || self.events_out < previous.events_out
+            || self.duration_in_millis < previous.duration_in_millis
+            || self.queue_push_duration_in_millis < previous.queue_push_duration_in_millis
+    }
+}
+
+impl From<&Events> for Counters {
+    fn from(events: &Events) -> Self {
+        Counters {
+            events_in: events.r#in,
+            events_filtered: events.filtered,
+            events_out: events.out,
+            duration_in_millis: events.duration_in_millis,
+            queue_push_duration_in_millis: events.queue_push_duration_in_millis,
+        }
+    }
+}
+
+impl From<&NodeStatsVertex> for Counters {
+    fn from(vertex: &NodeStatsVertex) -> Self {
+        Counters {
+            events_in: vertex.events_in,
+            events_filtered: 0,
+            events_out: vertex.events_out,
+            duration_in_millis: vertex.duration_in_millis,
+            queue_push_duration_in_millis: vertex.queue_push_duration_in_millis,
+        }
+    }
+}
+
+/// Counters increase over the last sampling interval. When the counters were reset by a
+/// restart or a reload, their current values are the increase since the reset.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct CountersRates {
+    pub interval_in_millis: u64,
+    pub events_in: i64,
+    pub events_filtered: i64,
+    pub events_out: i64,
+    pub duration_in_millis: u64,
+    pub queue_push_duration_in_millis: u64,
+}
+
+impl CountersRates {
+    fn new(current: &Counters, previous: &Counters, interval_in_millis: u64, reset: bool) -> Self {
+        let base = if reset || current.decreased(previous) {
+            Counters::default()
+        } else {
+            *previous
+        };
+
+        CountersRates {
+            interval_in_millis,
+            events_in: current.events_in - base.events_in,
+            events_filtered: current.events_filtered - base.events_filtered,
+            events_out: current.events_out - base.events_out,
+            duration_in_millis: current.duration_in_millis - base.duration_in_millis,
+            queue_push_duration_in_millis: current.queue_push_duration_in_millis
+                - base.queue_push_duration_in_millis,
+        }
+    }
+
+    pub fn per_second(&self, value: i64) -> f64 {
+        value as f64 / (self.interval_in_millis as f64 / 1000.0)
+    }
+
+    pub fn events_in_per_second(&self) -> f64 {
+        self.per_second(self.events_in)
+    }
+
+    pub fn events_out_per_second(&self) -> f64 {
+        self.per_second(self.events_out)
+    }
+
+    /// Average worker duration of the events received during the interval.
+    pub fn millis_per_event(&self) -> Option<f64> {
+        if self.events_in <= 0 {
+            return None;
+        }
+
+        Some(self.duration_in_millis as f64 / self.events_in as f64)
+    }
+
+    /// Average number of workers busy during the interval.
+    fn busy_workers(&self) -> f64 {
+        self.duration_in_millis as f64 / self.interval_in_millis as f64
+    }
+}
+
+#[derive(Default, Clone)]
+struct TrackedCounters {
+    counters: Option<Counters>,
+    /// Rates of the last intervals, oldest first
+    history: VecDeque<CountersRates>,
+}
+
+impl TrackedCounters {
+    fn update(&mut self, current: Counters, interval_in_millis: u64, reset: bool) {
+        if let Some(previous) = &self.counters {
+            self.history.push_back(CountersRates::new(
+                &current,
+                previous,
+                interval_in_millis,
+                reset,
+            ));
+            if self.history.len() > MAX_RATES_HISTORY {
+                self.history.pop_front();
+            }
+        }
+
+        self.counters = Some(current);
+    }
+
+    fn rates(&self) -> Option<&CountersRates> {
+        self.history.back()
+    }
+
+    /// Copy without the history older than the latest rates.
+    fn latest(&self) -> Self {
+        TrackedCounters {
+            counters: self.counters,
+            history: self.history.back().copied().into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+struct PipelineDerivedMetrics {
+    ephemeral_id: Option<String>,
+    events: TrackedCounters,
+    vertices: HashMap<String, TrackedCounters>,
+}
+
+/// Per-interval rates derived from the node stats counters, tracking the previous sample
+/// of the node, pipelines and vertices, so counters resets caused by restarts and reloads
+/// never produce negative values.
+#[derive(Default, Clone)]
+pub(crate) struct DerivedMetrics {
+    uptime_in_millis: Option<u64>,
+    node: TrackedCounters,
+    pipelines: HashMap<String, PipelineDerivedMetrics>,
+}
+
+impl DerivedMetrics {
+    pub fn update(&mut self, node_stats: &NodeStats) {
+        let uptime_in_millis = node_stats.jvm.uptime_in_millis;
+        let (interval_in_millis, restarted) = match self.uptime_in_millis {
+            Some(previous) if uptime_in_millis < previous => (uptime_in_millis, true),
+            Some(previous) => (uptime_in_millis - previous, false),
+            None => (0, false),
+        };
+
+        self.uptime_in_millis = Some(uptime_in_millis);
+
+        // Same sample applied twice, keeps the current rates
+        if self.node.counters.is_some() && interval_in_millis == 0 {
+            return;
+        }
+
+        self.node.update(
+            Counters::from(&node_stats.events),
+            interval_in_millis,
+            restarted,
+        );
+
+        self.pipelines
+            .retain(|name, _| node_stats.pipelines.contains_key(name));
+
+        for (name, pipeline_stats) in &node_stats.pipelines {
+            let pipeline = self.pipelines.entry(name.to_string()).or_default();
+            let reloaded = pipeline.ephemeral_id.is_some()
+                && pipeline.ephemeral_id != pipeline_stats.ephemeral_id;
+            pipeline.ephemeral_id = pipeline_stats.ephemeral_id.clone();
+
+            let reset = restarted || reloaded;
+            pipeline.events.update(
+                Counters::from(&pipeline_stats.events),
+                interval_in_millis,
+                reset,
+            );
+
+            pipeline
+                .vertices
+                .retain(|id, _| pipeline_stats.vertices.contains_key(id));
+
+            for (id, vertex_stats) in &pipeline_stats.vertices {
+                pipeline.vertices.entry(id.to_string()).or_default().update(
+                    Counters::from(vertex_stats),
+                    interval_in_millis,
+                    reset,
+                );
+            }
+        }
+    }
+
+    /// Copy of the metrics keeping only the latest rates of the vertices, which are
+    /// enough to display a past sample.
+    pub fn snapshot(&self) -> Self {
+        let pipelines = self
+            .pipelines
+            .iter()
+            .map(|(name, pipeline)| {
+                let vertices = pipeline
+                    .vertices
+                    .iter()
+                    .map(|(id, vertex)| (id.to_string(), vertex.latest()))
+                    .collect();
+
+                let snapshot = PipelineDerivedMetrics {
+                    ephemeral_id: pipeline.ephemeral_id.clone(),
+                    events: pipeline.events.clone(),
+                    vertices,
+                };
+                (name.to_string(), snapshot)
+            })
+            .collect();
+
+        DerivedMetrics {
+            uptime_in_millis: self.uptime_in_millis,
+            node: self.node.clone(),
+            pipelines,
+        }
+    }
+
+    pub fn node_rates(&self) -> Option<&CountersRates> {
+        self.node.rates()
+    }
+
+    pub fn pipeline_rates(&self, pipeline: &str) -> Option<&CountersRates> {
+        self.pipelines.get(pipeline).and_then(|p| p.events.rates())
+    }
+
+    pub fn pipeline_rates_history(&self, pipeline: &str) -> Option<&VecDeque<CountersRates>> {
+        self.pipelines.get(pipeline).map(|p| &p.events.history)
+    }
+
+    pub fn vertex_rates(&self, pipeline: &str, vertex: &str) -> Option<&CountersRates> {
+        self.pipelines
+            .get(pipeline)
+            .and_then(|p| p.vertices.get(vertex))
+            .and_then(|v| v.rates())
+    }
+
+    pub fn vertex_rates_history(
+        &self,
+        pipeline: &str,
+        vertex: &str,
+    ) -> Option<&VecDeque<CountersRates>> {
+        self.pipelines
+            .get(pipeline)
+            .and_then(|p| p.vertices.get(vertex))
+            .map(|v| &v.history)
+    }
+
+    /// Fills the flow metrics of the Logstash versions without the flow API, estimating
+    /// their current values from the last interval rates, and their lifetime values from
+    /// the counters and the node uptime. Only the current and lifetime values are set.
+    pub fn estimate_flow_metrics(&self, node_info: &NodeInfo, node_stats: &mut NodeStats) {
+        let uptime_in_millis = node_stats.jvm.uptime_in_millis as f64;
+        if uptime_in_millis == 0.0 {
+            return;
+        }
+
+        let uptime_in_seconds = uptime_in_millis / 1000.0;
+        let node_rates = self.node_rates();
+        let events = &node_stats.events;
+
+        node_stats.flow.input_throughput = estimated_metric(
+            node_rates.map(|r| r.events_in_per_second()),
+            events.r#in as f64 / uptime_in_seconds,
+        );
+        node_stats.flow.filter_throughput = estimated_metric(
+            node_rates.map(|r| r.per_second(r.events_filtered)),
+            events.filtered as f64 / uptime_in_seconds,
+        );
+        node_stats.flow.output_throughput = estimated_metric(
+            node_rates.map(|r| r.events_out_per_second()),
+            events.out as f64 / uptime_in_seconds,
+        );
+        node_stats.flow.queue_backpressure = estimated_metric(
+            node_rates
+                .map(|r| r.queue_push_duration_in_millis as f64 / r.interval_in_millis as f64),
+            events.queue_push_duration_in_millis as f64 / uptime_in_millis,
+        );
+        node_stats.flow.worker_concurrency = estimated_metric(
+            node_rates.map(|r| r.busy_workers()),
+            events.duration_in_millis as f64 / uptime_in_millis,
+        );
+
+        for (name, pipeline_stats) in node_stats.pipelines.iter_mut() {
+            let pipeline_rates = self.pipeline_rates(name);
+            let events = &pipeline_stats.events;
+            let flow = &mut pipeline_stats.flow;
+
+            flow.input_throughput = estimated_metric(
+                pipeline_rates.map(|r| r.events_in_per_second()),
+                events.r#in as f64 / uptime_in_seconds,
+            );
+            flow.filter_throughput = estimated_metric(
+                pipeline_rates.map(|r| r.per_second(r.events_filtered)),
+                events.filtered as f64 / uptime_in_seconds,
+            );
+            flow.output_throughput = estimated_metric(
+                pipeline_rates.map(|r| r.events_out_per_second()),
+                events.out as f64 / uptime_in_seconds,
+            );
+            flow.queue_backpressure = estimated_metric(
+                pipeline_rates
+                    .map(|r| r.queue_push_duration_in_millis as f64 / r.interval_in_millis as f64),
+                events.queue_push_duration_in_millis as f64 / uptime_in_millis,
+            );
+            flow.worker_concurrency = estimated_metric(
+                pipeline_rates.map(|r| r.busy_workers()),
+                events.duration_in_millis as f64 / uptime_in_millis,
+            );
+
+            let workers = node_info
+                .pipelines
+                .as_ref()
+                .and_then(|p| p.get(name))
+                .map(|p| p.workers)
+                .unwrap_or(node_info.node.pipeline.workers)
+                .max(1) as f64;
+
+            let plugins = &mut pipeline_stats.plugins;
+            for (id, plugin) in plugins.inputs.iter_mut() {
+                let rates = self.vertex_rates(name, id);
+                plugin.flow = Some(PluginFlow {
+                    throughput: Some(estimated_metric(
+                        rates.map(|r| r.events_out_per_second()),
+                        plugin.events.out as f64 / uptime_in_seconds,
+                    )),
+                    ..Default::default()
+                });
+            }
+
+            for (id, plugin) in plugins.filters.iter_mut().chain(plugins.outputs.iter_mut()) {
+                let rates = self.vertex_rates(name, id);
+                plugin.flow = Some(estimated_worker_plugin_flow(
+                    plugin,
+                    rates,
+                    workers,
+                    uptime_in_millis,
+                ));
+            }
+        }
+
+        node_stats.estimated_flow = true;
+    }
+}
+
+fn estimated_metric(current: Option<f64>, lifetime: f64) -> FlowMetricValue {
+    FlowMetricValue {
+        current: current.unwrap_or(lifetime),
+        lifetime,
+        ..Default::default()
+    }
+}
+
+fn estimated_worker_plugin_flow(
+    plugin: &Plugin,
+    rates: Option<&CountersRates>,
+    workers: f64,
+    uptime_in_millis: f64,
+) -> PluginFlow {
+    let events = &plugin.events;
+    let lifetime_millis_per_event = if events.r#in > 0 {
+        events.duration_in_millis as f64 / events.r#in as f64
+    } else {
+        0.0
+    };
+
+    let current_millis_per_event = rates.map(|r| r.millis_per_event().unwrap_or(0.0));
+
+    PluginFlow {
+        worker_utilization: Some(estimated_metric(
+            rates.map(|r| (r.busy_workers() / workers * 100.0).min(100.0)),
+            (events.duration_in_millis as f64 / uptime_in_millis / workers * 100.0).min(100.0),
+        )),
+        worker_millis_per_event: Some(estimated_metric(
+            current_millis_per_event,
+            lifetime_millis_per_event,
+        )),
+        ..Default::default()
+    }
+}
@@ -1,9 +1,16 @@
-use crate::commands::tui::app::AppData;
+use crate::commands::tui::app::{AppData, LiveData};
+use crate::commands::tui::search::Search;
 use crossterm::event::KeyEvent;
 
 pub(crate) trait EventsListener {
     fn update(&mut self, app_data: &AppData);
     fn reset(&mut self);
+    /// Called when the timeline cursor moves, so the data derived from the displayed
+    /// snapshot is refreshed. Charts and other history keeping states should not change.
+    fn timeline_changed(&mut self, _: &AppData) {}
+    /// Called on every tick with the latest fetched data, even while the display is paused
+    /// or moved back on the timeline, so alerting and captures keep following the node.
+    fn live_update(&mut self, _: &LiveData) {}
     fn focus_gained(&mut self, _: &AppData) {}
     fn focus_lost(&mut self, _: &AppData) {}
     fn on_enter(&mut self, _: &AppData) {}
@@ -12,4 +19,8 @@ pub(crate) trait EventsListener {
     fn on_up(&mut self, _: &AppData) {}
     fn on_down(&mut self, _: &AppData) {}
     fn on_other(&mut self, _: KeyEvent, _: &AppData) {}
+    /// Search of the focused table, receiving every key while its prompt is open.
+    fn search(&self) -> Option<&Search> {
+        None
+    }
 }
@@ -1,14 +1,17 @@
 use crate::commands::formatter::NumberFormatter;
 use crate::commands::tui::charts::{
-    create_chart_float_label_spans, create_chart_timestamp_label_spans, ChartDataPoint,
-    TimestampChartState, DEFAULT_LABELS_COUNT,
+    create_chart_float_label_spans, create_chart_timestamp_label_spans, rollup_envelope_dataset,
+    timeline_marker_data, timeline_marker_dataset, Aggregation, ChartDataPoint, ChartEventsMarkers,
+    ChartOptions, ChartView, TimestampChartState, DEFAULT_LABELS_COUNT,
 };
 use crate::commands::tui::now_local_unix_timestamp;
 use ratatui::layout::{Constraint, Rect};
 use ratatui::prelude::{Color, Span, Style};
 use ratatui::widgets::{Axis, Block, Borders, Chart, Dataset, GraphType};
 use ratatui::{symbols, Frame};
+use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PluginFlowMetricDataPoint {
     pub timestamp: i64,
     pub input: f64,
@@ -38,8 +41,18 @@ impl ChartDataPoint for PluginFlowMetricDataPoint {
     fn x_axis_bounds(&self) -> [f64; 2] {
         [self.timestamp as f64, self.timestamp as f64]
     }
+
+    fn aggregate(points: &[Self], aggregation: Aggregation, timestamp: i64) -> Self {
+        PluginFlowMetricDataPoint {
+            timestamp,
+            input: aggregation.apply(points.iter().map(|p| p.input)),
+            filter: aggregation.apply(points.iter().map(|p| p.filter)),
+            output: aggregation.apply(points.iter().map(|p| p.output)),
+        }
+    }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct FlowMetricDataPoint {
     pub timestamp: i64,
     pub value: f64,
@@ -62,6 +75,13 @@ impl ChartDataPoint for FlowMetricDataPoint {
     fn x_axis_bounds(&self) -> [f64; 2] {
         [self.timestamp as f64, self.timestamp as f64]
     }
+
+    fn aggregate(points: &[Self], aggregation: Aggregation, timestamp: i64) -> Self {
+        FlowMetricDataPoint {
+            timestamp,
+            value: aggregation.apply(points.iter().map(|p| p.value)),
+        }
+    }
 }
 
 pub(crate) fn draw_plugin_throughput_flow_chart(
@@ -69,16 +89,12 @@ pub(crate) fn draw_plugin_throughput_flow_chart(
     title: &str,
     state: &TimestampChartState<PluginFlowMetricDataPoint>,
     area: Rect,
+    options: &ChartOptions,
 ) {
-    let mut input_throughput_data: Vec<(f64, f64)> = vec![];
-    let mut filter_throughput_data: Vec<(f64, f64)> = vec![];
-    let mut output_throughput_data: Vec<(f64, f64)> = vec![];
-
-    for data_point in &state.data_points {
-        input_throughput_data.push((data_point.timestamp as f64, data_point.input));
-        filter_throughput_data.push((data_point.timestamp as f64, data_point.filter));
-        output_throughput_data.push((data_point.timestamp as f64, data_point.output));
-    }
+    let view = state.view(options.range);
+    let input_throughput_data = view.series(|p| p.input);
+    let filter_throughput_data = view.series(|p| p.filter);
+    let output_throughput_data = view.series(|p| p.output);
 
     let (input_throughput, filter_throughput, output_throughput) = state
         .data_points
@@ -86,7 +102,7 @@ pub(crate) fn draw_plugin_throughput_flow_chart(
         .map(|p| (p.input, p.filter, p.output))
         .unwrap_or((0.0, 0.0, 0.0));
 
-    let datasets = vec![
+    let mut datasets = vec![
         Dataset::default()
             .name(format!("Input: {} e/s", input_throughput.format_number()))
             .marker(symbols::Marker::Braille)
@@ -107,7 +123,12 @@ pub(crate) fn draw_plugin_throughput_flow_chart(
             .data(&output_throughput_data),
     ];
 
-    f.render_widget(create_flow_metric_chart(title, datasets, state), area);
+    let marker_data = timeline_marker_data(options.timeline_timestamp, &view);
+    datasets.push(timeline_marker_dataset(&marker_data));
+    let events_markers = ChartEventsMarkers::new(&options.events, &view);
+    datasets.extend(events_markers.datasets());
+
+    f.render_widget(create_flow_metric_chart(title, datasets, &view), area);
 }
 
 pub(crate) fn draw_flow_metric_chart(
@@ -117,12 +138,11 @@ pub(crate) fn draw_flow_metric_chart(
     state: &TimestampChartState<FlowMetricDataPoint>,
     area: Rect,
     human_format: bool,
+    options: &ChartOptions,
 ) {
-    let metric_data: Vec<(f64, f64)> = state
-        .data_points
-        .iter()
-        .map(|p| (p.timestamp as f64, p.value))
-        .collect();
+    let view = state.view(options.range);
+    let metric_data = view.series(|p| p.value);
+    let (min_data, max_data) = view.envelope_series(|p| p.value);
 
     let current_value = state.data_points.front().map(|p| p.value).unwrap_or(0.0);
     let formatted_current_value = if human_format {
@@ -131,24 +151,33 @@ pub(crate) fn draw_flow_metric_chart(
         current_value.strip_number_decimals(3)
     };
 
-    let datasets = vec![Dataset::default()
-        .name(format!(
-            "Current: {} {}",
-            formatted_current_value,
-            label_suffix.unwrap_or("")
-        ))
-        .marker(symbols::Marker::Braille)
-        .graph_type(GraphType::Line)
-        .style(Style::default().fg(Color::Blue))
-        .data(&metric_data)];
-
-    f.render_widget(create_flow_metric_chart(title, datasets, state), area);
+    let mut datasets = vec![
+        rollup_envelope_dataset(&min_data),
+        rollup_envelope_dataset(&max_data),
+        Dataset::default()
+            .name(format!(
+                "Current: {} {}",
+                formatted_current_value,
+                label_suffix.unwrap_or("")
+            ))
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Blue))
+            .data(&metric_data),
+    ];
+
+    let marker_data = timeline_marker_data(options.timeline_timestamp, &view);
+    datasets.push(timeline_marker_dataset(&marker_data));
+    let events_markers = ChartEventsMarkers::new(&options.events, &view);
+    datasets.extend(events_markers.datasets());
+
+    f.render_widget(create_flow_metric_chart(title, datasets, &view), area);
 }
 
 fn create_flow_metric_chart<'a>(
     title: &'a str,
     datasets: Vec<Dataset<'a>>,
-    state: &TimestampChartState<impl ChartDataPoint>,
+    view: &ChartView<impl ChartDataPoint>,
 ) -> Chart<'a> {
     Chart::new(datasets)
         .hidden_legend_constraints((Constraint::Percentage(90), Constraint::Percentage(90)))
@@ -160,17 +189,17 @@ fn create_flow_metric_chart<'a>(
         .x_axis(
             Axis::default()
                 .style(Style::default().fg(Color::Gray))
-                .bounds(*state.x_axis_bounds())
+                .bounds(*view.x_axis_bounds())
                 .labels(create_chart_timestamp_label_spans(
-                    state.x_axis_labels_values(DEFAULT_LABELS_COUNT),
+                    view.x_axis_labels_values(DEFAULT_LABELS_COUNT),
                 )),
         )
         .y_axis(
             Axis::default()
                 .style(Style::default().fg(Color::Gray))
-                .bounds(*state.y_axis_bounds())
+                .bounds(*view.y_axis_bounds())
                 .labels(create_chart_float_label_spans(
-                    state.y_axis_labels_values(DEFAULT_LABELS_COUNT),
+                    view.y_axis_labels_values(DEFAULT_LABELS_COUNT),
                 )),
         )
 }
@@ -2,6 +2,7 @@ use crate::api::node::{PipelineInfo, Vertex};
 use crate::api::stats::FlowMetricValue;
 use crate::commands::tui::app::AppData;
 use crate::commands::tui::events::EventsListener;
+use crate::commands::tui::search::Search;
 use crate::commands::tui::widgets::{StatefulTable, TabsState};
 use crossterm::event::{KeyCode, KeyEvent};
 use std::cmp::Ordering;
@@ -35,12 +36,29 @@ pub(crate) struct PipelineFlowTableItem {
     pub worker_concurrency: Option<FlowMetricValue>,
 }
 
+/// Whether the plugin id, or its configuration name, matches the search.
+fn plugin_matches(search: &Search, pipeline: &str, id: &str, app_data: &AppData) -> bool {
+    let config_name = app_data
+        .node_info()
+        .and_then(|info| info.pipelines.as_ref())
+        .and_then(|pipelines| pipelines.get(pipeline))
+        .and_then(|p| p.graph.graph.vertices.iter().find(|v| v.id == id))
+        .map(|v| v.config_name.as_str())
+        .unwrap_or_default();
+
+    search.matches(&[id, config_name])
+}
+
 impl StatefulTable<PluginFlowTableInputItem> {
-    fn update(&mut self, selected_pipeline: &String, app_data: &AppData) {
+    fn update(&mut self, selected_pipeline: &String, app_data: &AppData, search: &Search) {
         if let Some(node_stats) = app_data.node_stats() {
             if let Some(pipeline_stats) = node_stats.pipelines.get(selected_pipeline) {
                 let mut new_items = Vec::with_capacity(self.items.len());
                 for (id, plugin) in &pipeline_stats.plugins.inputs {
+                    if !plugin_matches(search, selected_pipeline, id, app_data) {
+                        continue;
+                    }
+
                     new_items.push(PluginFlowTableInputItem {
                         id: id.to_string(),
                         throughput: plugin.flow.as_ref().and_then(|p| p.throughput.clone()),
@@ -54,18 +72,21 @@ impl StatefulTable<PluginFlowTableInputItem> {
                 });
 
                 self.items = new_items;
+                self.clamp_selection();
             }
         }
     }
 }
 
 impl StatefulTable<PluginFlowTableItem> {
-    fn update(&mut self, selected_pipeline: &String, app_data: &AppData) {
+    fn update(&mut self, selected_pipeline: &String, app_data: &AppData, search: &Search) {
         if let Some(node_stats) = app_data.node_stats() {
             if let Some(pipeline_stats) = node_stats.pipelines.get(selected_pipeline) {
                 let mut new_items = Vec::new();
                 for (id, (plugin_type, plugin)) in pipeline_stats.plugins.all_with_type() {
-                    if plugin_type == "input" {
+                    if plugin_type == "input"
+                        || !plugin_matches(search, selected_pipeline, &id, app_data)
+                    {
                         continue;
                     }
 
@@ -101,6 +122,7 @@ impl StatefulTable<PluginFlowTableItem> {
                 });
 
                 self.items = new_items;
+                self.clamp_selection();
             }
         }
     }
@@ -118,12 +140,16 @@ impl StatefulTable<PipelineFlowTableItem> {
         map
     }
 
-    fn update(&mut self, data: &AppData) {
+    fn update(&mut self, data: &AppData, search: &Search) {
         if let Some(node_info) = &data.node_info() {
             if let Some(node_stats) = &data.node_stats() {
                 if let Some(pipelines) = &node_info.pipelines {
                     let mut new_items = Vec::with_capacity(pipelines.len());
                     for (name, pipeline_info) in pipelines {
+                        if !search.matches(&[name]) {
+                            continue;
+                        }
+
                         let mut new_item = PipelineFlowTableItem {
                             _id: pipeline_info.ephemeral_id.to_string(),
                             name: name.to_string(),
@@ -178,6 +204,7 @@ impl StatefulTable<PipelineFlowTableItem> {
                     }
 
                     self.items = new_items;
+                    self.clamp_selection();
                 }
             }
         }
@@ -207,6 +234,10 @@ pub(crate) struct FlowsState {
     pub(crate) show_lifetime_values: bool,
     pub(crate) show_selected_pipeline: bool,
     pub(crate) current_focus: usize,
+    /// Name filter of the pipelines list
+    pub(crate) pipelines_search: Search,
+    /// Id or name filter of the selected pipeline plugins lists
+    pub(crate) plugins_search: Search,
 }
 
 impl FlowsState {
@@ -220,15 +251,32 @@ impl FlowsState {
             show_lifetime_values: false,
             show_selected_pipeline: false,
             current_focus: PIPELINES_LIST,
+            pipelines_search: Search::default(),
+            plugins_search: Search::default(),
         }
     }
+
+    fn focused_search_mut(&mut self) -> &mut Search {
+        if self.current_focus == PIPELINES_LIST {
+            &mut self.pipelines_search
+        } else {
+            &mut self.plugins_search
+        }
+    }
+
     fn update_selected_pipeline_tables(&mut self, app_data: &AppData) {
         if self.show_selected_pipeline {
             if let Some(selected_pipeline) = self.pipelines_flow_table.selected_item() {
-                self.input_plugins_flow_table
-                    .update(&selected_pipeline.name, app_data);
-                self.other_plugins_flow_table
-                    .update(&selected_pipeline.name, app_data);
+                self.input_plugins_flow_table.update(
+                    &selected_pipeline.name,
+                    app_data,
+                    &self.plugins_search,
+                );
+                self.other_plugins_flow_table.update(
+                    &selected_pipeline.name,
+                    app_data,
+                    &self.plugins_search,
+                );
             }
         }
     }
@@ -236,10 +284,15 @@ impl FlowsState {
 
 impl EventsListener for FlowsState {
     fn update(&mut self, app_data: &AppData) {
-        self.pipelines_flow_table.update(app_data);
+        self.pipelines_flow_table
+            .update(app_data, &self.pipelines_search);
         self.update_selected_pipeline_tables(app_data);
     }
 
+    fn timeline_changed(&mut self, app_data: &AppData) {
+        self.update(app_data);
+    }
+
     fn reset(&mut self) {
         self.pipelines_flow_table.unselect();
         self.pipelines_flow_table.items.clear();
@@ -326,7 +379,28 @@ impl EventsListener for FlowsState {
         }
     }
 
-    fn on_other(&mut self, key_event: KeyEvent, _: &AppData) {
+    fn search(&self) -> Option<&Search> {
+        if self.current_focus == PIPELINES_LIST {
+            Some(&self.pipelines_search)
+        } else {
+            Some(&self.plugins_search)
+        }
+    }
+
+    fn on_other(&mut self, key_event: KeyEvent, app_data: &AppData) {
+        if self.focused_search_mut().on_key(key_event) {
+            self.update(app_data);
+            return;
+        }
+
+        if self.focused_search_mut().is_active() {
+            match key_event.code {
+                KeyCode::Char('n') => self.on_down(app_data),
+                KeyCode::Char('N') => self.on_up(app_data),
+                _ => {}
+            }
+        }
+
         // Tab navigation
         if key_event.code == KeyCode::Tab && self.show_selected_pipeline {
             if self.current_focus == PIPELINES_LIST {
@@ -11,11 +11,13 @@ use ratatui::widgets::{Block, Borders, Cell, Row, Table, Tabs};
 use ratatui::Frame;
 
 use crate::api::stats::FlowMetricValue;
+use crate::commands::analyze::analyzer::Bottleneck;
 use crate::commands::formatter::NumberFormatter;
+use crate::commands::tui::alerts::rules::AlertSeverity;
 use crate::commands::tui::app::App;
 use crate::commands::tui::widgets::{
-    TABLE_HEADER_CELL_STYLE, TABLE_HEADER_ROW_STYLE, TABLE_SELECTED_ROW_STYLE,
-    TABLE_SELECTED_ROW_SYMBOL,
+    alert_marker, bottleneck_marker, TABLE_HEADER_CELL_STYLE, TABLE_HEADER_ROW_STYLE,
+    TABLE_SELECTED_ROW_STYLE, TABLE_SELECTED_ROW_SYMBOL,
 };
 
 const ANALYSIS_WINDOW_10_SEC: usize = 1;
@@ -41,6 +43,8 @@ pub(crate) fn flows_tab_shortcuts_help(_: &App) -> HashMap<String, String> {
     keys.insert("[1-6]".to_string(), "analysis window".to_string());
     keys.insert("[V]".to_string(), "diff as number/%".to_string());
     keys.insert("[L]".to_string(), "lifetimes".to_string());
+    keys.insert("[/]".to_string(), "search".to_string());
+    keys.insert("[n/N]".to_string(), "next/previous match".to_string());
     keys
 }
 
@@ -191,6 +195,21 @@ fn draw_pipelines_table(f: &mut Frame, app: &mut App, area: Rect) {
     let show_as_percentage = app.flows_state.show_as_percentage;
     let show_lifetimes = app.flows_state.show_lifetime_values;
     let hide_flow_cells = app.flows_state.show_selected_pipeline;
+    let mut title = vec![Span::raw(
+        if app
+            .data
+            .read()
+            .unwrap()
+            .node_stats()
+            .is_some_and(|s| s.estimated_flow)
+        {
+            "Pipelines (estimated)"
+        } else {
+            "Pipelines"
+        },
+    )];
+
+    title.extend(app.flows_state.pipelines_search.title_spans());
 
     let rows: Vec<Row> = app
         .flows_state
@@ -241,7 +260,14 @@ fn draw_pipelines_table(f: &mut Frame, app: &mut App, area: Rect) {
             };
 
             let mut cells = Vec::with_capacity(flow_metric_cells.len() + 2);
-            cells.push(Cell::from(Text::from(i.name.to_string())));
+            let mut name_spans = vec![Span::raw(i.name.to_string())];
+            if let Some(severity) = app.shared_state.bottlenecks().pipeline_severity(&i.name) {
+                name_spans.push(bottleneck_marker(severity));
+            }
+            if let Some(severity) = app.alerts_state.pipeline_severity(&i.name) {
+                name_spans.push(alert_marker(severity));
+            }
+            cells.push(Cell::from(Line::from(name_spans)));
             cells.push(Cell::from(Text::from(i.workers.to_string())));
             cells.extend(flow_metric_cells);
 
@@ -290,7 +316,11 @@ fn draw_pipelines_table(f: &mut Frame, app: &mut App, area: Rect) {
 
     let pipelines = Table::new(rows, widths)
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title("Pipelines"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Line::from(title)),
+        )
         .column_spacing(2)
         .row_highlight_style(TABLE_SELECTED_ROW_STYLE)
         .highlight_symbol(TABLE_SELECTED_ROW_SYMBOL);
@@ -302,22 +332,33 @@ fn draw_pipelines_table(f: &mut Frame, app: &mut App, area: Rect) {
     );
 }
 
-fn create_plugin_name_cell<'a>(id: &String, vertex: Option<&Vertex>) -> Cell<'a> {
-    if let Some(vertex) = vertex {
-        return if vertex.explicit_id {
-            Cell::from(Line::from(vec![
-                Span::raw(vertex.config_name.to_string()),
-                Span::styled(
-                    format!(" ({})", vertex.id.as_str()),
-                    Style::default().fg(Color::Blue),
-                ),
-            ]))
-        } else {
-            Cell::from(Span::raw(vertex.config_name.to_string()))
-        };
+fn create_plugin_name_cell<'a>(
+    id: &String,
+    vertex: Option<&Vertex>,
+    bottleneck: Option<&Bottleneck>,
+    alert: Option<AlertSeverity>,
+) -> Cell<'a> {
+    let mut spans = match vertex {
+        Some(vertex) if vertex.explicit_id => vec![
+            Span::raw(vertex.config_name.to_string()),
+            Span::styled(
+                format!(" ({})", vertex.id.as_str()),
+                Style::default().fg(Color::Blue),
+            ),
+        ],
+        Some(vertex) => vec![Span::raw(vertex.config_name.to_string())],
+        None => vec![Span::raw(id.to_string())],
+    };
+
+    if let Some(bottleneck) = bottleneck {
+        spans.push(bottleneck_marker(bottleneck.severity));
+    }
+
+    if let Some(severity) = alert {
+        spans.push(alert_marker(severity));
     }
 
-    Cell::from(Span::raw(id.to_string()))
+    Cell::from(Line::from(spans))
 }
 
 fn draw_selected_pipeline_widgets(f: &mut Frame, app: &mut App, area: Rect) {
@@ -343,6 +384,14 @@ fn draw_selected_pipeline_input_plugins(f: &mut Frame, app: &mut App, area: Rect
         .map(|i| &i.vertices)
         .unwrap_or(empty);
 
+    let bottlenecks = app.shared_state.bottlenecks();
+    let selected_pipeline = app
+        .flows_state
+        .pipelines_flow_table
+        .selected_item()
+        .map(|i| i.name.as_str())
+        .unwrap_or_default();
+
     let rows: Vec<Row> = app
         .flows_state
         .input_plugins_flow_table
@@ -350,7 +399,12 @@ fn draw_selected_pipeline_input_plugins(f: &mut Frame, app: &mut App, area: Rect
         .iter()
         .map(|i| {
             Row::new(vec![
-                create_plugin_name_cell(&i.id, pipeline_vertices.get(&i.id)),
+                create_plugin_name_cell(
+                    &i.id,
+                    pipeline_vertices.get(&i.id),
+                    bottlenecks.plugin(selected_pipeline, &i.id),
+                    app.alerts_state.plugin_severity(selected_pipeline, &i.id),
+                ),
                 Cell::from(Text::from("input")),
                 create_flow_metric_cell(
                     &i.throughput,
@@ -380,7 +434,15 @@ fn draw_selected_pipeline_input_plugins(f: &mut Frame, app: &mut App, area: Rect
 
     let pipelines = Table::new(rows, widths)
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title("Inputs"))
+        .block(
+            Block::default().borders(Borders::ALL).title(Line::from(
+                [
+                    vec![Span::raw("Inputs")],
+                    app.flows_state.plugins_search.title_spans(),
+                ]
+                .concat(),
+            )),
+        )
         .column_spacing(2)
         .row_highlight_style(TABLE_SELECTED_ROW_STYLE)
         .highlight_symbol(TABLE_SELECTED_ROW_SYMBOL);
@@ -404,6 +466,14 @@ fn draw_selected_pipeline_other_plugins(f: &mut Frame, app: &mut App, area: Rect
         .map(|i| &i.vertices)
         .unwrap_or(empty);
 
+    let bottlenecks = app.shared_state.bottlenecks();
+    let selected_pipeline = app
+        .flows_state
+        .pipelines_flow_table
+        .selected_item()
+        .map(|i| i.name.as_str())
+        .unwrap_or_default();
+
     let rows: Vec<Row> = app
         .flows_state
         .other_plugins_flow_table
@@ -411,7 +481,12 @@ fn draw_selected_pipeline_other_plugins(f: &mut Frame, app: &mut App, area: Rect
         .iter()
         .map(|i| {
             Row::new(vec![
-                create_plugin_name_cell(&i.id, pipeline_vertices.get(&i.id)),
+                create_plugin_name_cell(
+                    &i.id,
+                    pipeline_vertices.get(&i.id),
+                    bottlenecks.plugin(selected_pipeline, &i.id),
+                    app.alerts_state.plugin_severity(selected_pipeline, &i.id),
+                ),
                 Cell::from(Text::raw(&i.plugin_type)),
                 create_flow_metric_cell(
                     &i.worker_millis_per_event,
@@ -455,7 +530,15 @@ fn draw_selected_pipeline_other_plugins(f: &mut Frame, app: &mut App, area: Rect
 
     let pipelines = Table::new(rows, widths)
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title("Plugins"))
+        .block(
+            Block::default().borders(Borders::ALL).title(Line::from(
+                [
+                    vec![Span::raw("Plugins")],
+                    app.flows_state.plugins_search.title_spans(),
+                ]
+                .concat(),
+            )),
+        )
         .column_spacing(2)
         .row_highlight_style(TABLE_SELECTED_ROW_STYLE)
         .highlight_symbol(TABLE_SELECTED_ROW_SYMBOL);
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::tui::node::state::NodeState;
+use crate::commands::tui::now_local_unix_timestamp;
+use crate::commands::tui::shared_state::PipelineChartState;
+use crate::errors::{AnyError, TuiError};
+
+const HISTORY_VERSION: u32 = 1;
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+pub struct ChartsHistory {
+    version: u32,
+    node_id: String,
+    pub node: NodeState,
+    pub pipelines: HashMap<String, PipelineChartState>,
+}
+
+#[derive(Serialize)]
+struct ChartsHistoryRef<'a> {
+    version: u32,
+    node_id: &'a str,
+    saved_at: i64,
+    node: &'a NodeState,
+    pipelines: &'a HashMap<String, PipelineChartState>,
+}
+
+/// Persists the charts series of each node into a local state file, so they can be
+/// restored when tuistash connects to the same node again.
+pub struct ChartsHistoryStore {
+    dir: PathBuf,
+    retention: Duration,
+    last_save: Option<Instant>,
+}
+
+impl ChartsHistoryStore {
+    pub fn new(dir: Option<PathBuf>, retention: Duration) -> Result<Option<Self>, AnyError> {
+        let Some(dir) = dir.or_else(Self::default_dir) else {
+            return Ok(None);
+        };
+
+        fs::create_dir_all(&dir).map_err(|e| {
+            TuiError::from(
+                format!(
+                    "Failed to create the charts history directory {}: {}",
+                    dir.display(),
+                    e
+                )
+                .as_str(),
+            )
+        })?;
+
+        Ok(Some(ChartsHistoryStore {
+            dir,
+            retention,
+            last_save: None,
+        }))
+    }
+
+    fn default_dir() -> Option<PathBuf> {
+        if let Some(dir) = env::var_os("XDG_STATE_HOME").filter(|v| !v.is_empty()) {
+            return Some(PathBuf::from(dir).join("tuistash"));
+        }
+
+        if let Some(dir) = env::var_os("LOCALAPPDATA").filter(|v| !v.is_empty()) {
+            return Some(PathBuf::from(dir).join("tuistash"));
+        }
+
+        env::var_os("HOME")
+            .filter(|v| !v.is_empty())
+            .map(|home| PathBuf::from(home).join(".local/state/tuistash"))
+    }
+
+    fn path(&self, node_id: &str) -> PathBuf {
+        let file_name: String = node_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        self.dir.join(format!("charts_{}.json", file_name))
+    }
+
+    /// Loads the node charts history, discarding the data points older than the retention.
+    /// Missing, unreadable or incompatible files are ignored.
+    pub fn load(&self, node_id: &str) -> Option<ChartsHistory> {
+        let file = File::open(self.path(node_id)).ok()?;
+        let mut history: ChartsHistory = serde_json::from_reader(BufReader::new(file)).ok()?;
+        if history.version != HISTORY_VERSION || history.node_id != node_id {
+            return None;
+        }
+
+        let oldest_timestamp = now_local_unix_timestamp() - self.retention.as_secs() as i64;
+        history.node.discard_charts_older_than(oldest_timestamp);
+        history.pipelines.retain(|_, pipeline| {
+            pipeline.discard_older_than(oldest_timestamp);
+            !pipeline.pipeline.input_throughput.is_empty()
+        });
+
+        Some(history)
+    }
+
+    pub fn should_save(&self) -> bool {
+        self.last_save
+            .is_none_or(|last_save| last_save.elapsed() >= SAVE_INTERVAL)
+    }
+
+    pub fn save(
+        &mut self,
+        node_id: &str,
+        node: &NodeState,
+        pipelines: &HashMap<String, PipelineChartState>,
+    ) -> Result<(), AnyError> {
+        self.last_save = Some(Instant::now());
+
+        let history = ChartsHistoryRef {
+            version: HISTORY_VERSION,
+            node_id,
+            saved_at: now_local_unix_timestamp(),
+            node,
+            pipelines,
+        };
+
+        // Writes to a temporary file first, so a crash never leaves a truncated history
+        let path = self.path(node_id);
+        let tmp_path = path.with_extension("json.tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        serde_json::to_writer(&mut writer, &history)?;
+        writer.flush()?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+}
@@ -1,17 +1,23 @@
 use time::OffsetDateTime;
 
+mod alerts;
 mod app;
-mod backend;
+pub(crate) mod backend;
+mod chart_events;
 mod charts;
 pub mod command;
-mod data_fetcher;
+pub(crate) mod data_fetcher;
 mod events;
 mod flow_charts;
 
 mod data_decorator;
+mod derived_metrics;
 mod flows;
+mod history;
 mod node;
-mod pipelines;
+pub(crate) mod pipelines;
+mod plugins;
+mod search;
 mod shared_state;
 mod threads;
 mod ui;
@@ -1,11 +1,15 @@
 use crate::api::stats::NodeStats;
 use crate::commands::tui::app::AppData;
-use crate::commands::tui::charts::{ChartDataPoint, TimestampChartState, DEFAULT_MAX_DATA_POINTS};
+use crate::commands::tui::charts::{
+    Aggregation, ChartDataPoint, TimestampChartState, DEFAULT_MAX_DATA_POINTS,
+};
 use crate::commands::tui::events::EventsListener;
 use crate::commands::tui::flow_charts::{FlowMetricDataPoint, PluginFlowMetricDataPoint};
 use crate::commands::tui::now_local_unix_timestamp;
 use crossterm::event::KeyEvent;
+use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ProcessCpuDataPoint {
     pub timestamp: i64,
     pub percent: i64,
@@ -28,7 +32,17 @@ impl ChartDataPoint for ProcessCpuDataPoint {
     fn x_axis_bounds(&self) -> [f64; 2] {
         [self.timestamp as f64, self.timestamp as f64]
     }
+
+    fn aggregate(points: &[Self], aggregation: Aggregation, timestamp: i64) -> Self {
+        ProcessCpuDataPoint {
+            timestamp,
+            percent: aggregation
+                .apply(points.iter().map(|p| p.percent as f64))
+                .round() as i64,
+        }
+    }
 }
+#[derive(Clone, Serialize, Deserialize)]
 pub struct JvmMemNonHeapDataPoint {
     pub timestamp: i64,
     pub non_heap_committed_in_bytes: i64,
@@ -52,8 +66,21 @@ impl ChartDataPoint for JvmMemNonHeapDataPoint {
     fn x_axis_bounds(&self) -> [f64; 2] {
         [self.timestamp as f64, self.timestamp as f64]
     }
+
+    fn aggregate(points: &[Self], aggregation: Aggregation, timestamp: i64) -> Self {
+        JvmMemNonHeapDataPoint {
+            timestamp,
+            non_heap_committed_in_bytes: aggregation
+                .apply(points.iter().map(|p| p.non_heap_committed_in_bytes as f64))
+                .round() as i64,
+            non_heap_used_in_bytes: aggregation
+                .apply(points.iter().map(|p| p.non_heap_used_in_bytes as f64))
+                .round() as i64,
+        }
+    }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct JvmMemHeapDataPoint {
     pub timestamp: i64,
     pub heap_max_in_bytes: i64,
@@ -77,8 +104,21 @@ impl ChartDataPoint for JvmMemHeapDataPoint {
     fn x_axis_bounds(&self) -> [f64; 2] {
         [self.timestamp as f64, self.timestamp as f64]
     }
+
+    fn aggregate(points: &[Self], aggregation: Aggregation, timestamp: i64) -> Self {
+        JvmMemHeapDataPoint {
+            timestamp,
+            heap_max_in_bytes: aggregation
+                .apply(points.iter().map(|p| p.heap_max_in_bytes as f64))
+                .round() as i64,
+            heap_used_in_bytes: aggregation
+                .apply(points.iter().map(|p| p.heap_used_in_bytes as f64))
+                .round() as i64,
+        }
+    }
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct NodeState {
     pub chart_jvm_heap_state: TimestampChartState<JvmMemHeapDataPoint>,
     pub chart_jvm_non_heap_state: TimestampChartState<JvmMemNonHeapDataPoint>,
@@ -117,6 +157,16 @@ impl NodeState {
             ));
     }
 
+    pub(crate) fn discard_charts_older_than(&mut self, timestamp: i64) {
+        self.chart_jvm_heap_state.discard_older_than(timestamp);
+        self.chart_jvm_non_heap_state.discard_older_than(timestamp);
+        self.chart_process_cpu.discard_older_than(timestamp);
+        self.chart_flow_plugins_throughput
+            .discard_older_than(timestamp);
+        self.chart_flow_queue_backpressure
+            .discard_older_than(timestamp);
+    }
+
     fn update_jvm_charts_states(&mut self, node_stats: &NodeStats) {
         self.chart_jvm_heap_state.push(JvmMemHeapDataPoint {
             timestamp: now_local_unix_timestamp(),
@@ -9,11 +9,13 @@ use crate::commands::formatter::{DurationFormatter, NumberFormatter};
 use crate::commands::tui::app::App;
 use crate::commands::tui::charts::{
     create_chart_binary_size_label_spans, create_chart_percentage_label_spans,
-    create_chart_timestamp_label_spans, DEFAULT_LABELS_COUNT,
+    create_chart_timestamp_label_spans, rollup_envelope_dataset, timeline_marker_data,
+    timeline_marker_dataset, ChartEventsMarkers, DEFAULT_LABELS_COUNT,
 };
 use crate::commands::tui::flow_charts::{
     draw_flow_metric_chart, draw_plugin_throughput_flow_chart,
 };
+use crate::commands::tui::widgets::{alert_marker, events_rate_span};
 
 pub(crate) fn draw_node_tab(f: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
@@ -30,15 +32,25 @@ fn draw_node_widgets(f: &mut Frame, app: &App, area: Rect) {
         .split(area);
     {
         // Node overview
-        let events_block = Block::default().title("Overview").borders(Borders::ALL);
+        let mut title = vec![Span::raw("Overview")];
+        if let Some(severity) = app.alerts_state.node_severity() {
+            title.push(alert_marker(severity));
+        }
+
+        let events_block = Block::default()
+            .title(Line::from(title))
+            .borders(Borders::ALL);
         let data = app.data.read().unwrap();
+        let node_rates = data.derived_metrics().node_rates();
         let overview_text: Line = if let Some(node_stats) = data.node_stats() {
             Line::from(vec![
                 Span::styled("Events in: ", Style::default().fg(Color::DarkGray)),
                 Span::from(node_stats.events.r#in.format_number()),
+                events_rate_span(node_rates.map(|r| r.events_in_per_second())),
                 Span::styled(" | ", Style::default().fg(Color::Yellow)),
                 Span::styled("Events out: ", Style::default().fg(Color::DarkGray)),
                 Span::from(node_stats.events.out.format_number()),
+                events_rate_span(node_rates.map(|r| r.events_out_per_second())),
                 Span::styled(" | ", Style::default().fg(Color::Yellow)),
                 Span::styled("Reloads: ", Style::default().fg(Color::DarkGray)),
                 Span::from(node_stats.reloads.successes.format_number()),
@@ -98,6 +110,7 @@ fn draw_node_charts(f: &mut Frame, app: &App, area: Rect) {
         "Throughput",
         &app.node_state.chart_flow_plugins_throughput,
         flow_chart_chunks[1],
+        &app.chart_options,
     );
     draw_flow_metric_chart(
         f,
@@ -106,6 +119,7 @@ fn draw_node_charts(f: &mut Frame, app: &App, area: Rect) {
         &app.node_state.chart_flow_queue_backpressure,
         flow_chart_chunks[2],
         false,
+        &app.chart_options,
     );
 
     let node_chart_chunks = Layout::default()
@@ -125,13 +139,12 @@ fn draw_node_charts(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn draw_jvm_heap_chart(f: &mut Frame, app: &App, area: Rect) {
-    let mut heap_max_data: Vec<(f64, f64)> = vec![];
-    let mut heap_used_data: Vec<(f64, f64)> = vec![];
-
-    for data in &app.node_state.chart_jvm_heap_state.data_points {
-        heap_max_data.push((data.timestamp as f64, data.heap_max_in_bytes as f64));
-        heap_used_data.push((data.timestamp as f64, data.heap_used_in_bytes as f64));
-    }
+    let view = app
+        .node_state
+        .chart_jvm_heap_state
+        .view(app.chart_options.range);
+    let heap_max_data = view.series(|p| p.heap_max_in_bytes as f64);
+    let heap_used_data = view.series(|p| p.heap_used_in_bytes as f64);
 
     let current_max_heap = app
         .node_state
@@ -149,7 +162,7 @@ fn draw_jvm_heap_chart(f: &mut Frame, app: &App, area: Rect) {
         .map(|p| p.heap_used_in_bytes)
         .unwrap_or(0);
 
-    let datasets = vec![
+    let mut datasets = vec![
         Dataset::default()
             .name(format!("Max: {}", format_size_i(current_max_heap, DECIMAL)))
             .marker(symbols::Marker::Braille)
@@ -167,6 +180,11 @@ fn draw_jvm_heap_chart(f: &mut Frame, app: &App, area: Rect) {
             .data(&heap_used_data),
     ];
 
+    let marker_data = timeline_marker_data(app.chart_options.timeline_timestamp, &view);
+    datasets.push(timeline_marker_dataset(&marker_data));
+    let events_markers = ChartEventsMarkers::new(&app.chart_options.events, &view);
+    datasets.extend(events_markers.datasets());
+
     let chart = Chart::new(datasets)
         .hidden_legend_constraints((Constraint::Percentage(90), Constraint::Percentage(90)))
         .block(
@@ -177,21 +195,17 @@ fn draw_jvm_heap_chart(f: &mut Frame, app: &App, area: Rect) {
         .x_axis(
             Axis::default()
                 .style(Style::default().fg(Color::Gray))
-                .bounds(*app.node_state.chart_jvm_heap_state.x_axis_bounds())
+                .bounds(*view.x_axis_bounds())
                 .labels(create_chart_timestamp_label_spans(
-                    app.node_state
-                        .chart_jvm_heap_state
-                        .x_axis_labels_values(DEFAULT_LABELS_COUNT),
+                    view.x_axis_labels_values(DEFAULT_LABELS_COUNT),
                 )),
         )
         .y_axis(
             Axis::default()
                 .style(Style::default().fg(Color::Gray))
-                .bounds(*app.node_state.chart_jvm_heap_state.y_axis_bounds())
+                .bounds(*view.y_axis_bounds())
                 .labels(create_chart_binary_size_label_spans(
-                    app.node_state
-                        .chart_jvm_heap_state
-                        .y_axis_labels_values(DEFAULT_LABELS_COUNT),
+                    view.y_axis_labels_values(DEFAULT_LABELS_COUNT),
                 )),
         );
 
@@ -199,16 +213,12 @@ fn draw_jvm_heap_chart(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn draw_jvm_non_heap_chart(f: &mut Frame, app: &App, area: Rect) {
-    let mut non_heap_used: Vec<(f64, f64)> = vec![];
-    let mut non_heap_committed: Vec<(f64, f64)> = vec![];
-
-    for data in &app.node_state.chart_jvm_non_heap_state.data_points {
-        non_heap_used.push((data.timestamp as f64, data.non_heap_used_in_bytes as f64));
-        non_heap_committed.push((
-            data.timestamp as f64,
-            data.non_heap_committed_in_bytes as f64,
-        ));
-    }
+    let view = app
+        .node_state
+        .chart_jvm_non_heap_state
+        .view(app.chart_options.range);
+    let non_heap_used = view.series(|p| p.non_heap_used_in_bytes as f64);
+    let non_heap_committed = view.series(|p| p.non_heap_committed_in_bytes as f64);
 
     let current_heap_used = app
         .node_state
@@ -226,7 +236,7 @@ fn draw_jvm_non_heap_chart(f: &mut Frame, app: &App, area: Rect) {
         .map(|p| p.non_heap_committed_in_bytes)
         .unwrap_or(0);
 
-    let datasets = vec![
+    let mut datasets = vec![
         Dataset::default()
             .name(format!(
                 "Used: {}",
@@ -247,6 +257,11 @@ fn draw_jvm_non_heap_chart(f: &mut Frame, app: &App, area: Rect) {
             .data(&non_heap_committed),
     ];
 
+    let marker_data = timeline_marker_data(app.chart_options.timeline_timestamp, &view);
+    datasets.push(timeline_marker_dataset(&marker_data));
+    let events_markers = ChartEventsMarkers::new(&app.chart_options.events, &view);
+    datasets.extend(events_markers.datasets());
+
     let chart = Chart::new(datasets)
         .hidden_legend_constraints((Constraint::Percentage(90), Constraint::Percentage(90)))
         .block(
@@ -257,21 +272,17 @@ fn draw_jvm_non_heap_chart(f: &mut Frame, app: &App, area: Rect) {
         .x_axis(
             Axis::default()
                 .style(Style::default().fg(Color::Gray))
-                .bounds(*app.node_state.chart_jvm_non_heap_state.x_axis_bounds())
+                .bounds(*view.x_axis_bounds())
                 .labels(create_chart_timestamp_label_spans(
-                    app.node_state
-                        .chart_jvm_non_heap_state
-                        .x_axis_labels_values(DEFAULT_LABELS_COUNT),
+                    view.x_axis_labels_values(DEFAULT_LABELS_COUNT),
                 )),
         )
         .y_axis(
             Axis::default()
                 .style(Style::default().fg(Color::Gray))
-                .bounds(*app.node_state.chart_jvm_non_heap_state.y_axis_bounds())
+                .bounds(*view.y_axis_bounds())
                 .labels(create_chart_binary_size_label_spans(
-                    app.node_state
-                        .chart_jvm_non_heap_state
-                        .y_axis_labels_values(DEFAULT_LABELS_COUNT),
+                    view.y_axis_labels_values(DEFAULT_LABELS_COUNT),
                 )),
         );
 
@@ -279,13 +290,13 @@ fn draw_jvm_non_heap_chart(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn draw_process_cpu_chart(f: &mut Frame, app: &App, area: Rect) {
-    let cpu_percentage_data: Vec<(f64, f64)> = app
+    let view = app
         .node_state
         .chart_process_cpu
-        .data_points
-        .iter()
-        .map(|p| (p.timestamp as f64, p.percent as f64))
-        .collect();
+        .view(app.chart_options.range);
+    let cpu_percentage_data = view.series(|p| p.percent as f64);
+    let (cpu_percentage_min_data, cpu_percentage_max_data) =
+        view.envelope_series(|p| p.percent as f64);
 
     let current_value = app
         .node_state
@@ -295,12 +306,21 @@ fn draw_process_cpu_chart(f: &mut Frame, app: &App, area: Rect) {
         .map(|p| p.percent)
         .unwrap_or(0);
 
-    let datasets = vec![Dataset::default()
-        .name(format!("Usage: {}%", current_value))
-        .marker(symbols::Marker::Braille)
-        .graph_type(GraphType::Line)
-        .style(Style::default().fg(Color::Blue))
-        .data(&cpu_percentage_data)];
+    let mut datasets = vec![
+        rollup_envelope_dataset(&cpu_percentage_min_data),
+        rollup_envelope_dataset(&cpu_percentage_max_data),
+        Dataset::default()
+            .name(format!("Usage: {}%", current_value))
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Blue))
+            .data(&cpu_percentage_data),
+    ];
+
+    let marker_data = timeline_marker_data(app.chart_options.timeline_timestamp, &view);
+    datasets.push(timeline_marker_dataset(&marker_data));
+    let events_markers = ChartEventsMarkers::new(&app.chart_options.events, &view);
+    datasets.extend(events_markers.datasets());
 
     let chart = Chart::new(datasets)
         .hidden_legend_constraints((Constraint::Percentage(90), Constraint::Percentage(90)))
@@ -312,21 +332,17 @@ fn draw_process_cpu_chart(f: &mut Frame, app: &App, area: Rect) {
         .x_axis(
             Axis::default()
                 .style(Style::default().fg(Color::Gray))
-                .bounds(*app.node_state.chart_process_cpu.x_axis_bounds())
+                .bounds(*view.x_axis_bounds())
                 .labels(create_chart_timestamp_label_spans(
-                    app.node_state
-                        .chart_process_cpu
-                        .x_axis_labels_values(DEFAULT_LABELS_COUNT),
+                    view.x_axis_labels_values(DEFAULT_LABELS_COUNT),
                 )),
         )
         .y_axis(
             Axis::default()
                 .style(Style::default().fg(Color::Gray))
-                .bounds(*app.node_state.chart_process_cpu.y_axis_bounds())
+                .bounds(*view.y_axis_bounds())
                 .labels(create_chart_percentage_label_spans(
-                    app.node_state
-                        .chart_process_cpu
-                        .y_axis_labels_values(DEFAULT_LABELS_COUNT),
+                    view.y_axis_labels_values(DEFAULT_LABELS_COUNT),
                 )),
         );
 
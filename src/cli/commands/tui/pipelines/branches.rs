@@ -0,0 +1,228 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::api::stats::PipelineStats;
+use crate::commands::tui::derived_metrics::DerivedMetrics;
+use crate::commands::tui::pipelines::graph::PipelineGraph;
+
+/// Number of events that took each branch of a conditional vertex.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BranchSplit {
+    pub when_true: i64,
+    pub when_false: i64,
+}
+
+impl BranchSplit {
+    pub fn events(&self, when: bool) -> i64 {
+        if when {
+            self.when_true
+        } else {
+            self.when_false
+        }
+    }
+
+    pub fn percentage(&self, when: bool) -> Option<f64> {
+        let total = self.when_true + self.when_false;
+        if total <= 0 {
+            return None;
+        }
+
+        Some(self.events(when) as f64 / total as f64 * 100.0)
+    }
+}
+
+type VisitKey<'a> = (&'a str, Option<bool>);
+
+/// Splits the events reaching the conditional vertices between their branches, using the
+/// plugins events counters. The branches starting on a vertex exclusively reached by the
+/// conditional are measured by its `events_in`, the others (e.g. a missing `else` falling
+/// through to the next vertex) by the conditional events not taken by the other branch.
+pub(crate) struct BranchFlows<'a> {
+    graph: &'a PipelineGraph<'a>,
+    incoming: HashMap<&'a str, Vec<(&'a str, Option<bool>)>>,
+    // Plugins events_in and events_out
+    counters: HashMap<&'a str, (i64, i64)>,
+    queue_events_out: i64,
+}
+
+impl<'a> BranchFlows<'a> {
+    fn new(
+        graph: &'a PipelineGraph<'a>,
+        counters: HashMap<&'a str, (i64, i64)>,
+        queue_events_out: i64,
+    ) -> Self {
+        let mut incoming: HashMap<&'a str, Vec<(&'a str, Option<bool>)>> = HashMap::new();
+        for (from, edges) in &graph.vertices {
+            for edge in edges {
+                incoming
+                    .entry(edge.vertex_id)
+                    .or_default()
+                    .push((*from, edge.when));
+            }
+        }
+
+        BranchFlows {
+            graph,
+            incoming,
+            counters,
+            queue_events_out,
+        }
+    }
+
+    /// Branches split of the pipeline lifetime events.
+    pub fn from_stats(graph: &'a PipelineGraph<'a>, pipeline_stats: &PipelineStats) -> Self {
+        let counters = graph
+            .data
+            .keys()
+            .filter_map(|id| {
+                pipeline_stats
+                    .vertices
+                    .get(*id)
+                    .map(|v| (*id, (v.events_in, v.events_out)))
+            })
+            .collect();
+
+        Self::new(
+            graph,
+            counters,
+            pipeline_stats.events.r#in - pipeline_stats.queue.events,
+        )
+    }
+
+    /// Branches split of the events received during the last sampling interval.
+    pub fn from_rates(
+        graph: &'a PipelineGraph<'a>,
+        pipeline_name: &str,
+        derived_metrics: &DerivedMetrics,
+    ) -> Option<Self> {
+        let pipeline_rates = derived_metrics.pipeline_rates(pipeline_name)?;
+        let counters = graph
+            .data
+            .keys()
+            .filter_map(|id| {
+                derived_metrics
+                    .vertex_rates(pipeline_name, id)
+                    .map(|r| (*id, (r.events_in, r.events_out)))
+            })
+            .collect();
+
+        Some(Self::new(graph, counters, pipeline_rates.events_filtered))
+    }
+
+    pub fn split(&self, conditional_id: &str) -> Option<BranchSplit> {
+        let (conditional_id, _) = self.graph.data.get_key_value(conditional_id)?;
+        let mut visiting = HashSet::new();
+        Some(BranchSplit {
+            when_true: self.branch_events(conditional_id, true, &mut visiting)?,
+            when_false: self.branch_events(conditional_id, false, &mut visiting)?,
+        })
+    }
+
+    fn vertex_type(&self, vertex_id: &str) -> Option<&'a str> {
+        self.graph
+            .data
+            .get(vertex_id)
+            .map(|d| d.value.r#type.as_str())
+    }
+
+    fn branch_events(
+        &self,
+        conditional_id: &'a str,
+        when: bool,
+        visiting: &mut HashSet<VisitKey<'a>>,
+    ) -> Option<i64> {
+        if !visiting.insert((conditional_id, Some(when))) {
+            return None;
+        }
+
+        let children: Vec<&'a str> = self
+            .graph
+            .vertices
+            .get(conditional_id)
+            .map(|edges| {
+                edges
+                    .iter()
+                    .filter(|e| e.when == Some(when))
+                    .map(|e| e.vertex_id)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let exclusive = !children.is_empty()
+            && children
+                .iter()
+                .all(|id| self.incoming.get(id).is_some_and(|edges| edges.len() == 1));
+
+        // Sibling vertices of a branch receive all its events (e.g. multiple outputs)
+        let events = if exclusive {
+            children
+                .iter()
+                .map(|id| self.vertex_events_in(id, visiting))
+                .try_fold(0, |max, events| events.map(|e| e.max(max)))
+        } else {
+            None
+        };
+
+        let events = events.or_else(|| {
+            let total = self.conditional_events_in(conditional_id, visiting)?;
+            let other = self.branch_events(conditional_id, !when, visiting)?;
+            Some((total - other).max(0))
+        });
+
+        visiting.remove(&(conditional_id, Some(when)));
+        events
+    }
+
+    fn conditional_events_in(
+        &self,
+        conditional_id: &'a str,
+        visiting: &mut HashSet<VisitKey<'a>>,
+    ) -> Option<i64> {
+        if !visiting.insert((conditional_id, None)) {
+            return None;
+        }
+
+        let upstream = self.incoming.get(conditional_id).and_then(|edges| {
+            edges
+                .iter()
+                .map(|(from, when)| self.edge_events(from, *when, visiting))
+                .sum::<Option<i64>>()
+        });
+
+        let events = upstream.or_else(|| {
+            Some(
+                self.branch_events(conditional_id, true, visiting)?
+                    + self.branch_events(conditional_id, false, visiting)?,
+            )
+        });
+
+        visiting.remove(&(conditional_id, None));
+        events
+    }
+
+    fn vertex_events_in(
+        &self,
+        vertex_id: &'a str,
+        visiting: &mut HashSet<VisitKey<'a>>,
+    ) -> Option<i64> {
+        match self.vertex_type(vertex_id)? {
+            "if" => self.conditional_events_in(vertex_id, visiting),
+            _ => self
+                .counters
+                .get(vertex_id)
+                .map(|(events_in, _)| *events_in),
+        }
+    }
+
+    fn edge_events(
+        &self,
+        from: &'a str,
+        when: Option<bool>,
+        visiting: &mut HashSet<VisitKey<'a>>,
+    ) -> Option<i64> {
+        match self.vertex_type(from)? {
+            "if" => self.branch_events(from, when?, visiting),
+            "queue" => Some(self.queue_events_out),
+            _ => self.counters.get(from).map(|(_, events_out)| *events_out),
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
-use uuid::Uuid;
 
 use crate::api::node::{GraphDefinition, Vertex};
-use crate::commands::tui::pipelines::state::PipelineTableItem;
+
+const ELSE_ROW_ID_PREFIX: &str = "else:";
 
 pub struct Data<'a, T> {
     pub value: &'a T,
@@ -21,7 +21,53 @@ pub struct PipelineGraph<'a> {
     pub heads: Vec<&'a str>,
 }
 
-type GraphVisitorStack<'b> = RefCell<Vec<(&'b str, Option<i32>, i32, bool)>>;
+pub enum PipelineVertexRowKind<'a> {
+    Vertex(&'a str),
+    /// `else` row of the conditional, displayed before its first `when=false` vertex
+    Else {
+        conditional_id: &'a str,
+    },
+}
+
+/// Row of the pipeline vertices table.
+pub struct PipelineVertexRow<'a> {
+    pub kind: PipelineVertexRowKind<'a>,
+    pub ident_level: i32,
+    /// Number of rows hidden by collapsing this conditional branch
+    pub hidden_rows: usize,
+}
+
+impl PipelineVertexRow<'_> {
+    /// Stable identifier of the row, the vertex id or the `else` row conditional id.
+    pub fn id(&self) -> String {
+        match self.kind {
+            PipelineVertexRowKind::Vertex(vertex_id) => vertex_id.to_string(),
+            PipelineVertexRowKind::Else { conditional_id, .. } => {
+                format!("{}{}", ELSE_ROW_ID_PREFIX, conditional_id)
+            }
+        }
+    }
+
+    /// Level of the row in the branches tree. The `else` rows are displayed at the
+    /// same level of their conditional, and their vertices one level deeper.
+    pub fn depth(&self) -> i32 {
+        match self.kind {
+            PipelineVertexRowKind::Vertex(_) => self.ident_level,
+            PipelineVertexRowKind::Else { .. } => self.ident_level - 1,
+        }
+    }
+}
+
+/// Whether the row id belongs to an `if` or `else` row, which branches can be collapsed.
+pub fn is_branch_row_id(row_id: &str, graph: &GraphDefinition) -> bool {
+    row_id.starts_with(ELSE_ROW_ID_PREFIX)
+        || graph
+            .vertices
+            .iter()
+            .any(|v| v.id == row_id && v.r#type == "if")
+}
+
+type GraphVisitorStack<'b> = RefCell<Vec<(&'b str, Option<i32>, i32, Option<&'b str>)>>;
 
 impl<'a> PipelineGraph<'a> {
     pub fn from(graph_value: &'a GraphDefinition) -> Self {
@@ -45,15 +91,19 @@ impl<'a> PipelineGraph<'a> {
         }
 
         for edge in &graph_value.edges {
-            vertices
-                .get_mut(edge.from.as_str())
-                .unwrap()
-                .push(VertexEdge {
-                    vertex_id: edge.to.as_str(),
-                    when: edge.when,
-                });
-
-            let edge_to_vertex = data.get(edge.to.as_str()).unwrap();
+            // Skips the edges referencing vertices missing from partial graphs
+            let Some(edge_to_vertex) = data.get(edge.to.as_str()) else {
+                continue;
+            };
+            let Some(neighbours) = vertices.get_mut(edge.from.as_str()) else {
+                continue;
+            };
+
+            neighbours.push(VertexEdge {
+                vertex_id: edge.to.as_str(),
+                when: edge.when,
+            });
+
             if edge_to_vertex.value.plugin_type == "input" || edge_to_vertex.value.r#type == "if" {
                 inputs_incoming_edge.insert(edge.to.as_str(), edge.from.as_str());
             }
@@ -111,7 +161,7 @@ impl<'a> PipelineGraph<'a> {
         }
     }
 
-    pub(crate) fn process_vertices_edges<'b>(
+    fn process_vertices_edges<'b>(
         graph: &'b PipelineGraph,
         vertex_type: &'b str,
         vertex_id: &'b str,
@@ -146,7 +196,7 @@ impl<'a> PipelineGraph<'a> {
                             edge.vertex_id,
                             next_row_index,
                             next_ident_level,
-                            false,
+                            None,
                         ));
                     }
                 }
@@ -158,14 +208,14 @@ impl<'a> PipelineGraph<'a> {
                                 edge.vertex_id,
                                 next_row_index,
                                 next_ident_level,
-                                true,
+                                Some(vertex_id),
                             ));
                         } else {
                             stack.get_mut().push((
                                 edge.vertex_id,
                                 next_row_index,
                                 next_ident_level,
-                                false,
+                                None,
                             ));
                         }
                     }
@@ -177,7 +227,7 @@ impl<'a> PipelineGraph<'a> {
                             edge.vertex_id,
                             next_row_index,
                             next_ident_level,
-                            false,
+                            None,
                         ));
                     }
                 }
@@ -188,7 +238,7 @@ impl<'a> PipelineGraph<'a> {
                             edge.vertex_id,
                             next_row_index,
                             next_ident_level,
-                            false,
+                            None,
                         ));
                     }
                 }
@@ -196,10 +246,14 @@ impl<'a> PipelineGraph<'a> {
         }
     }
 
-    pub fn create_pipeline_vertex_ids(&self, selected_pipeline: &PipelineTableItem) -> Vec<String> {
-        let mut visited: RefCell<HashSet<&str>> = RefCell::new(HashSet::with_capacity(
-            selected_pipeline.graph.vertices.len(),
-        ));
+    /// Rows of the pipeline vertices table, in the source order. The rows of the
+    /// `collapsed` conditional branches (`if` vertex ids or `else` rows ids) are omitted.
+    pub fn create_pipeline_vertex_rows(
+        &self,
+        collapsed: Option<&HashSet<String>>,
+    ) -> Vec<PipelineVertexRow<'a>> {
+        let mut visited: RefCell<HashSet<&str>> =
+            RefCell::new(HashSet::with_capacity(self.data.len()));
         let mut stack: GraphVisitorStack = RefCell::new(Vec::new());
         let mut head_stack = self.heads.to_vec();
         if head_stack.is_empty() {
@@ -209,15 +263,27 @@ impl<'a> PipelineGraph<'a> {
         // Add first head
         let first_head = head_stack.pop().unwrap();
         visited.get_mut().insert(first_head);
-        stack.get_mut().push((first_head, None, 0, false));
+        stack.get_mut().push((first_head, None, 0, None));
 
-        let mut table_rows: Vec<String> = Vec::with_capacity(selected_pipeline.graph.edges.len());
-        while let Some((vertex_id, mut next_row_index, _, add_else_row)) = stack.get_mut().pop() {
+        let mut table_rows: Vec<PipelineVertexRow<'a>> = Vec::with_capacity(self.data.len());
+        while let Some((vertex_id, mut next_row_index, ident_level, else_of)) =
+            stack.get_mut().pop()
+        {
             visited.get_mut().insert(vertex_id);
 
-            let vertex = &self.data.get(vertex_id).unwrap().value;
-
-            let mut push_row = |row| {
+            let (vertex_id, vertex) = self
+                .data
+                .get_key_value(vertex_id)
+                .map(|(id, data)| (*id, data.value))
+                .unwrap();
+            let mut next_ident_level = ident_level;
+
+            let mut push_row = |kind| {
+                let row = PipelineVertexRow {
+                    kind,
+                    ident_level,
+                    hidden_rows: 0,
+                };
                 if let Some(i) = next_row_index {
                     table_rows.insert(i as usize, row);
                     next_row_index = Some(i + 1);
@@ -226,17 +292,22 @@ impl<'a> PipelineGraph<'a> {
                 }
             };
 
-            if add_else_row {
-                push_row(Uuid::new_v4().to_string());
+            if let Some(conditional_id) = else_of {
+                let (conditional_id, _) = self.data.get_key_value(conditional_id).unwrap();
+                push_row(PipelineVertexRowKind::Else { conditional_id });
             }
 
-            push_row(vertex.id.to_string());
+            push_row(PipelineVertexRowKind::Vertex(vertex_id));
+
+            if vertex.r#type == "if" {
+                next_ident_level += 1;
+            }
 
             Self::process_vertices_edges(
                 self,
                 &vertex.r#type,
                 vertex_id,
-                0,
+                next_ident_level,
                 next_row_index,
                 &mut visited,
                 &mut stack,
@@ -244,11 +315,141 @@ impl<'a> PipelineGraph<'a> {
 
             if stack.borrow().is_empty() && !head_stack.is_empty() {
                 while let Some(vertex_id) = head_stack.pop() {
-                    stack.get_mut().push((vertex_id, Some(0), 0, false));
+                    stack.get_mut().push((vertex_id, Some(0), 0, None));
+                }
+            }
+        }
+
+        match collapsed {
+            Some(collapsed) if !collapsed.is_empty() => Self::collapse_rows(table_rows, collapsed),
+            _ => table_rows,
+        }
+    }
+
+    /// Vertices grouped by layers from the inputs to the outputs, see `create_layers`.
+    pub fn create_vertices_layers(&self) -> Vec<Vec<&'a str>> {
+        let sort_by_source_fn = Self::sort_vertices_by_source();
+        let mut nodes: Vec<&'a str> = self.data.keys().copied().collect();
+        nodes.sort_by(|a, b| sort_by_source_fn(self.data[a].value, self.data[b].value));
+
+        let edges: Vec<(&'a str, &'a str)> = self
+            .vertices
+            .iter()
+            .flat_map(|(from, edges)| edges.iter().map(|edge| (*from, edge.vertex_id)))
+            .collect();
+
+        create_layers(&nodes, &edges)
+    }
+
+    fn collapse_rows(
+        rows: Vec<PipelineVertexRow<'a>>,
+        collapsed: &HashSet<String>,
+    ) -> Vec<PipelineVertexRow<'a>> {
+        let mut visible_rows: Vec<PipelineVertexRow<'a>> = Vec::with_capacity(rows.len());
+        // Index and depth of the collapsed row hiding the next rows
+        let mut collapsed_row: Option<(usize, i32)> = None;
+
+        for row in rows {
+            if let Some((index, depth)) = collapsed_row {
+                if row.depth() > depth {
+                    visible_rows[index].hidden_rows += 1;
+                    continue;
                 }
+                collapsed_row = None;
+            }
+
+            if collapsed.contains(&row.id()) {
+                collapsed_row = Some((visible_rows.len(), row.depth()));
+            }
+
+            visible_rows.push(row);
+        }
+
+        visible_rows
+    }
+}
+
+/// Groups the nodes by layers, placing each node on the layer after its furthest
+/// predecessor. The nodes of each layer are ordered by the position of their predecessors,
+/// reducing the edges crossings, then by their position on `nodes`. Nodes on cycles are
+/// placed on the first layer.
+pub fn create_layers<'a>(nodes: &[&'a str], edges: &[(&'a str, &'a str)]) -> Vec<Vec<&'a str>> {
+    let order: HashMap<&'a str, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+    let mut incoming_count: HashMap<&'a str, usize> = nodes.iter().map(|n| (*n, 0)).collect();
+    let mut predecessors: HashMap<&'a str, Vec<&'a str>> = HashMap::new();
+    let mut successors: HashMap<&'a str, Vec<&'a str>> = HashMap::new();
+    for (from, to) in edges {
+        *incoming_count.entry(to).or_default() += 1;
+        predecessors.entry(to).or_default().push(from);
+        successors.entry(from).or_default().push(to);
+    }
+
+    let mut ready: Vec<&'a str> = nodes
+        .iter()
+        .filter(|n| incoming_count[*n] == 0)
+        .copied()
+        .collect();
+
+    let mut node_layer: HashMap<&'a str, usize> = HashMap::with_capacity(nodes.len());
+    let mut layers: Vec<Vec<&'a str>> = Vec::new();
+    while let Some(node) = ready.pop() {
+        let layer = predecessors
+            .get(node)
+            .and_then(|p| p.iter().filter_map(|id| node_layer.get(id)).max())
+            .map(|l| l + 1)
+            .unwrap_or(0);
+
+        node_layer.insert(node, layer);
+        if layers.len() <= layer {
+            layers.resize(layer + 1, Vec::new());
+        }
+        layers[layer].push(node);
+
+        for successor in successors.get(node).into_iter().flatten() {
+            let count = incoming_count.get_mut(successor).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                ready.push(successor);
             }
         }
+    }
 
-        table_rows
+    for node in nodes {
+        if !node_layer.contains_key(node) {
+            if layers.is_empty() {
+                layers.push(Vec::new());
+            }
+            layers[0].push(node);
+        }
     }
+
+    let mut positions: HashMap<&'a str, f64> = HashMap::with_capacity(nodes.len());
+    for layer in layers.iter_mut() {
+        let barycenter = |node: &&'a str| {
+            let positions: Vec<f64> = predecessors
+                .get(node)
+                .into_iter()
+                .flatten()
+                .filter_map(|p| positions.get(p))
+                .copied()
+                .collect();
+            if positions.is_empty() {
+                0.0
+            } else {
+                positions.iter().sum::<f64>() / positions.len() as f64
+            }
+        };
+
+        layer.sort_by(|a, b| {
+            barycenter(a)
+                .total_cmp(&barycenter(b))
+                .then_with(|| order.get(a).cmp(&order.get(b)))
+        });
+
+        for (index, node) in layer.iter().enumerate() {
+            positions.insert(node, index as f64);
+        }
+    }
+
+    layers
 }
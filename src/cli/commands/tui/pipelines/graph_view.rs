@@ -0,0 +1,546 @@
+use std::collections::HashMap;
+
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::symbols::Marker;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::canvas::{Canvas, Line as CanvasLine, Rectangle};
+use ratatui::widgets::{Block, Borders};
+use ratatui::Frame;
+
+use crate::api::node::Vertex;
+use crate::api::stats::PipelineStats;
+use crate::commands::formatter::NumberFormatter;
+use crate::commands::tui::app::{App, AppData};
+use crate::commands::tui::pipelines::branches::BranchFlows;
+use crate::commands::tui::pipelines::graph::{create_layers, PipelineGraph};
+use crate::commands::tui::pipelines::topology::PipelinesTopology;
+use crate::commands::tui::widgets::utilization_color;
+
+// Vertices boxes size and spacing, in terminal cells at the 100% zoom
+const BOX_WIDTH: f64 = 24.0;
+const BOX_HEIGHT: f64 = 4.0;
+const LAYER_SPACING: f64 = 32.0;
+// Wider, so the links labels fit between the pipelines
+const TOPOLOGY_LAYER_SPACING: f64 = 48.0;
+const TOPOLOGY_LINK_LABEL_WIDTH: usize = 22;
+const ROW_SPACING: f64 = 6.0;
+
+// Terminal cells represented by each cell of the canvas
+const ZOOM_LEVELS: [f64; 5] = [1.0, 1.5, 2.0, 3.0, 4.0];
+const PAN_STEP_X: f64 = 8.0;
+const PAN_STEP_Y: f64 = 3.0;
+
+/// Panning offset and zoom of the pipeline graph view.
+#[derive(Default)]
+pub struct PipelineGraphViewport {
+    offset_x: f64,
+    offset_y: f64,
+    zoom_level: usize,
+}
+
+impl PipelineGraphViewport {
+    pub fn zoom(&self) -> f64 {
+        ZOOM_LEVELS[self.zoom_level]
+    }
+
+    pub fn pan(&mut self, x_steps: i32, y_steps: i32) {
+        self.offset_x = (self.offset_x + x_steps as f64 * PAN_STEP_X * self.zoom()).max(0.0);
+        self.offset_y = (self.offset_y + y_steps as f64 * PAN_STEP_Y * self.zoom()).max(0.0);
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom_level = self.zoom_level.saturating_sub(1);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom_level = (self.zoom_level + 1).min(ZOOM_LEVELS.len() - 1);
+    }
+
+    pub fn reset_position(&mut self) {
+        self.offset_x = 0.0;
+        self.offset_y = 0.0;
+    }
+}
+
+/// Box of a graph node.
+pub(crate) struct GraphNode {
+    pub label: String,
+    pub label_color: Color,
+    pub details: Option<String>,
+    pub color: Color,
+}
+
+pub(crate) struct GraphEdge<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub color: Color,
+    /// Printed next to the edge, above it or below when `label_below` is set
+    pub label: Option<String>,
+    pub label_below: bool,
+}
+
+pub(crate) fn draw_pipeline_graph(f: &mut Frame, app: &App, area: Rect) {
+    let title = "Pipeline graph";
+    let Some(selected_pipeline) = app.pipelines_state.pipelines_table.selected_item() else {
+        draw_graph(
+            f,
+            area,
+            title,
+            &app.pipelines_state.graph_viewport,
+            &GraphLayout::default(),
+        );
+        return;
+    };
+
+    let data = app.data.read().unwrap();
+    let pipeline_stats = data
+        .node_stats()
+        .and_then(|s| s.pipelines.get(&selected_pipeline.name));
+
+    let graph = PipelineGraph::from(&selected_pipeline.graph);
+    let branch_flows = pipeline_stats.map(|s| BranchFlows::from_stats(&graph, s));
+
+    let nodes: HashMap<&str, GraphNode> = graph
+        .data
+        .iter()
+        .map(|(id, data)| {
+            (
+                *id,
+                vertex_node(data.value, pipeline_stats, branch_flows.as_ref()),
+            )
+        })
+        .collect();
+
+    let edges: Vec<GraphEdge> = graph
+        .vertices
+        .iter()
+        .flat_map(|(from, edges)| {
+            edges.iter().map(|edge| {
+                let (color, label) = match edge.when {
+                    Some(true) => (Color::Green, Some("T".to_string())),
+                    Some(false) => (Color::Red, Some("F".to_string())),
+                    None => (Color::DarkGray, None),
+                };
+
+                GraphEdge {
+                    from,
+                    to: edge.vertex_id,
+                    color,
+                    label,
+                    label_below: edge.when == Some(false),
+                }
+            })
+        })
+        .collect();
+
+    let layout = GraphLayout::new(graph.create_vertices_layers(), nodes, edges, LAYER_SPACING);
+    draw_graph(f, area, title, &app.pipelines_state.graph_viewport, &layout);
+}
+
+/// Pipelines linked by the pipeline-to-pipeline plugins, with the events rates of each
+/// link. Addresses without consumer or producer are drawn as red nodes.
+pub(crate) fn draw_pipelines_topology(f: &mut Frame, app: &App, area: Rect) {
+    let data = app.data.read().unwrap();
+    let topology = data
+        .node_stats()
+        .map(|s| PipelinesTopology::new(s, data.derived_metrics()))
+        .unwrap_or_default();
+
+    let without_consumer: Vec<String> = topology
+        .addresses_without_consumer()
+        .map(|a| format!("address:{}", a))
+        .collect();
+    let without_producer: Vec<String> = topology
+        .addresses_without_producer()
+        .map(|a| format!("address:{}", a))
+        .collect();
+
+    let title = if topology.is_empty() {
+        "Pipelines topology (no pipeline-to-pipeline plugins)".to_string()
+    } else if without_consumer.is_empty() && without_producer.is_empty() {
+        "Pipelines topology".to_string()
+    } else {
+        format!(
+            "Pipelines topology ⚠ {} without consumer, {} without producer",
+            without_consumer.len(),
+            without_producer.len()
+        )
+    };
+
+    let selected_pipeline = app.pipelines_state.selected_pipeline_name();
+    let mut nodes: HashMap<&str, GraphNode> = HashMap::new();
+    let mut edges: Vec<GraphEdge> = Vec::new();
+
+    for (address, endpoints) in &topology.addresses {
+        for endpoint in endpoints.producers.iter().chain(endpoints.consumers.iter()) {
+            nodes
+                .entry(endpoint.pipeline.as_str())
+                .or_insert_with(|| pipeline_node(&endpoint.pipeline, selected_pipeline, &data));
+        }
+
+        let placeholder_id = format!("address:{}", address);
+        let placeholder = without_consumer
+            .iter()
+            .chain(without_producer.iter())
+            .find(|id| **id == placeholder_id);
+
+        if let Some(placeholder) = placeholder {
+            let details = if endpoints.consumers.is_empty() {
+                "no consumer"
+            } else {
+                "no producer"
+            };
+            nodes.insert(
+                placeholder.as_str(),
+                GraphNode {
+                    label: format!("⚠ {}", address),
+                    label_color: Color::Red,
+                    details: Some(details.to_string()),
+                    color: Color::Red,
+                },
+            );
+        }
+
+        for producer in &endpoints.producers {
+            match (&placeholder, endpoints.consumers.is_empty()) {
+                (Some(placeholder), true) => edges.push(GraphEdge {
+                    from: producer.pipeline.as_str(),
+                    to: placeholder.as_str(),
+                    color: Color::Red,
+                    label: None,
+                    label_below: false,
+                }),
+                _ => {
+                    for consumer in &endpoints.consumers {
+                        let label = producer.events_per_second.map(|rate| {
+                            truncate(
+                                &format!("{} {} e/s", address, rate.format_number().trim()),
+                                TOPOLOGY_LINK_LABEL_WIDTH,
+                            )
+                        });
+
+                        edges.push(GraphEdge {
+                            from: producer.pipeline.as_str(),
+                            to: consumer.pipeline.as_str(),
+                            color: Color::Cyan,
+                            label,
+                            label_below: false,
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(placeholder) = placeholder.filter(|_| endpoints.producers.is_empty()) {
+            for consumer in &endpoints.consumers {
+                edges.push(GraphEdge {
+                    from: placeholder.as_str(),
+                    to: consumer.pipeline.as_str(),
+                    color: Color::Red,
+                    label: None,
+                    label_below: false,
+                });
+            }
+        }
+    }
+
+    let mut node_ids: Vec<&str> = nodes.keys().copied().collect();
+    node_ids.sort();
+    let links: Vec<(&str, &str)> = edges.iter().map(|e| (e.from, e.to)).collect();
+    let layers = create_layers(&node_ids, &links);
+
+    let layout = GraphLayout::new(layers, nodes, edges, TOPOLOGY_LAYER_SPACING);
+    draw_graph(
+        f,
+        area,
+        &title,
+        &app.pipelines_state.graph_viewport,
+        &layout,
+    );
+}
+
+fn pipeline_node(pipeline: &str, selected_pipeline: Option<&String>, data: &AppData) -> GraphNode {
+    let details = data
+        .derived_metrics()
+        .pipeline_rates(pipeline)
+        .map(|rates| {
+            format!(
+                "{} → {} e/s",
+                rates.events_in_per_second().format_number().trim(),
+                rates.events_out_per_second().format_number().trim()
+            )
+        });
+
+    let color = if selected_pipeline.is_some_and(|p| p == pipeline) {
+        Color::Yellow
+    } else {
+        Color::Cyan
+    };
+
+    GraphNode {
+        label: pipeline.to_string(),
+        label_color: color,
+        details,
+        color,
+    }
+}
+
+/// Nodes and edges of a graph, with the nodes boxes placed by layers from left to right.
+#[derive(Default)]
+pub(crate) struct GraphLayout<'a> {
+    nodes: HashMap<&'a str, (f64, f64, GraphNode)>,
+    edges: Vec<GraphEdge<'a>>,
+    layer_spacing: f64,
+}
+
+impl<'a> GraphLayout<'a> {
+    pub fn new(
+        layers: Vec<Vec<&'a str>>,
+        mut nodes: HashMap<&'a str, GraphNode>,
+        edges: Vec<GraphEdge<'a>>,
+        layer_spacing: f64,
+    ) -> Self {
+        let mut placed_nodes = HashMap::with_capacity(nodes.len());
+        for (layer_index, layer) in layers.iter().enumerate() {
+            for (row_index, id) in layer.iter().enumerate() {
+                if let Some(node) = nodes.remove(id) {
+                    let x = layer_index as f64 * layer_spacing;
+                    let y = -(row_index as f64 * ROW_SPACING) - BOX_HEIGHT;
+                    placed_nodes.insert(*id, (x, y, node));
+                }
+            }
+        }
+
+        GraphLayout {
+            nodes: placed_nodes,
+            edges,
+            layer_spacing,
+        }
+    }
+}
+
+pub(crate) fn draw_graph(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    viewport: &PipelineGraphViewport,
+    layout: &GraphLayout,
+) {
+    let zoom = viewport.zoom();
+    let block = Block::default().borders(Borders::ALL).title(format!(
+        "{} [←↑↓→ pan, +/- zoom {}%]",
+        title,
+        (100.0 / zoom).round()
+    ));
+
+    let width = area.width.saturating_sub(2) as f64 * zoom;
+    let height = area.height.saturating_sub(2) as f64 * zoom;
+    let left = viewport.offset_x - 1.0;
+    let top = -viewport.offset_y + 1.0;
+
+    // Text is never scaled, so it's only printed when it fits into the boxes
+    let label_width = (BOX_WIDTH / zoom) as usize;
+    let box_lines = (BOX_HEIGHT / zoom) as usize;
+    let layer_spacing = layout.layer_spacing;
+
+    let canvas = Canvas::default()
+        .block(block)
+        .marker(Marker::Braille)
+        .x_bounds([left, left + width])
+        .y_bounds([top - height, top])
+        .paint(|ctx| {
+            for edge in &layout.edges {
+                let (Some((from_x, from_y, _)), Some((to_x, to_y, _))) =
+                    (layout.nodes.get(edge.from), layout.nodes.get(edge.to))
+                else {
+                    continue;
+                };
+
+                let (x1, y1) = (from_x + BOX_WIDTH, from_y + BOX_HEIGHT / 2.0);
+                let (x2, y2) = (*to_x, to_y + BOX_HEIGHT / 2.0);
+
+                // Edges skipping layers go through the gap below the source row,
+                // so they don't cross the boxes in between
+                let points = if x2 - x1 > layer_spacing {
+                    let lane_y = from_y - (ROW_SPACING - BOX_HEIGHT) / 2.0;
+                    let bend = (layer_spacing - BOX_WIDTH) / 2.0;
+                    vec![(x1, y1), (x1 + bend, lane_y), (x2 - bend, lane_y), (x2, y2)]
+                } else {
+                    vec![(x1, y1), (x2, y2)]
+                };
+
+                for segment in points.windows(2) {
+                    ctx.draw(&CanvasLine {
+                        x1: segment[0].0,
+                        y1: segment[0].1,
+                        x2: segment[1].0,
+                        y2: segment[1].1,
+                        color: edge.color,
+                    });
+                }
+
+                if let Some(label) = &edge.label {
+                    if label_width > 2 {
+                        // Centered on the first segment, so the labels of the edges
+                        // leaving the same node don't overlap
+                        let label_x = (points[0].0 + points[1].0) / 2.0
+                            - label.chars().count() as f64 / 2.0 * zoom;
+                        let label_y = (points[0].1 + points[1].1) / 2.0
+                            + if edge.label_below { -zoom } else { zoom };
+                        ctx.print(
+                            label_x,
+                            label_y,
+                            Span::styled(label.to_string(), edge.color),
+                        );
+                    }
+                }
+            }
+
+            ctx.layer();
+
+            for (x, y, node) in layout.nodes.values() {
+                ctx.draw(&Rectangle {
+                    x: *x,
+                    y: *y,
+                    width: BOX_WIDTH,
+                    height: BOX_HEIGHT,
+                    color: node.color,
+                });
+
+                if box_lines < 3 || label_width < 5 {
+                    continue;
+                }
+
+                let text_x = x + zoom;
+                ctx.print(
+                    text_x,
+                    y + BOX_HEIGHT - zoom,
+                    Line::styled(
+                        truncate(&node.label, label_width - 2),
+                        Style::default().fg(node.label_color),
+                    ),
+                );
+
+                if box_lines >= 4 {
+                    if let Some(details) = &node.details {
+                        ctx.print(
+                            text_x,
+                            y + BOX_HEIGHT - 2.0 * zoom,
+                            Line::styled(
+                                truncate(details, label_width - 2),
+                                Style::default().fg(Color::DarkGray),
+                            ),
+                        );
+                    }
+                }
+            }
+        });
+
+    f.render_widget(canvas, area);
+}
+
+/// Vertex box, workers plugins are colored by their worker utilization.
+fn vertex_node(
+    vertex: &Vertex,
+    pipeline_stats: Option<&PipelineStats>,
+    branch_flows: Option<&BranchFlows>,
+) -> GraphNode {
+    let (label, details, color) = match vertex.r#type.as_str() {
+        "if" => {
+            let details = branch_flows
+                .and_then(|f| f.split(&vertex.id))
+                .and_then(|split| Some((split.percentage(true)?, split.percentage(false)?)))
+                .map(|(when_true, when_false)| {
+                    format!(
+                        "T {}% F {}%",
+                        when_true.strip_number_decimals(1),
+                        when_false.strip_number_decimals(1)
+                    )
+                });
+
+            (format!("if {}", vertex.condition), details, Color::Magenta)
+        }
+        "queue" => {
+            let details = pipeline_stats.map(|stats| {
+                format!(
+                    "{} BP {}",
+                    stats.queue.r#type,
+                    stats
+                        .flow
+                        .queue_backpressure
+                        .current
+                        .strip_number_decimals(2)
+                )
+            });
+
+            ("queue".to_string(), details, Color::Cyan)
+        }
+        _ => {
+            let label = if vertex.explicit_id {
+                format!("{} ({})", vertex.config_name, vertex.id)
+            } else {
+                vertex.config_name.to_string()
+            };
+
+            let flow = pipeline_stats
+                .and_then(|s| s.plugins.get(&vertex.id))
+                .and_then(|p| p.flow.as_ref());
+
+            if vertex.plugin_type == "input" {
+                let details = flow
+                    .and_then(|f| f.throughput.as_ref())
+                    .map(|t| format!("{} e/s", t.current.format_number().trim()));
+                return GraphNode {
+                    label_color: Color::Blue,
+                    label,
+                    details,
+                    color: Color::Blue,
+                };
+            }
+
+            let utilization = flow
+                .and_then(|f| f.worker_utilization.as_ref())
+                .map(|u| u.current);
+            let millis_per_event = flow
+                .and_then(|f| f.worker_millis_per_event.as_ref())
+                .map(|m| m.current);
+
+            let details = utilization.map(|utilization| {
+                format!(
+                    "{}% {} ms/e",
+                    utilization.strip_number_decimals(1),
+                    millis_per_event
+                        .unwrap_or_default()
+                        .strip_number_decimals(2)
+                )
+            });
+
+            (label, details, utilization_color(utilization))
+        }
+    };
+
+    // Conditionals are labeled like on the vertices table
+    let label_color = if vertex.r#type == "if" {
+        Color::Red
+    } else {
+        color
+    };
+
+    GraphNode {
+        label,
+        label_color,
+        details,
+        color,
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
@@ -1,3 +1,7 @@
+pub(crate) mod branches;
 pub(crate) mod graph;
+pub(crate) mod graph_view;
+pub(crate) mod source;
 pub(crate) mod state;
+pub(crate) mod topology;
 pub(crate) mod ui;
@@ -0,0 +1,196 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+
+use crate::api::node::VertexMetaSource;
+
+const KEYWORDS: &[&str] = &["if", "else", "and", "or", "xor", "nand", "in", "not"];
+const SECTIONS: &[&str] = &["input", "filter", "output"];
+
+pub(crate) type SourceLines = Rc<Vec<String>>;
+
+/// Pipeline configuration files referenced by the vertices `meta.source`, loaded
+/// from the local file system, optionally relative to the `--config-root` directory.
+pub struct SourceFiles {
+    config_root: Option<PathBuf>,
+    cache: RefCell<HashMap<String, Result<SourceLines, String>>>,
+}
+
+impl SourceFiles {
+    pub fn new(config_root: Option<PathBuf>) -> Self {
+        SourceFiles {
+            config_root,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    pub fn lines(&self, source: &VertexMetaSource) -> Result<SourceLines, String> {
+        if source.protocol != "file" {
+            return Err(format!(
+                "Source '{}' is not a file and can't be displayed",
+                source.id
+            ));
+        }
+
+        if let Some(result) = self.cache.borrow().get(&source.id) {
+            return result.clone();
+        }
+
+        let result = match self.resolve_path(&source.id) {
+            Some(path) => fs::read_to_string(&path)
+                .map(|content| Rc::new(content.lines().map(|l| l.to_string()).collect()))
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e)),
+            None => Err(format!(
+                "File {} not found, use --config-root to set the pipelines configuration directory",
+                source.id
+            )),
+        };
+
+        self.cache
+            .borrow_mut()
+            .insert(source.id.to_string(), result.clone());
+        result
+    }
+
+    /// Logstash reports the absolute path of the file on its host. When a root directory
+    /// is set, the file is looked up on the same relative path, or directly on the root.
+    fn resolve_path(&self, id: &str) -> Option<PathBuf> {
+        let candidates = match &self.config_root {
+            None => vec![PathBuf::from(id)],
+            Some(root) => {
+                let mut candidates = vec![root.join(id.trim_start_matches('/'))];
+                if let Some(file_name) = Path::new(id).file_name() {
+                    candidates.push(root.join(file_name));
+                }
+                candidates
+            }
+        };
+
+        candidates.into_iter().find(|p| p.is_file())
+    }
+}
+
+/// Returns the 0-based first and last lines of the block starting at the given
+/// 1-based position, ending at the `}` that closes its first `{`.
+pub(crate) fn find_block_lines(lines: &[String], line: u32, column: u32) -> (usize, usize) {
+    let start_line = (line.max(1) as usize - 1).min(lines.len().saturating_sub(1));
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+
+    for (index, content) in lines.iter().enumerate().skip(start_line) {
+        let skip = if index == start_line {
+            column.max(1) as usize - 1
+        } else {
+            0
+        };
+
+        let mut chars = content.chars().skip(skip);
+        while let Some(c) = chars.next() {
+            match (quote, c) {
+                (Some(_), '\\') => {
+                    chars.next();
+                }
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                // Regular expressions are delimited by `/`, and might contain braces
+                (None, '"' | '\'' | '/') => quote = Some(c),
+                (None, '#') => break,
+                (None, '{') => depth += 1,
+                (None, '}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return (start_line, index);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    (start_line, start_line)
+}
+
+/// Splits a configuration line into styled spans.
+pub(crate) fn highlight_line(line: &str) -> Vec<Span<'static>> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut spans = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let style = match c {
+            '#' => {
+                i = chars.len();
+                Style::default().fg(Color::DarkGray)
+            }
+            '"' | '\'' | '/' => {
+                i += 1;
+                while i < chars.len() && chars[i] != c {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i = (i + 1).min(chars.len());
+                Style::default().fg(Color::Green)
+            }
+            '[' => {
+                while i < chars.len() && chars[i] != ']' && chars[i] != ' ' {
+                    i += 1;
+                }
+                if i < chars.len() && chars[i] == ']' {
+                    i += 1;
+                }
+                Style::default().fg(Color::Cyan)
+            }
+            '=' if chars.get(i + 1) == Some(&'>') => {
+                i += 2;
+                Style::default().fg(Color::DarkGray)
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+
+                let word: String = chars[start..i].iter().collect();
+                let next_char = chars[i..].iter().find(|c| !c.is_whitespace());
+                if KEYWORDS.contains(&word.as_str()) {
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD)
+                } else if SECTIONS.contains(&word.as_str()) && next_char == Some(&'{') {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else if word.chars().all(|c| c.is_ascii_digit()) {
+                    Style::default().fg(Color::Yellow)
+                } else if next_char == Some(&'{') {
+                    Style::default()
+                        .fg(Color::Blue)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                }
+            }
+            _ => {
+                i += 1;
+                Style::default()
+            }
+        };
+
+        spans.push(Span::styled(
+            chars[start..i].iter().collect::<String>(),
+            style,
+        ));
+    }
+
+    spans
+}
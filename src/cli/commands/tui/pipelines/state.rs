@@ -1,26 +1,66 @@
 use crossterm::event::{KeyCode, KeyEvent};
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use std::path::PathBuf;
 
-use crate::api::node::{GraphDefinition, NodeInfo};
+use crate::api::node::{GraphDefinition, NodeInfo, VertexMetaSource};
 use crate::commands::tui::app::AppData;
 use crate::commands::tui::events::EventsListener;
-use crate::commands::tui::pipelines::graph::PipelineGraph;
+use crate::commands::tui::pipelines::graph::{
+    is_branch_row_id, PipelineGraph, PipelineVertexRow, PipelineVertexRowKind,
+};
+use crate::commands::tui::pipelines::graph_view::PipelineGraphViewport;
+use crate::commands::tui::pipelines::source::SourceFiles;
+use crate::commands::tui::search::Search;
 use crate::commands::tui::widgets::StatefulTable;
 
 pub const PIPELINE_VERTEX_LIST: usize = 0;
 pub const PIPELINE_VERTEX_VIEW: usize = 1;
 
+// Number of lines displayed before the selected vertex source line
+pub const SOURCE_CONTEXT_LINES: i32 = 3;
+const SOURCE_SCROLL_LINES: i32 = 10;
+
 pub struct PipelineTableItem {
     pub name: String,
     pub graph: GraphDefinition,
 }
 
+/// Rows of the pipeline vertices table. While searching, only the vertices matching the
+/// query are listed, including the ones hidden by the `collapsed` branches.
+pub(crate) fn vertex_rows<'a>(
+    graph: &PipelineGraph<'a>,
+    collapsed: Option<&HashSet<String>>,
+    search: &Search,
+) -> Vec<PipelineVertexRow<'a>> {
+    if !search.is_active() {
+        return graph.create_pipeline_vertex_rows(collapsed);
+    }
+
+    graph
+        .create_pipeline_vertex_rows(None)
+        .into_iter()
+        .filter(|row| match row.kind {
+            PipelineVertexRowKind::Vertex(vertex_id) => {
+                graph.data.get(vertex_id).is_some_and(|d| {
+                    search.matches(&[&d.value.id, &d.value.config_name, &d.value.condition])
+                })
+            }
+            PipelineVertexRowKind::Else { .. } => false,
+        })
+        .collect()
+}
+
 impl StatefulTable<PipelineTableItem> {
-    fn update(&mut self, data: &AppData) {
+    fn update(&mut self, data: &AppData, search: &Search) {
         if let Some(node_info) = &data.node_info() {
             if let Some(pipelines) = &node_info.pipelines {
                 let mut new_items = Vec::with_capacity(pipelines.len());
                 for (name, pipeline_info) in pipelines {
+                    if !search.matches(&[name]) {
+                        continue;
+                    }
+
                     let new_item = PipelineTableItem {
                         name: name.to_string(),
                         graph: pipeline_info.graph.graph.clone(),
@@ -37,6 +77,8 @@ impl StatefulTable<PipelineTableItem> {
                         .position(|p| p.name == selected_pipeline_name)
                     {
                         self.state.select(Some(new_index));
+                    } else {
+                        self.state.select(None);
                     }
                 }
 
@@ -53,6 +95,8 @@ impl StatefulTable<SelectedPipelineVertexTableItem> {
         &mut self,
         node_info: &Option<&NodeInfo>,
         selected_pipeline: &Option<&PipelineTableItem>,
+        collapsed_branches: Option<&HashSet<String>>,
+        search: &Search,
     ) {
         if selected_pipeline.is_none() || node_info.is_none() {
             self.items = vec![];
@@ -60,8 +104,21 @@ impl StatefulTable<SelectedPipelineVertexTableItem> {
             return;
         }
 
-        self.items = PipelineGraph::from(&selected_pipeline.unwrap().graph)
-            .create_pipeline_vertex_ids(selected_pipeline.unwrap());
+        let selected_vertex = self.selected_item().cloned();
+        let graph = PipelineGraph::from(&selected_pipeline.unwrap().graph);
+        self.items = vertex_rows(&graph, collapsed_branches, search)
+            .iter()
+            .map(|row| row.id())
+            .collect();
+
+        // Keeps the selected row when it's moved by collapsing or expanding branches
+        if let Some(selected_vertex) = selected_vertex {
+            if let Some(index) = self.items.iter().position(|id| *id == selected_vertex) {
+                self.select(Some(index));
+            } else if self.state.selected().is_some_and(|i| i >= self.items.len()) {
+                self.select(self.items.len().checked_sub(1));
+            }
+        }
     }
 }
 
@@ -70,18 +127,46 @@ pub struct PipelinesState<'a> {
     pub pipelines_table: StatefulTable<PipelineTableItem>,
     pub selected_pipeline_vertex: StatefulTable<SelectedPipelineVertexTableItem>,
     pub show_selected_pipeline_charts: bool,
+    pub show_selected_pipeline_advisor: bool,
     pub show_selected_vertex_details: bool,
+    pub show_selected_vertex_source: bool,
+    /// Displays the vertices values of the last sampling interval instead of the lifetime ones
+    pub show_recent_values: bool,
+    /// Displays the selected pipeline vertices as a graph instead of a table
+    pub show_pipeline_graph: bool,
+    /// Displays the node pipelines linked by the pipeline-to-pipeline plugins
+    pub show_pipelines_topology: bool,
+    pub graph_viewport: PipelineGraphViewport,
+    pub selected_vertex_source_scroll: i32,
+    pub source_files: SourceFiles,
+    /// Collapsed `if` and `else` rows ids, per pipeline
+    collapsed_branches: HashMap<String, HashSet<String>>,
+    /// Name filter of the pipelines list
+    pub pipelines_search: Search,
+    /// Id, name or condition filter of the selected pipeline vertices
+    pub vertices_search: Search,
     _marker: PhantomData<&'a ()>,
 }
 
 impl PipelinesState<'_> {
-    pub fn new() -> Self {
+    pub fn new(config_root: Option<PathBuf>) -> Self {
         PipelinesState {
             current_focus: 0,
             pipelines_table: StatefulTable::new(),
             selected_pipeline_vertex: StatefulTable::new(),
             show_selected_pipeline_charts: false,
+            show_selected_pipeline_advisor: false,
             show_selected_vertex_details: false,
+            show_selected_vertex_source: false,
+            show_recent_values: false,
+            show_pipeline_graph: false,
+            show_pipelines_topology: false,
+            graph_viewport: PipelineGraphViewport::default(),
+            selected_vertex_source_scroll: 0,
+            source_files: SourceFiles::new(config_root),
+            collapsed_branches: HashMap::new(),
+            pipelines_search: Search::default(),
+            vertices_search: Search::default(),
             _marker: PhantomData,
         }
     }
@@ -93,11 +178,257 @@ impl PipelinesState<'_> {
     pub fn selected_pipeline_vertex(&self) -> Option<&String> {
         self.selected_pipeline_vertex.selected_item()
     }
+
+    pub fn collapsed_branches(&self, pipeline: &str) -> Option<&HashSet<String>> {
+        self.collapsed_branches.get(pipeline)
+    }
+
+    fn update_selected_pipeline_vertices(&mut self, app_data: &AppData) {
+        let selected_pipeline = self.pipelines_table.selected_item();
+        let collapsed_branches =
+            selected_pipeline.and_then(|p| self.collapsed_branches.get(&p.name));
+
+        self.selected_pipeline_vertex.update(
+            &app_data.node_info(),
+            &selected_pipeline,
+            collapsed_branches,
+            &self.vertices_search,
+        );
+    }
+
+    /// Collapses or expands the branch of the selected `if` or `else` row.
+    fn toggle_selected_branch(&mut self, app_data: &AppData) {
+        let Some(pipeline) = self.pipelines_table.selected_item() else {
+            return;
+        };
+
+        let Some(row_id) = self.selected_pipeline_vertex.selected_item() else {
+            return;
+        };
+
+        if !is_branch_row_id(row_id, &pipeline.graph) {
+            return;
+        }
+
+        let collapsed = self
+            .collapsed_branches
+            .entry(pipeline.name.to_string())
+            .or_default();
+
+        if !collapsed.remove(row_id) {
+            collapsed.insert(row_id.to_string());
+        }
+
+        self.update_selected_pipeline_vertices(app_data);
+    }
+
+    fn collapse_all_branches(&mut self, app_data: &AppData) {
+        let Some(pipeline) = self.pipelines_table.selected_item() else {
+            return;
+        };
+
+        let rows_ids: Vec<String> = PipelineGraph::from(&pipeline.graph)
+            .create_pipeline_vertex_rows(None)
+            .iter()
+            .map(|row| row.id())
+            .collect();
+
+        let collapsed: HashSet<String> = rows_ids
+            .iter()
+            .filter(|id| is_branch_row_id(id, &pipeline.graph))
+            .cloned()
+            .collect();
+
+        self.collapsed_branches
+            .insert(pipeline.name.to_string(), collapsed);
+
+        // Hidden rows are right after their collapsed row, which is selected instead
+        let selected_position = self
+            .selected_pipeline_vertex()
+            .and_then(|selected| rows_ids.iter().position(|id| id == selected));
+
+        self.update_selected_pipeline_vertices(app_data);
+
+        if let Some(position) = selected_position {
+            let visible_index = rows_ids[..=position].iter().rev().find_map(|id| {
+                self.selected_pipeline_vertex
+                    .items
+                    .iter()
+                    .position(|item| item == id)
+            });
+            self.selected_pipeline_vertex.select(visible_index);
+        }
+    }
+
+    fn expand_all_branches(&mut self, app_data: &AppData) {
+        let Some(pipeline) = self.pipelines_table.selected_item() else {
+            return;
+        };
+
+        self.collapsed_branches.remove(&pipeline.name);
+        self.update_selected_pipeline_vertices(app_data);
+    }
+
+    /// Selects the `if` or `else` row enclosing the selected row, or the `if` row
+    /// of the selected `else` row.
+    fn select_parent_branch(&mut self) {
+        let Some(pipeline) = self.pipelines_table.selected_item() else {
+            return;
+        };
+
+        let Some(selected_index) = self.selected_pipeline_vertex.state.selected() else {
+            return;
+        };
+
+        let graph = PipelineGraph::from(&pipeline.graph);
+        let rows = vertex_rows(
+            &graph,
+            self.collapsed_branches.get(&pipeline.name),
+            &self.vertices_search,
+        );
+
+        let Some(selected_row) = rows.get(selected_index) else {
+            return;
+        };
+
+        let parent_index = match selected_row.kind {
+            PipelineVertexRowKind::Else { conditional_id } => rows[..selected_index]
+                .iter()
+                .rposition(|row| matches!(row.kind, PipelineVertexRowKind::Vertex(id) if id == conditional_id)),
+            PipelineVertexRowKind::Vertex(_) => rows[..selected_index]
+                .iter()
+                .rposition(|row| row.depth() < selected_row.depth()),
+        };
+
+        if parent_index.is_some() {
+            self.selected_pipeline_vertex.select(parent_index);
+            self.selected_vertex_source_scroll = 0;
+        }
+    }
+
+    /// Selects the pipeline and its vertex, expanding the collapsed branches hiding it.
+    pub fn select_vertex(&mut self, pipeline: &str, vertex_id: &str, app_data: &AppData) {
+        self.pipelines_search.clear();
+        self.vertices_search.clear();
+        self.pipelines_table
+            .update(app_data, &self.pipelines_search);
+        let Some(index) = self
+            .pipelines_table
+            .items
+            .iter()
+            .position(|p| p.name == pipeline)
+        else {
+            return;
+        };
+
+        self.pipelines_table.select(Some(index));
+        if let Some(collapsed) = self.collapsed_branches.get_mut(pipeline) {
+            let rows = PipelineGraph::from(&self.pipelines_table.items[index].graph)
+                .create_pipeline_vertex_rows(None);
+
+            let position = rows.iter().position(
+                |row| matches!(row.kind, PipelineVertexRowKind::Vertex(id) if id == vertex_id),
+            );
+
+            if let Some(position) = position {
+                let mut depth = rows[position].depth();
+                for row in rows[..position].iter().rev() {
+                    if row.depth() < depth {
+                        collapsed.remove(&row.id());
+                        depth = row.depth();
+                    }
+                }
+            }
+        }
+
+        self.update_selected_pipeline_vertices(app_data);
+        let vertex_index = self
+            .selected_pipeline_vertex
+            .items
+            .iter()
+            .position(|id| id == vertex_id);
+
+        self.selected_pipeline_vertex.select(vertex_index);
+        self.current_focus = if vertex_index.is_some() {
+            PIPELINE_VERTEX_VIEW
+        } else {
+            PIPELINE_VERTEX_LIST
+        };
+        self.show_pipeline_graph = false;
+        self.show_pipelines_topology = false;
+        self.graph_viewport.reset_position();
+        self.show_selected_pipeline_charts = false;
+        self.show_selected_pipeline_advisor = false;
+        self.show_selected_vertex_source = false;
+        self.show_selected_vertex_details = vertex_index.is_some();
+        self.selected_vertex_source_scroll = 0;
+    }
+
+    fn focus_pipelines_list(&mut self, _: &AppData) {
+        if self.current_focus == PIPELINE_VERTEX_VIEW {
+            self.current_focus = PIPELINE_VERTEX_LIST;
+            self.selected_pipeline_vertex.unselect();
+            self.show_selected_vertex_details = false;
+        }
+    }
+
+    fn focus_pipeline_vertices(&mut self, _: &AppData) {
+        if self.current_focus == PIPELINE_VERTEX_LIST
+            && !self.selected_pipeline_vertex.items.is_empty()
+        {
+            self.current_focus = PIPELINE_VERTEX_VIEW;
+            self.selected_pipeline_vertex.next();
+        }
+    }
+
+    fn is_showing_graph(&self) -> bool {
+        self.show_pipeline_graph || self.show_pipelines_topology
+    }
+
+    /// On the graph views, the arrows keys pan the graph once it's focused.
+    fn is_panning_graph(&self) -> bool {
+        self.is_showing_graph() && self.current_focus == PIPELINE_VERTEX_VIEW
+    }
+
+    fn selected_pipeline_vertex_source(&self, app_data: &AppData) -> Option<VertexMetaSource> {
+        let pipeline = self.selected_pipeline_name()?;
+        let vertex_id = self.selected_pipeline_vertex()?;
+        app_data
+            .node_info()?
+            .pipelines
+            .as_ref()?
+            .get(pipeline)?
+            .graph
+            .graph
+            .vertices
+            .iter()
+            .find(|v| &v.id == vertex_id)
+            .and_then(|v| v.meta.as_ref())
+            .map(|m| m.source.clone())
+    }
+
+    fn scroll_selected_vertex_source(&mut self, lines: i32, app_data: &AppData) {
+        let Some(source) = self.selected_pipeline_vertex_source(app_data) else {
+            return;
+        };
+
+        let Ok(source_lines) = self.source_files.lines(&source) else {
+            return;
+        };
+
+        // Keeps the first visible line within the file
+        let first_line = source.line as i32 - 1 - SOURCE_CONTEXT_LINES;
+        let scroll = self.selected_vertex_source_scroll + lines;
+        self.selected_vertex_source_scroll = scroll
+            .min(source_lines.len() as i32 - 1 - first_line)
+            .max(-first_line);
+    }
 }
 
 impl EventsListener for PipelinesState<'_> {
     fn update(&mut self, app_data: &AppData) {
-        self.pipelines_table.update(app_data);
+        self.pipelines_table
+            .update(app_data, &self.pipelines_search);
 
         let selected_pipeline_item = if self.pipelines_table.selected_item().is_none()
             && !self.pipelines_table.items.is_empty()
@@ -107,15 +438,30 @@ impl EventsListener for PipelinesState<'_> {
             self.pipelines_table.selected_item()
         };
 
-        self.selected_pipeline_vertex
-            .update(&app_data.node_info(), &selected_pipeline_item);
+        let collapsed_branches =
+            selected_pipeline_item.and_then(|p| self.collapsed_branches.get(&p.name));
+
+        self.selected_pipeline_vertex.update(
+            &app_data.node_info(),
+            &selected_pipeline_item,
+            collapsed_branches,
+            &self.vertices_search,
+        );
+    }
+
+    fn timeline_changed(&mut self, app_data: &AppData) {
+        self.update(app_data);
     }
 
     fn reset(&mut self) {
         // UI
         self.current_focus = PIPELINE_VERTEX_LIST;
         self.show_selected_pipeline_charts = false;
+        self.show_selected_pipeline_advisor = false;
         self.show_selected_vertex_details = false;
+        self.show_selected_vertex_source = false;
+        self.selected_vertex_source_scroll = 0;
+        self.source_files.clear();
 
         self.pipelines_table = StatefulTable::new();
         self.selected_pipeline_vertex = StatefulTable::new();
@@ -125,66 +471,168 @@ impl EventsListener for PipelinesState<'_> {
         if self.current_focus == PIPELINE_VERTEX_LIST {
             if self.pipelines_table.selected_item().is_some() {
                 self.show_selected_vertex_details = false;
+                self.show_selected_pipeline_advisor = false;
                 self.show_selected_pipeline_charts = !self.show_selected_pipeline_charts;
             }
         } else if self.current_focus == PIPELINE_VERTEX_VIEW {
             self.show_selected_pipeline_charts = false;
+            self.show_selected_pipeline_advisor = false;
             self.show_selected_vertex_details = !self.show_selected_vertex_details;
         }
     }
 
-    fn on_left(&mut self, _: &AppData) {
-        if self.current_focus == PIPELINE_VERTEX_VIEW {
-            self.current_focus = PIPELINE_VERTEX_LIST;
-            self.selected_pipeline_vertex.unselect();
-            self.show_selected_vertex_details = false;
+    fn on_left(&mut self, app_data: &AppData) {
+        if self.is_panning_graph() {
+            self.graph_viewport.pan(-1, 0);
+        } else {
+            self.focus_pipelines_list(app_data);
         }
     }
 
-    fn on_right(&mut self, _: &AppData) {
-        if self.current_focus == PIPELINE_VERTEX_LIST
-            && !self.selected_pipeline_vertex.items.is_empty()
-        {
-            self.current_focus = PIPELINE_VERTEX_VIEW;
-            self.selected_pipeline_vertex.next();
+    fn on_right(&mut self, app_data: &AppData) {
+        if self.is_panning_graph() {
+            self.graph_viewport.pan(1, 0);
+        } else {
+            self.focus_pipeline_vertices(app_data);
         }
     }
 
     fn on_up(&mut self, app_data: &AppData) {
         if self.current_focus == PIPELINE_VERTEX_LIST {
-            self.selected_pipeline_vertex
-                .update(&app_data.node_info(), &self.pipelines_table.previous());
+            self.pipelines_table.previous();
+            self.graph_viewport.reset_position();
+            self.update_selected_pipeline_vertices(app_data);
+        } else if self.is_panning_graph() {
+            self.graph_viewport.pan(0, -1);
         } else {
             self.selected_pipeline_vertex.previous();
+            self.selected_vertex_source_scroll = 0;
         }
     }
 
     fn on_down(&mut self, app_data: &AppData) {
         if self.current_focus == PIPELINE_VERTEX_LIST {
-            self.selected_pipeline_vertex
-                .update(&app_data.node_info(), &self.pipelines_table.next());
+            self.pipelines_table.next();
+            self.graph_viewport.reset_position();
+            self.update_selected_pipeline_vertices(app_data);
+        } else if self.is_panning_graph() {
+            self.graph_viewport.pan(0, 1);
         } else {
             self.selected_pipeline_vertex.next();
+            self.selected_vertex_source_scroll = 0;
+        }
+    }
+
+    fn search(&self) -> Option<&Search> {
+        if self.current_focus == PIPELINE_VERTEX_LIST {
+            Some(&self.pipelines_search)
+        } else {
+            Some(&self.vertices_search)
         }
     }
 
     fn on_other(&mut self, key_event: KeyEvent, app_data: &AppData) {
+        let search = if self.current_focus == PIPELINE_VERTEX_LIST {
+            &mut self.pipelines_search
+        } else {
+            &mut self.vertices_search
+        };
+
+        if search.on_key(key_event) {
+            self.update(app_data);
+            return;
+        }
+
+        if search.is_active() {
+            match key_event.code {
+                KeyCode::Char('n') => self.on_down(app_data),
+                KeyCode::Char('N') => self.on_up(app_data),
+                _ => {}
+            }
+        }
+
         // Tab navigation
         if key_event.code == KeyCode::Tab {
             if self.current_focus == PIPELINE_VERTEX_LIST {
-                self.on_right(app_data);
+                self.focus_pipeline_vertices(app_data);
             } else {
-                self.on_left(app_data);
+                self.focus_pipelines_list(app_data);
             }
 
             return;
         }
 
-        if let KeyCode::Char(c) = key_event.code {
-            if c.eq_ignore_ascii_case(&'c') && self.pipelines_table.selected_item().is_some() {
-                self.show_selected_vertex_details = false;
-                self.show_selected_pipeline_charts = !self.show_selected_pipeline_charts;
+        match key_event.code {
+            KeyCode::PageUp if self.show_selected_vertex_source => {
+                self.scroll_selected_vertex_source(-SOURCE_SCROLL_LINES, app_data);
             }
-        };
+            KeyCode::PageDown if self.show_selected_vertex_source => {
+                self.scroll_selected_vertex_source(SOURCE_SCROLL_LINES, app_data);
+            }
+            KeyCode::Char(c) => {
+                if c.eq_ignore_ascii_case(&'c') && self.pipelines_table.selected_item().is_some() {
+                    self.show_selected_vertex_details = false;
+                    self.show_selected_pipeline_advisor = false;
+                    self.show_selected_pipeline_charts = !self.show_selected_pipeline_charts;
+                }
+
+                if c.eq_ignore_ascii_case(&'a') && self.pipelines_table.selected_item().is_some() {
+                    self.show_selected_vertex_details = false;
+                    self.show_selected_pipeline_charts = false;
+                    self.show_selected_pipeline_advisor = !self.show_selected_pipeline_advisor;
+                }
+
+                if c.eq_ignore_ascii_case(&'l') {
+                    self.show_recent_values = !self.show_recent_values;
+                }
+
+                if c.eq_ignore_ascii_case(&'g') {
+                    self.show_pipelines_topology = false;
+                    self.show_pipeline_graph = !self.show_pipeline_graph;
+                    self.graph_viewport.reset_position();
+                }
+
+                if c.eq_ignore_ascii_case(&'o') {
+                    self.show_pipeline_graph = false;
+                    self.show_pipelines_topology = !self.show_pipelines_topology;
+                    self.graph_viewport.reset_position();
+                }
+
+                if self.is_showing_graph() {
+                    if c == '-' {
+                        self.graph_viewport.zoom_out();
+                    }
+
+                    if c == '+' || c == '=' {
+                        self.graph_viewport.zoom_in();
+                    }
+                } else {
+                    if c.eq_ignore_ascii_case(&'x') && self.current_focus == PIPELINE_VERTEX_VIEW {
+                        self.toggle_selected_branch(app_data);
+                    }
+
+                    if c == '-' {
+                        self.collapse_all_branches(app_data);
+                    }
+
+                    if c == '+' || c == '=' {
+                        self.expand_all_branches(app_data);
+                    }
+
+                    if c.eq_ignore_ascii_case(&'u') && self.current_focus == PIPELINE_VERTEX_VIEW {
+                        self.select_parent_branch();
+                    }
+                }
+
+                if c.eq_ignore_ascii_case(&'s') && self.current_focus == PIPELINE_VERTEX_VIEW {
+                    self.show_selected_pipeline_charts = false;
+                    self.show_selected_pipeline_advisor = false;
+                    self.show_selected_vertex_details = true;
+                    self.show_selected_vertex_source = !self.show_selected_vertex_source;
+                    self.selected_vertex_source_scroll = 0;
+                }
+            }
+            _ => {}
+        }
     }
 }
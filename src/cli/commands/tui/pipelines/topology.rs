@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use crate::api::stats::{NodeStats, Plugin};
+use crate::commands::tui::derived_metrics::DerivedMetrics;
+
+const PIPELINE_PLUGIN_NAME: &str = "pipeline";
+
+/// Pipeline-to-pipeline plugin of a pipeline.
+pub(crate) struct AddressEndpoint {
+    pub pipeline: String,
+    /// Lifetime events sent, or received by inputs
+    pub events: i64,
+    /// Events sent or received over the last sampling interval, per second
+    pub events_per_second: Option<f64>,
+}
+
+#[derive(Default)]
+pub(crate) struct PipelineAddress {
+    /// `pipeline` outputs sending to the address
+    pub producers: Vec<AddressEndpoint>,
+    /// `pipeline` inputs listening on the address
+    pub consumers: Vec<AddressEndpoint>,
+}
+
+/// Pipelines linked by the pipeline-to-pipeline plugins of the node, matching the `pipeline`
+/// outputs `send_to` with the `pipeline` inputs `address`.
+#[derive(Default)]
+pub(crate) struct PipelinesTopology {
+    pub addresses: BTreeMap<String, PipelineAddress>,
+}
+
+impl PipelinesTopology {
+    pub fn new(node_stats: &NodeStats, derived_metrics: &DerivedMetrics) -> Self {
+        let mut addresses: BTreeMap<String, PipelineAddress> = BTreeMap::new();
+        let endpoint =
+            |pipeline: &str, plugin_id: &str, plugin: &Plugin, input: bool| AddressEndpoint {
+                pipeline: pipeline.to_string(),
+                events: plugin.events.out,
+                events_per_second: derived_metrics.vertex_rates(pipeline, plugin_id).map(|r| {
+                    if input {
+                        r.events_out_per_second()
+                    } else {
+                        r.events_in_per_second()
+                    }
+                }),
+            };
+
+        for (pipeline, stats) in &node_stats.pipelines {
+            for (id, plugin) in &stats.plugins.inputs {
+                if !is_pipeline_plugin(plugin) {
+                    continue;
+                }
+
+                if let Some(address) = plugin.get_other("address", |v| v.as_str(), None) {
+                    addresses
+                        .entry(address.to_string())
+                        .or_default()
+                        .consumers
+                        .push(endpoint(pipeline, id, plugin, true));
+                }
+            }
+
+            for (id, plugin) in &stats.plugins.outputs {
+                if !is_pipeline_plugin(plugin) {
+                    continue;
+                }
+
+                let send_to = plugin.get_other("send_to", |v| v.as_array(), None);
+                for address in send_to.into_iter().flatten().filter_map(|v| v.as_str()) {
+                    addresses
+                        .entry(address.to_string())
+                        .or_default()
+                        .producers
+                        .push(endpoint(pipeline, id, plugin, false));
+                }
+            }
+        }
+
+        PipelinesTopology { addresses }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    /// Addresses that events are sent to, but no pipeline is listening on.
+    pub fn addresses_without_consumer(&self) -> impl Iterator<Item = &String> {
+        self.addresses
+            .iter()
+            .filter(|(_, a)| a.consumers.is_empty())
+            .map(|(address, _)| address)
+    }
+
+    /// Addresses listened on by pipelines, but no pipeline is sending events to.
+    pub fn addresses_without_producer(&self) -> impl Iterator<Item = &String> {
+        self.addresses
+            .iter()
+            .filter(|(_, a)| a.producers.is_empty())
+            .map(|(address, _)| address)
+    }
+}
+
+/// Only the plugins named differently are skipped, the unnamed ones are kept.
+fn is_pipeline_plugin(plugin: &Plugin) -> bool {
+    plugin
+        .name
+        .as_ref()
+        .is_none_or(|name| name == PIPELINE_PLUGIN_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn node_stats(value: serde_json::Value) -> NodeStats {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn matches_the_outputs_with_the_inputs_addresses() {
+        let stats = node_stats(json!({
+            "pipelines": {
+                "upstream": {"plugins": {"outputs": [
+                    {"id": "out", "name": "pipeline", "send_to": ["address"], "events": {"out": 10}},
+                    {"id": "es", "name": "elasticsearch", "send_to": ["address"], "events": {"out": 5}},
+                ]}},
+                "downstream": {"plugins": {"inputs": [
+                    {"id": "in", "name": "pipeline", "address": "address", "events": {"out": 10}},
+                ]}},
+            }
+        }));
+
+        let topology = PipelinesTopology::new(&stats, &DerivedMetrics::default());
+        let address = &topology.addresses["address"];
+
+        assert_eq!(address.producers.len(), 1);
+        assert_eq!(address.producers[0].pipeline, "upstream");
+        assert_eq!(address.producers[0].events, 10);
+        assert_eq!(address.consumers.len(), 1);
+        assert_eq!(address.consumers[0].pipeline, "downstream");
+    }
+
+    #[test]
+    fn keeps_the_unnamed_outputs() {
+        let stats = node_stats(json!({
+            "pipelines": {
+                "upstream": {"plugins": {"outputs": [
+                    {"id": "out", "send_to": ["address"], "events": {"out": 10}},
+                ]}},
+            }
+        }));
+
+        let topology = PipelinesTopology::new(&stats, &DerivedMetrics::default());
+
+        let producers = &topology.addresses["address"].producers;
+        assert_eq!(producers.len(), 1);
+        assert_eq!(producers[0].pipeline, "upstream");
+        assert_eq!(topology.addresses_without_consumer().count(), 1);
+    }
+}
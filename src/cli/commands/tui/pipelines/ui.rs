@@ -1,6 +1,5 @@
-use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::vec;
 
 use humansize::{format_size_i, DECIMAL};
@@ -11,18 +10,31 @@ use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap};
 use ratatui::Frame;
 use serde_json::Value;
 
-use crate::api::node::Vertex;
+use crate::api::node::{Vertex, VertexMetaSource};
 use crate::api::stats::PipelineStats;
+use crate::commands::analyze::advisor::{advise, Verdict};
+use crate::commands::analyze::analyzer::{Analysis, Bottleneck};
 use crate::commands::formatter::{DurationFormatter, NumberFormatter};
+use crate::commands::tui::alerts::rules::AlertSeverity;
+use crate::commands::tui::alerts::state::AlertsState;
 use crate::commands::tui::app::{App, AppData};
+use crate::commands::tui::derived_metrics::{CountersRates, DerivedMetrics};
 use crate::commands::tui::flow_charts::{
     draw_flow_metric_chart, draw_plugin_throughput_flow_chart,
 };
-use crate::commands::tui::pipelines::graph::PipelineGraph;
-use crate::commands::tui::pipelines::state::PipelineTableItem;
+use crate::commands::tui::pipelines::branches::BranchFlows;
+use crate::commands::tui::pipelines::graph::{
+    PipelineGraph, PipelineVertexRow, PipelineVertexRowKind,
+};
+use crate::commands::tui::pipelines::graph_view::{draw_pipeline_graph, draw_pipelines_topology};
+use crate::commands::tui::pipelines::source::{find_block_lines, highlight_line};
+use crate::commands::tui::pipelines::state::{
+    vertex_rows, PipelineTableItem, SOURCE_CONTEXT_LINES,
+};
+use crate::commands::tui::pipelines::topology::PipelinesTopology;
 use crate::commands::tui::widgets::{
-    TABLE_HEADER_CELL_STYLE, TABLE_HEADER_ROW_STYLE, TABLE_SELECTED_ROW_STYLE,
-    TABLE_SELECTED_ROW_SYMBOL,
+    alert_marker, bottleneck_marker, events_rate_span, sparkline_text, TABLE_HEADER_CELL_STYLE,
+    TABLE_HEADER_ROW_STYLE, TABLE_SELECTED_ROW_STYLE, TABLE_SELECTED_ROW_SYMBOL,
 };
 
 pub(crate) fn draw_pipelines_tab(f: &mut Frame, app: &mut App, area: Rect) {
@@ -34,12 +46,24 @@ pub(crate) fn draw_pipelines_tab(f: &mut Frame, app: &mut App, area: Rect) {
 }
 
 pub(crate) fn pipelines_tab_shortcuts_help(_: &App) -> HashMap<String, String> {
-    let mut keys = HashMap::with_capacity(2);
+    let mut keys = HashMap::with_capacity(3);
     keys.insert(
         "[↵]".to_string(),
         "open pipeline charts/vertex details".to_string(),
     );
     keys.insert("[C]".to_string(), "pipeline charts".to_string());
+    keys.insert("[A]".to_string(), "pipeline tuning advisor".to_string());
+    keys.insert("[S]".to_string(), "vertex source config".to_string());
+    keys.insert("[L]".to_string(), "lifetime/recent values".to_string());
+    keys.insert(
+        "[X/-/+]".to_string(),
+        "collapse/expand branch/all".to_string(),
+    );
+    keys.insert("[U]".to_string(), "parent condition".to_string());
+    keys.insert("[G]".to_string(), "pipeline graph".to_string());
+    keys.insert("[O]".to_string(), "pipelines topology".to_string());
+    keys.insert("[/]".to_string(), "search".to_string());
+    keys.insert("[n/N]".to_string(), "next/previous match".to_string());
     keys
 }
 
@@ -60,7 +84,16 @@ fn draw_pipelines_table(f: &mut Frame, app: &mut App, area: Rect) {
         .pipelines_table
         .items
         .iter()
-        .map(|i| Row::new(vec![Cell::from(Text::from(i.name.to_string()))]))
+        .map(|i| {
+            let mut spans = vec![Span::raw(i.name.to_string())];
+            if let Some(severity) = app.shared_state.bottlenecks().pipeline_severity(&i.name) {
+                spans.push(bottleneck_marker(severity));
+            }
+            if let Some(severity) = app.alerts_state.pipeline_severity(&i.name) {
+                spans.push(alert_marker(severity));
+            }
+            Row::new(vec![Cell::from(Line::from(spans))])
+        })
         .collect();
 
     let headers = ["Name"];
@@ -72,13 +105,15 @@ fn draw_pipelines_table(f: &mut Frame, app: &mut App, area: Rect) {
         .style(TABLE_HEADER_ROW_STYLE)
         .height(1);
 
-    let pipelines_count = rows.len();
+    let mut title = vec![Span::raw(format!("Pipelines({})", rows.len()))];
+    title.extend(app.pipelines_state.pipelines_search.title_spans());
+
     let pipelines = Table::new(rows, vec![Constraint::Percentage(100)])
         .header(header)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Pipelines({})", pipelines_count)),
+                .title(Line::from(title)),
         )
         .column_spacing(2)
         .row_highlight_style(TABLE_SELECTED_ROW_STYLE)
@@ -109,10 +144,16 @@ fn draw_selected_pipeline_vertices(f: &mut Frame, app: &mut App, area: Rect) {
             .pipelines_table
             .selected_item()
             .is_some();
+    let draw_pipeline_advisor = app.pipelines_state.show_selected_pipeline_advisor
+        && app
+            .pipelines_state
+            .pipelines_table
+            .selected_item()
+            .is_some();
     let draw_vertex_charts = app.pipelines_state.show_selected_vertex_details
         && app.pipelines_state.selected_pipeline_vertex().is_some();
 
-    let constraints = if draw_pipeline_charts || draw_vertex_charts {
+    let constraints = if draw_pipeline_charts || draw_pipeline_advisor || draw_vertex_charts {
         vec![Constraint::Percentage(70), Constraint::Percentage(30)]
     } else {
         vec![Constraint::Percentage(100)]
@@ -132,13 +173,29 @@ fn draw_selected_pipeline_vertices(f: &mut Frame, app: &mut App, area: Rect) {
                 pipeline_graph.as_ref().unwrap(),
                 selected_pipeline,
                 &data,
+                app.shared_state.bottlenecks(),
+                &app.alerts_state,
+                app.pipelines_state.show_recent_values,
+                vertex_rows(
+                    pipeline_graph.as_ref().unwrap(),
+                    app.pipelines_state
+                        .collapsed_branches(&selected_pipeline.name),
+                    &app.pipelines_state.vertices_search,
+                ),
             );
         } else {
             pipeline_graph = None;
             rows = vec![];
         }
 
-        let headers = ["Name", "Kind", "Events in", "Events out", "Duration (ms/e)"];
+        let headers = [
+            "Name",
+            "Kind",
+            "Events in",
+            "Events out",
+            "Duration (ms/e)",
+            "Trend (e/s)",
+        ];
         let header_cells = headers
             .iter()
             .map(|h| Cell::from(*h).style(TABLE_HEADER_CELL_STYLE));
@@ -148,28 +205,48 @@ fn draw_selected_pipeline_vertices(f: &mut Frame, app: &mut App, area: Rect) {
             .height(1);
 
         let widths: Vec<Constraint> = vec![
-            Constraint::Percentage(49), // Name
+            Constraint::Percentage(40), // Name
             Constraint::Percentage(8),  // Kind
             Constraint::Percentage(11), // In
-            Constraint::Percentage(15), // Out
-            Constraint::Percentage(18), // Duration
+            Constraint::Percentage(14), // Out
+            Constraint::Percentage(15), // Duration
+            Constraint::Percentage(12), // Trend
         ];
 
+        let mut title = vec![Span::raw(if app.pipelines_state.show_recent_values {
+            "Pipeline (recent)"
+        } else {
+            "Pipeline (lifetime)"
+        })];
+        title.extend(app.pipelines_state.vertices_search.title_spans());
+
         let vertices_table = Table::new(rows, widths)
             .header(header)
-            .block(Block::default().borders(Borders::ALL).title("Pipeline"))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Line::from(title)),
+            )
             .column_spacing(2)
             .row_highlight_style(TABLE_SELECTED_ROW_STYLE)
             .highlight_symbol(TABLE_SELECTED_ROW_SYMBOL);
 
-        f.render_stateful_widget(
-            vertices_table,
-            chunks[0],
-            &mut app.pipelines_state.selected_pipeline_vertex.state,
-        );
+        if app.pipelines_state.show_pipelines_topology {
+            draw_pipelines_topology(f, app, chunks[0]);
+        } else if app.pipelines_state.show_pipeline_graph {
+            draw_pipeline_graph(f, app, chunks[0]);
+        } else {
+            f.render_stateful_widget(
+                vertices_table,
+                chunks[0],
+                &mut app.pipelines_state.selected_pipeline_vertex.state,
+            );
+        }
 
         if draw_pipeline_charts {
             draw_selected_pipeline_flow_charts(f, app, chunks[1]);
+        } else if draw_pipeline_advisor {
+            draw_selected_pipeline_advisor(f, app, chunks[1]);
         } else if draw_vertex_charts {
             draw_selected_pipeline_vertex_details(f, app, pipeline_graph, chunks[1]);
         }
@@ -192,12 +269,14 @@ fn draw_selected_pipeline_flow_charts(f: &mut Frame, app: &App, area: Rect) {
             .direction(Direction::Vertical)
             .split(area);
 
+        let chart_options = app.chart_options.for_pipeline(selected_pipeline.unwrap());
         if !selected_pipeline_state.plugins_throughput.is_empty() {
             draw_plugin_throughput_flow_chart(
                 f,
                 "Pipeline Throughput",
                 &selected_pipeline_state.plugins_throughput,
                 pipeline_flow_chunks[0],
+                &chart_options,
             );
         }
 
@@ -209,11 +288,114 @@ fn draw_selected_pipeline_flow_charts(f: &mut Frame, app: &App, area: Rect) {
                 &selected_pipeline_state.queue_backpressure,
                 pipeline_flow_chunks[1],
                 false,
+                &chart_options,
             );
         }
     }
 }
 
+fn draw_selected_pipeline_advisor(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title("Advisor");
+
+    let data = app.data.read().unwrap();
+    let advice = match (
+        app.pipelines_state.selected_pipeline_name(),
+        data.node_info(),
+        data.node_stats(),
+    ) {
+        (Some(pipeline), Some(node_info), Some(node_stats)) => {
+            advise(pipeline, node_info, node_stats)
+        }
+        _ => None,
+    };
+
+    let Some(advice) = advice else {
+        f.render_widget(
+            Paragraph::new("No data available for this pipeline")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(block),
+            area,
+        );
+        return;
+    };
+
+    let label_style = Style::default().fg(Color::DarkGray);
+    let verdict_color = match advice.verdict {
+        Verdict::UnderProvisioned => Color::Red,
+        Verdict::OverProvisioned => Color::LightYellow,
+        Verdict::Balanced => Color::Green,
+        Verdict::Unknown => Color::DarkGray,
+    };
+
+    let mut text = vec![
+        Line::from(vec![
+            Span::styled("Verdict: ", label_style),
+            Span::styled(advice.verdict.as_str(), Style::default().fg(verdict_color)),
+        ]),
+        Line::from(vec![
+            Span::styled("Headroom: ", label_style),
+            Span::from(format!(
+                "{}% (worker concurrency {} of {} workers)",
+                (advice.headroom * 100.0).strip_number_decimals(0),
+                advice.worker_concurrency.strip_number_decimals(2),
+                advice.workers
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Batch: ", label_style),
+            Span::from(format!(
+                "size {}, delay {} ms",
+                advice.batch_size, advice.batch_delay
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("CPU: ", label_style),
+            Span::from(format!(
+                "{} processors, {}% process usage, {} workers on all pipelines",
+                advice
+                    .available_processors
+                    .map(|p| p.to_string())
+                    .unwrap_or("-".to_string()),
+                advice.cpu_percent,
+                advice.node_workers
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Heap: ", label_style),
+            Span::from(format!("{}% used", advice.heap_used_percent)),
+        ]),
+    ];
+
+    if !advice.suggestions.is_empty() {
+        text.push(Line::default());
+        text.push(Line::from(Span::styled("Suggestions:", label_style)));
+        for suggestion in &advice.suggestions {
+            text.push(Line::from(vec![
+                Span::raw(format!(" {}: {} ", suggestion.setting, suggestion.current)),
+                Span::styled("→ ", label_style),
+                Span::styled(
+                    suggestion.suggested.to_string(),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ]));
+        }
+    }
+
+    text.push(Line::default());
+    text.push(Line::from(Span::styled("Reasoning:", label_style)));
+    for reason in &advice.reasons {
+        text.push(Line::from(vec![
+            Span::styled(" - ", label_style),
+            Span::from(reason.to_string()),
+        ]));
+    }
+
+    f.render_widget(
+        Paragraph::new(text).block(block).wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
 fn draw_selected_pipeline_vertex_details(
     f: &mut Frame,
     app: &App,
@@ -280,6 +462,25 @@ fn draw_selected_pipeline_vertex_details(
         ]))
     }
 
+    if let Some(bottleneck) = app
+        .pipelines_state
+        .selected_pipeline_name()
+        .and_then(|p| app.shared_state.bottlenecks().plugin(p, vertex_id))
+    {
+        details_text.push(Line::from(vec![
+            Span::styled("Bottleneck: ", Style::default().fg(Color::DarkGray)),
+            Span::from(bottleneck.severity.as_str()),
+            bottleneck_marker(bottleneck.severity),
+        ]));
+
+        for reason in &bottleneck.reasons {
+            details_text.push(Line::from(vec![
+                Span::styled(" - ", Style::default().fg(Color::DarkGray)),
+                Span::from(reason.to_string()),
+            ]));
+        }
+    }
+
     let w = Paragraph::new(details_text)
         .alignment(Alignment::Left)
         .wrap(Wrap { trim: true });
@@ -296,6 +497,18 @@ fn draw_selected_pipeline_vertex_details(
 
     f.render_widget(w, chunks[0]);
 
+    if app.pipelines_state.show_selected_vertex_source {
+        match &vertex.value.meta {
+            Some(meta) => draw_selected_pipeline_vertex_source(f, app, &meta.source, chunks[1]),
+            None => f.render_widget(
+                Paragraph::new("No source information available for this vertex")
+                    .style(Style::default().fg(Color::DarkGray)),
+                chunks[1],
+            ),
+        }
+        return;
+    }
+
     match vertex.value.r#type.as_str() {
         "plugin" => draw_selected_pipeline_plugin_vertex_details(f, app, vertex.value, chunks[1]),
         "queue" => draw_selected_pipeline_queue_vertex_details(f, app, chunks[1]),
@@ -303,6 +516,69 @@ fn draw_selected_pipeline_vertex_details(
     }
 }
 
+fn draw_selected_pipeline_vertex_source(
+    f: &mut Frame,
+    app: &App,
+    source: &VertexMetaSource,
+    area: Rect,
+) {
+    let lines = match app.pipelines_state.source_files.lines(source) {
+        Ok(lines) => lines,
+        Err(e) => {
+            f.render_widget(
+                Paragraph::new(e)
+                    .style(Style::default().fg(Color::DarkGray))
+                    .wrap(Wrap { trim: true }),
+                area,
+            );
+            return;
+        }
+    };
+
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .title(format!("Source [PgUp/PgDn] - {}", source.id));
+
+    let (block_start, block_end) = find_block_lines(&lines, source.line, source.column);
+    let visible_lines = area.height.saturating_sub(1) as usize;
+    let first_line = (source.line as i32 - 1 - SOURCE_CONTEXT_LINES
+        + app.pipelines_state.selected_vertex_source_scroll)
+        .clamp(0, lines.len().saturating_sub(1) as i32) as usize;
+
+    let line_number_width = lines.len().to_string().len();
+    let text: Vec<Line> = lines
+        .iter()
+        .enumerate()
+        .skip(first_line)
+        .take(visible_lines)
+        .map(|(index, content)| {
+            let in_block = index >= block_start && index <= block_end;
+            let (symbol, line_number_style) = if in_block {
+                (
+                    TABLE_SELECTED_ROW_SYMBOL,
+                    Style::default().fg(Color::Yellow),
+                )
+            } else {
+                (" ", Style::default().fg(Color::DarkGray))
+            };
+
+            let mut spans = vec![Span::styled(
+                format!(
+                    "{:>width$}{} ",
+                    index + 1,
+                    symbol,
+                    width = line_number_width
+                ),
+                line_number_style,
+            )];
+            spans.extend(highlight_line(content));
+            Line::from(spans)
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(text).block(block), area);
+}
+
 fn draw_selected_pipeline_events_block(f: &mut Frame, app: &mut App, area: Rect) {
     let events_block = Block::default()
         .title("Pipeline events")
@@ -326,15 +602,21 @@ fn draw_selected_pipeline_events_block(f: &mut Frame, app: &mut App, area: Rect)
                     .trim()
             );
 
+            let pipeline_rates = data
+                .derived_metrics()
+                .pipeline_rates(&selected_pipeline.name);
+
             events_text = Line::from(vec![
                 Span::styled("In: ", Style::default().fg(Color::DarkGray)),
                 Span::from(selected_pipeline_stats.events.r#in.format_number()),
+                events_rate_span(pipeline_rates.map(|r| r.events_in_per_second())),
                 Span::styled(" | ", Style::default().fg(Color::Yellow)),
                 Span::styled("Filtered: ", Style::default().fg(Color::DarkGray)),
                 Span::from(selected_pipeline_stats.events.filtered.format_number()),
                 Span::styled(" | ", Style::default().fg(Color::Yellow)),
                 Span::styled("Out: ", Style::default().fg(Color::DarkGray)),
                 Span::from(selected_pipeline_stats.events.out.format_number()),
+                events_rate_span(pipeline_rates.map(|r| r.events_out_per_second())),
                 Span::styled(" | ", Style::default().fg(Color::Yellow)),
                 Span::styled(
                     "Queue push duration (ms/e): ",
@@ -387,23 +669,98 @@ fn get_pipeline_workers(app: &App, pipeline_name: &str) -> Option<i64> {
     None
 }
 
-fn create_pipeline_vertex_if_row(vertex: &Vertex, ident_spaces: String) -> Row {
-    let if_text = Line::from(vec![
+fn create_pipeline_vertex_if_row<'a>(
+    vertex: &'a Vertex,
+    ident_spaces: String,
+    hidden_rows: usize,
+    ctx: &VertexRowContext<'a>,
+) -> Row<'a> {
+    let mut if_spans = vec![
         Span::raw(ident_spaces),
         Span::styled("if ", Style::default().fg(Color::Red)),
         Span::styled(&vertex.condition, Style::default().fg(Color::DarkGray)),
-    ]);
+    ];
+    if_spans.extend(branch_split_spans(ctx, &vertex.id, true));
+    if_spans.extend(collapsed_branch_span(hidden_rows));
 
-    Row::new(vec![Cell::from(if_text)])
+    Row::new(vec![Cell::from(Line::from(if_spans))])
 }
 
-fn create_pipeline_vertex_queue_row(
+fn collapsed_branch_span<'a>(hidden_rows: usize) -> Option<Span<'a>> {
+    if hidden_rows == 0 {
+        return None;
+    }
+
+    Some(Span::styled(
+        format!(" [+{}]", hidden_rows),
+        Style::default().fg(Color::Yellow),
+    ))
+}
+
+/// Share of the conditional events that took the `when` branch, followed by its rate.
+fn branch_split_spans<'a>(
+    ctx: &VertexRowContext<'a>,
+    conditional_id: &str,
+    when: bool,
+) -> Vec<Span<'a>> {
+    let percentage = ctx
+        .branch_flows()
+        .and_then(|f| f.split(conditional_id))
+        .and_then(|s| s.percentage(when));
+
+    let Some(percentage) = percentage else {
+        return vec![];
+    };
+
+    let mut spans = vec![Span::styled(
+        format!(" → {}%", percentage.strip_number_decimals(2)),
+        Style::default().fg(Color::Magenta),
+    )];
+
+    let rate = ctx
+        .recent_branch_flows
+        .as_ref()
+        .and_then(|f| f.split(conditional_id))
+        .zip(ctx.derived_metrics.pipeline_rates(ctx.pipeline_name))
+        .map(|(split, rates)| rates.per_second(split.events(when)));
+
+    if let Some(rate) = rate {
+        spans.push(Span::styled(
+            format!(" ({} e/s)", rate.format_number().trim()),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    spans
+}
+
+fn create_pipeline_vertex_queue_row<'a>(
     ident_spaces: String,
-    pipeline_stats: Option<&PipelineStats>,
-) -> Row {
+    ctx: &VertexRowContext<'a>,
+) -> Row<'a> {
+    let recent_rates = ctx.derived_metrics.pipeline_rates(ctx.pipeline_name);
     let (queue_type, events_in, events_out, queue_push_duration, backpressure) =
-        match pipeline_stats {
+        match ctx.pipeline_stats {
             None => ("-", 0, 0, 0, None),
+            Some(stats) if ctx.show_recent_values => {
+                let (events_in, events_out, queue_push_duration) = recent_rates
+                    .map(|r| {
+                        (
+                            r.events_in,
+                            r.events_filtered,
+                            r.queue_push_duration_in_millis,
+                        )
+                    })
+                    .unwrap_or((0, 0, 0));
+
+                (
+                    stats.queue.r#type.as_str(),
+                    events_in,
+                    events_out,
+                    queue_push_duration,
+                    Some(&stats.flow.queue_backpressure),
+                )
+            }
             Some(stats) => (
                 stats.queue.r#type.as_str(),
                 stats.events.r#in,
@@ -436,55 +793,145 @@ fn create_pipeline_vertex_queue_row(
         vec![duration_without_backpressure]
     };
 
+    let trend = ctx
+        .derived_metrics
+        .pipeline_rates_history(ctx.pipeline_name)
+        .map(|h| sparkline_text(h.iter().map(|r| r.events_in_per_second())))
+        .unwrap_or_default();
+
     let cells = vec![
         Cell::from(Text::styled(
             format!("{}{}", ident_spaces, "queue"),
             Style::default().fg(Color::Cyan),
         )), // Name
-        Cell::from(Text::raw(queue_type)),                 // Kind
-        Cell::from(Text::raw(events_in.format_number())),  // Events in
-        Cell::from(Text::raw(events_out.format_number())), // Events out
-        Cell::from(Line::from(duration_spans)),            // Duration
+        Cell::from(Text::raw(queue_type)),                   // Kind
+        Cell::from(Text::raw(ctx.format_events(events_in))), // Events in
+        Cell::from(Text::raw(ctx.format_events(events_out))), // Events out
+        Cell::from(Line::from(duration_spans)),              // Duration
+        Cell::from(Text::styled(trend, Style::default().fg(Color::Cyan))), // Trend
     ];
 
     Row::new(cells)
 }
 
+/// Data shared by the rows of the selected pipeline vertices table.
+struct VertexRowContext<'a> {
+    pipeline_name: &'a str,
+    pipeline_stats: Option<&'a PipelineStats>,
+    derived_metrics: &'a DerivedMetrics,
+    show_recent_values: bool,
+    /// Conditionals branches split of the lifetime counters, unset when displaying the
+    /// recent values
+    lifetime_branch_flows: Option<BranchFlows<'a>>,
+    /// Conditionals branches split of the last sampling interval, for their rates
+    recent_branch_flows: Option<BranchFlows<'a>>,
+}
+
+impl<'a> VertexRowContext<'a> {
+    /// Conditionals branches split of the displayed values.
+    fn branch_flows(&self) -> Option<&BranchFlows<'a>> {
+        if self.show_recent_values {
+            self.recent_branch_flows.as_ref()
+        } else {
+            self.lifetime_branch_flows.as_ref()
+        }
+    }
+
+    /// Formats the events counters, or the events rates when displaying the recent values.
+    fn format_events(&self, events: i64) -> String {
+        if !self.show_recent_values {
+            return events.format_number();
+        }
+
+        match self.derived_metrics.pipeline_rates(self.pipeline_name) {
+            Some(rates) => format!("{} e/s", rates.per_second(events).format_number().trim()),
+            None => "-".to_string(),
+        }
+    }
+}
+
 fn create_pipeline_vertex_plugin_row<'a>(
     vertex: &'a Vertex,
     ident_spaces: String,
-    pipeline_stats: Option<&'a PipelineStats>,
+    ctx: &VertexRowContext<'a>,
+    bottleneck: Option<&Bottleneck>,
+    alert: Option<AlertSeverity>,
 ) -> Row<'a> {
-    let plugin_name_cell = if vertex.explicit_id {
-        Cell::from(Line::from(vec![
+    let mut plugin_name_spans = if vertex.explicit_id {
+        vec![
             Span::from(format!("{}{} ", ident_spaces, vertex.config_name)),
             Span::styled(
                 format!("({})", vertex.id.as_str()),
                 Style::default().fg(Color::Blue),
             ),
-        ]))
+        ]
     } else {
-        Cell::from(Text::from(format!(
+        vec![Span::from(format!(
             "{}{}",
             ident_spaces, vertex.config_name
-        )))
+        ))]
     };
 
+    if let Some(bottleneck) = bottleneck {
+        plugin_name_spans.push(bottleneck_marker(bottleneck.severity));
+    }
+
+    if let Some(severity) = alert {
+        plugin_name_spans.push(alert_marker(severity));
+    }
+
+    let plugin_name_cell = Cell::from(Line::from(plugin_name_spans));
+
     let mut cells = vec![
         plugin_name_cell,
         Cell::from(Text::from(vertex.plugin_type.to_string())),
     ];
 
-    if let Some(stats) = pipeline_stats {
+    if let Some(stats) = ctx.pipeline_stats {
         if let Some(vertex_stats) = stats.vertices.get(vertex.id.as_str()) {
             let is_input_plugin = vertex.plugin_type == "input";
+            let rates_history = ctx
+                .derived_metrics
+                .vertex_rates_history(ctx.pipeline_name, &vertex.id);
+
+            // Lifetime totals, or their increase over the last sampling interval
+            let (events_in, events_out, duration_in_millis, total_duration_in_millis) =
+                if ctx.show_recent_values {
+                    let pipeline_rates = ctx.derived_metrics.pipeline_rates(ctx.pipeline_name);
+                    match (rates_history.and_then(|h| h.back()), pipeline_rates) {
+                        (Some(rates), Some(pipeline_rates)) => {
+                            recent_vertex_counters(rates, pipeline_rates, is_input_plugin)
+                        }
+                        _ => (0, 0, 0, 0),
+                    }
+                } else if is_input_plugin {
+                    (
+                        vertex_stats.events_in,
+                        vertex_stats.events_out,
+                        vertex_stats.queue_push_duration_in_millis,
+                        stats.events.queue_push_duration_in_millis,
+                    )
+                } else {
+                    (
+                        vertex_stats.events_in,
+                        vertex_stats.events_out,
+                        vertex_stats.duration_in_millis,
+                        stats.events.duration_in_millis,
+                    )
+                };
+
             let vertex_events_in = if is_input_plugin {
-                vertex_stats.events_out
+                events_out
             } else {
-                vertex_stats.events_in
+                events_in
             };
 
-            if is_input_plugin {
+            if is_input_plugin && ctx.show_recent_values {
+                cells.push(Cell::from(Line::from(vec![Span::styled(
+                    ctx.format_events(vertex_events_in),
+                    Style::default().fg(Color::Blue),
+                )])));
+            } else if is_input_plugin {
                 let mut empty_events_in = true;
                 if let Some(plugin) = stats.plugins.get(&vertex.id) {
                     if let Some(plugin_flow) = &plugin.flow {
@@ -501,15 +948,15 @@ fn create_pipeline_vertex_plugin_row<'a>(
                     cells.push(Cell::from(Text::from("-")));
                 }
             } else {
-                cells.push(Cell::from(Text::from(vertex_events_in.format_number())));
+                cells.push(Cell::from(Text::from(ctx.format_events(vertex_events_in))));
             }
 
             // Drop percentage
-            let events_in_out_diff = vertex_stats.events_out - vertex_events_in;
+            let events_in_out_diff = events_out - vertex_events_in;
             match events_in_out_diff.cmp(&0) {
                 Ordering::Less => {
-                    let drop_percentage = 100.00
-                        - ((vertex_stats.events_out as f64 / vertex_events_in as f64) * 100.0);
+                    let drop_percentage =
+                        100.00 - ((events_out as f64 / vertex_events_in as f64) * 100.0);
                     let drop_percentage_text = if drop_percentage > 0.01 {
                         format!(" {}% ↓", drop_percentage.strip_number_decimals(2))
                     } else {
@@ -524,7 +971,7 @@ fn create_pipeline_vertex_plugin_row<'a>(
                         };
 
                     cells.push(Cell::from(Line::from(vec![
-                        Span::raw(vertex_stats.events_out.format_number()),
+                        Span::raw(ctx.format_events(events_out)),
                         Span::styled(
                             drop_percentage_text,
                             Style::default().fg(drop_percentage_color),
@@ -532,25 +979,11 @@ fn create_pipeline_vertex_plugin_row<'a>(
                     ])));
                 }
                 Ordering::Equal | Ordering::Greater => {
-                    cells.push(Cell::from(Text::from(
-                        vertex_stats.events_out.format_number(),
-                    )));
+                    cells.push(Cell::from(Text::from(ctx.format_events(events_out))));
                 }
             }
 
             // Duration
-            let (duration_in_millis, total_duration_in_millis) = if is_input_plugin {
-                (
-                    vertex_stats.queue_push_duration_in_millis,
-                    stats.events.queue_push_duration_in_millis,
-                )
-            } else {
-                (
-                    vertex_stats.duration_in_millis,
-                    stats.events.duration_in_millis,
-                )
-            };
-
             let mut duration_spans = vec![Span::raw(
                 duration_in_millis.format_duration_per_event(vertex_events_in as u64),
             )];
@@ -582,105 +1015,134 @@ fn create_pipeline_vertex_plugin_row<'a>(
             }
 
             cells.push(Cell::from(Line::from(duration_spans)));
+
+            let trend = rates_history
+                .map(|h| {
+                    sparkline_text(h.iter().map(|r| {
+                        if is_input_plugin {
+                            r.events_out_per_second()
+                        } else {
+                            r.events_in_per_second()
+                        }
+                    }))
+                })
+                .unwrap_or_default();
+            cells.push(Cell::from(Text::styled(
+                trend,
+                Style::default().fg(Color::Cyan),
+            )));
         }
     }
 
     Row::new(cells)
 }
 
-fn create_pipeline_vertex_else_row<'a>(ident_spaces: &str) -> Row<'a> {
-    let else_text: Line = Line::from(vec![
+/// Vertex events and durations increase over the last sampling interval, with the
+/// pipeline total duration the vertex duration is compared with.
+fn recent_vertex_counters(
+    rates: &CountersRates,
+    pipeline_rates: &CountersRates,
+    is_input_plugin: bool,
+) -> (i64, i64, u64, u64) {
+    if is_input_plugin {
+        (
+            rates.events_in,
+            rates.events_out,
+            rates.queue_push_duration_in_millis,
+            pipeline_rates.queue_push_duration_in_millis,
+        )
+    } else {
+        (
+            rates.events_in,
+            rates.events_out,
+            rates.duration_in_millis,
+            pipeline_rates.duration_in_millis,
+        )
+    }
+}
+
+fn create_pipeline_vertex_else_row<'a>(
+    ident_spaces: &str,
+    conditional_id: &str,
+    hidden_rows: usize,
+    ctx: &VertexRowContext<'a>,
+) -> Row<'a> {
+    let mut else_spans = vec![
         Span::raw(ident_spaces[1..].to_string()),
         Span::styled("else", Style::default().fg(Color::Red)),
-    ]);
+    ];
 
-    Row::new(vec![Cell::from(else_text)])
-}
+    else_spans.extend(branch_split_spans(ctx, conditional_id, false));
+    else_spans.extend(collapsed_branch_span(hidden_rows));
 
-type GraphVisitorStack<'b> = RefCell<Vec<(&'b str, Option<i32>, i32, bool)>>;
+    Row::new(vec![Cell::from(Line::from(else_spans))])
+}
 
 fn create_selected_pipeline_vertices_rows<'a>(
     graph: &'a PipelineGraph,
-    selected_pipeline: &PipelineTableItem,
+    selected_pipeline: &'a PipelineTableItem,
     data: &'a AppData,
+    bottlenecks: &Analysis,
+    alerts: &AlertsState,
+    show_recent_values: bool,
+    vertex_rows: Vec<PipelineVertexRow<'a>>,
 ) -> Vec<Row<'a>> {
     let selected_pipeline_stats: Option<&PipelineStats> = match &data.node_stats() {
         None => None,
         Some(stats) => stats.pipelines.get(selected_pipeline.name.as_str()),
     };
 
-    let mut visited: RefCell<HashSet<&str>> = RefCell::new(HashSet::with_capacity(
-        selected_pipeline.graph.vertices.len(),
-    ));
-    let mut stack: GraphVisitorStack = RefCell::new(Vec::new());
-
-    let mut head_stack = graph.heads.to_vec();
-    if head_stack.is_empty() {
-        return vec![];
-    }
-
-    // Add first head
-    let first_head = head_stack.pop().unwrap();
-    visited.get_mut().insert(first_head);
-    stack.get_mut().push((first_head, None, 0, false));
-
-    let mut table_rows: Vec<Row> = Vec::new();
-    while let Some((vertex_id, mut next_row_index, ident_level, add_else_row)) =
-        stack.get_mut().pop()
-    {
-        visited.get_mut().insert(vertex_id);
-
-        let vertex = graph.data.get(vertex_id).unwrap().value;
-        let ident_spaces = " ".repeat(ident_level as usize);
-        let mut next_ident_level = ident_level;
-
-        let mut push_row = |row| {
-            if let Some(i) = next_row_index {
-                table_rows.insert(i as usize, row);
-                next_row_index = Some(i + 1);
-            } else {
-                table_rows.push(row);
-            }
-        };
-
-        if add_else_row {
-            push_row(create_pipeline_vertex_else_row(&ident_spaces));
-        }
-
-        match vertex.r#type.as_str() {
-            "if" => {
-                push_row(create_pipeline_vertex_if_row(vertex, ident_spaces));
-                next_ident_level += 1;
-            }
-            "queue" => push_row(create_pipeline_vertex_queue_row(
-                ident_spaces,
-                selected_pipeline_stats,
-            )),
-            _ => push_row(create_pipeline_vertex_plugin_row(
-                vertex,
-                ident_spaces,
-                selected_pipeline_stats,
-            )),
-        };
+    let derived_metrics = data.derived_metrics();
+    let recent_branch_flows =
+        BranchFlows::from_rates(graph, &selected_pipeline.name, derived_metrics);
+    let lifetime_branch_flows = if show_recent_values {
+        None
+    } else {
+        selected_pipeline_stats.map(|stats| BranchFlows::from_stats(graph, stats))
+    };
 
-        PipelineGraph::process_vertices_edges(
-            graph,
-            &vertex.r#type,
-            vertex_id,
-            next_ident_level,
-            next_row_index,
-            &mut visited,
-            &mut stack,
-        );
+    let ctx = VertexRowContext {
+        pipeline_name: selected_pipeline.name.as_str(),
+        pipeline_stats: selected_pipeline_stats,
+        derived_metrics,
+        show_recent_values,
+        lifetime_branch_flows,
+        recent_branch_flows,
+    };
 
-        if stack.borrow().is_empty() && !head_stack.is_empty() {
-            while let Some(vertex_id) = head_stack.pop() {
-                stack.get_mut().push((vertex_id, Some(0), 0, false));
+    vertex_rows
+        .into_iter()
+        .map(|row| {
+            let ident_spaces = " ".repeat(row.ident_level as usize);
+            match row.kind {
+                PipelineVertexRowKind::Else { conditional_id } => create_pipeline_vertex_else_row(
+                    &ident_spaces,
+                    conditional_id,
+                    row.hidden_rows,
+                    &ctx,
+                ),
+                PipelineVertexRowKind::Vertex(vertex_id) => {
+                    let vertex = graph.data.get(vertex_id).unwrap().value;
+                    match vertex.r#type.as_str() {
+                        "if" => create_pipeline_vertex_if_row(
+                            vertex,
+                            ident_spaces,
+                            row.hidden_rows,
+                            &ctx,
+                        ),
+                        "queue" => create_pipeline_vertex_queue_row(ident_spaces, &ctx),
+                        _ => create_pipeline_vertex_plugin_row(
+                            vertex,
+                            ident_spaces,
+                            &ctx,
+                            bottlenecks.plugin(&selected_pipeline.name, &vertex.id),
+                            alerts.plugin_severity(&selected_pipeline.name, &vertex.id),
+                        ),
+                    }
+                }
             }
-        }
-    }
-
-    table_rows
+        })
+        .collect()
 }
 
 fn draw_selected_pipeline_queue_vertex_details(f: &mut Frame, app: &App, area: Rect) {
@@ -749,6 +1211,7 @@ fn draw_selected_pipeline_queue_vertex_details(f: &mut Frame, app: &App, area: R
         .pipeline_flows_chart_state(&selected_pipeline.name)
         .map(|p| &p.pipeline)
     {
+        let chart_options = app.chart_options.for_pipeline(&selected_pipeline.name);
         if !selected_pipeline_state.queue_backpressure.is_empty() {
             draw_flow_metric_chart(
                 f,
@@ -757,6 +1220,7 @@ fn draw_selected_pipeline_queue_vertex_details(f: &mut Frame, app: &App, area: R
                 &selected_pipeline_state.queue_backpressure,
                 chart_chunks[0],
                 false,
+                &chart_options,
             );
         }
 
@@ -771,6 +1235,7 @@ fn draw_selected_pipeline_queue_vertex_details(f: &mut Frame, app: &App, area: R
                 &selected_pipeline_state.queue_persisted_growth_bytes,
                 chart_chunks[1],
                 false,
+                &chart_options,
             );
         }
 
@@ -785,6 +1250,7 @@ fn draw_selected_pipeline_queue_vertex_details(f: &mut Frame, app: &App, area: R
                 &selected_pipeline_state.queue_persisted_growth_events,
                 chart_chunks[2],
                 false,
+                &chart_options,
             );
         }
     }
@@ -895,6 +1361,7 @@ fn draw_selected_pipeline_worker_plugin_widgets(
         .shared_state
         .pipeline_plugin_flows_chart_state(selected_pipeline, selected_vertex);
 
+    let chart_options = app.chart_options.for_pipeline(selected_pipeline);
     let worker_utilization_state = flow_state.map(|p| &p.worker_utilization);
     let worker_millis_per_event_state = flow_state.map(|p| &p.worker_millis_per_event);
 
@@ -907,6 +1374,7 @@ fn draw_selected_pipeline_worker_plugin_widgets(
                 worker_utilization_state,
                 chunks[0],
                 false,
+                &chart_options,
             );
         }
     }
@@ -920,6 +1388,7 @@ fn draw_selected_pipeline_worker_plugin_widgets(
                 worker_millis_per_event_state,
                 chunks[1],
                 false,
+                &chart_options,
             );
         }
     }
@@ -949,9 +1418,18 @@ fn draw_selected_pipeline_input_plugin_widgets(
         .pipeline_plugin_flows_chart_state(selected_pipeline, selected_vertex)
         .map(|p| &p.throughput);
 
+    let chart_options = app.chart_options.for_pipeline(selected_pipeline);
     if let Some(throughput) = throughput_state {
         if !throughput.is_empty() {
-            draw_flow_metric_chart(f, "Throughput", Some("e/s"), throughput, chunks[0], true);
+            draw_flow_metric_chart(
+                f,
+                "Throughput",
+                Some("e/s"),
+                throughput,
+                chunks[0],
+                true,
+                &chart_options,
+            );
         }
     }
 
@@ -988,38 +1466,24 @@ fn draw_selected_pipeline_input_pipeline_plugin_widgets(
 
     let plugin = plugin_option.unwrap();
     if let Some(listen_address) = plugin.get_other("address", |v| v.as_str(), None) {
+        let topology = PipelinesTopology::new(data.node_stats().unwrap(), data.derived_metrics());
         let mut writing_pipelines: Vec<(&String, i64)> = vec![];
-        for (pipeline_id, stats) in &data.node_stats().unwrap().pipelines {
-            let mut has_producers = false;
-            let pipeline_plugins_events_out = stats
-                .plugins
-                .outputs
-                .iter()
-                .filter(|(_, source_plugin)| {
-                    if source_plugin.name.as_ref().is_some_and(|v| v != "pipeline") {
-                        return false;
-                    }
-                    if let Some(send_to) =
-                        source_plugin.get_other("send_to", |p| p.as_array(), None)
-                    {
-                        let addresses: Vec<&str> =
-                            send_to.iter().map(|v| v.as_str().unwrap_or("-")).collect();
-                        if addresses.contains(&listen_address) {
-                            has_producers = true;
-                            return true;
-                        }
-                    }
-                    false
-                })
-                .map(|(_id, plugin)| plugin.events.out)
-                .sum();
-
-            if has_producers {
-                writing_pipelines.push((pipeline_id, pipeline_plugins_events_out));
+        for producer in topology
+            .addresses
+            .get(listen_address)
+            .map(|a| a.producers.as_slice())
+            .unwrap_or_default()
+        {
+            match writing_pipelines
+                .iter_mut()
+                .find(|(pipeline, _)| **pipeline == producer.pipeline)
+            {
+                Some((_, events)) => *events += producer.events,
+                None => writing_pipelines.push((&producer.pipeline, producer.events)),
             }
         }
 
-        writing_pipelines.sort_by(|a, b| b.1.cmp(&a.1));
+        writing_pipelines.sort_by_key(|p| std::cmp::Reverse(p.1));
 
         let headers = ["Pipeline", "Events"];
         let header_cells = headers
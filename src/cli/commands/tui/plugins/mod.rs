@@ -0,0 +1,2 @@
+pub(crate) mod state;
+pub(crate) mod ui;
@@ -0,0 +1,270 @@
+use std::cmp::Ordering;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::commands::tui::app::AppData;
+use crate::commands::tui::events::EventsListener;
+use crate::commands::tui::search::Search;
+use crate::commands::tui::widgets::StatefulTable;
+
+pub(crate) struct PluginTableItem {
+    pub pipeline: String,
+    pub id: String,
+    pub name: String,
+    pub explicit_id: bool,
+    pub plugin_type: String,
+    pub events_in: i64,
+    pub events_out: i64,
+    pub worker_utilization: Option<f64>,
+    pub worker_millis_per_event: Option<f64>,
+    pub throughput: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PluginsSortColumn {
+    Pipeline,
+    Type,
+    Name,
+    EventsIn,
+    EventsOut,
+    WorkerUtilization,
+    WorkerMillisPerEvent,
+    Throughput,
+}
+
+impl PluginsSortColumn {
+    /// Columns in the table order, selected by the `1-8` keys.
+    pub const ALL: [PluginsSortColumn; 8] = [
+        PluginsSortColumn::Pipeline,
+        PluginsSortColumn::Type,
+        PluginsSortColumn::Name,
+        PluginsSortColumn::EventsIn,
+        PluginsSortColumn::EventsOut,
+        PluginsSortColumn::WorkerUtilization,
+        PluginsSortColumn::WorkerMillisPerEvent,
+        PluginsSortColumn::Throughput,
+    ];
+
+    pub fn header(&self) -> &'static str {
+        match self {
+            PluginsSortColumn::Pipeline => "Pipeline",
+            PluginsSortColumn::Type => "Type",
+            PluginsSortColumn::Name => "Name",
+            PluginsSortColumn::EventsIn => "Events in",
+            PluginsSortColumn::EventsOut => "Events out",
+            PluginsSortColumn::WorkerUtilization => "Worker utilization",
+            PluginsSortColumn::WorkerMillisPerEvent => "Worker millis per event",
+            PluginsSortColumn::Throughput => "Throughput",
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        !matches!(
+            self,
+            PluginsSortColumn::Pipeline | PluginsSortColumn::Type | PluginsSortColumn::Name
+        )
+    }
+
+    fn metric(&self, item: &PluginTableItem) -> Option<f64> {
+        match self {
+            PluginsSortColumn::WorkerUtilization => item.worker_utilization,
+            PluginsSortColumn::WorkerMillisPerEvent => item.worker_millis_per_event,
+            PluginsSortColumn::Throughput => item.throughput,
+            _ => None,
+        }
+    }
+
+    fn compare(&self, a: &PluginTableItem, b: &PluginTableItem) -> Ordering {
+        match self {
+            PluginsSortColumn::Pipeline => a.pipeline.cmp(&b.pipeline),
+            PluginsSortColumn::Type => a.plugin_type.cmp(&b.plugin_type),
+            PluginsSortColumn::Name => a.name.cmp(&b.name),
+            PluginsSortColumn::EventsIn => a.events_in.cmp(&b.events_in),
+            PluginsSortColumn::EventsOut => a.events_out.cmp(&b.events_out),
+            _ => {
+                let a_value = self.metric(a).unwrap_or(0.0);
+                let b_value = self.metric(b).unwrap_or(0.0);
+                a_value.total_cmp(&b_value)
+            }
+        }
+    }
+
+    /// Plugins without the sorted flow metric (e.g. inputs worker utilization) come last
+    /// on both directions.
+    fn has_value(&self, item: &PluginTableItem) -> bool {
+        match self {
+            PluginsSortColumn::WorkerUtilization
+            | PluginsSortColumn::WorkerMillisPerEvent
+            | PluginsSortColumn::Throughput => self.metric(item).is_some(),
+            _ => true,
+        }
+    }
+}
+
+impl StatefulTable<PluginTableItem> {
+    fn update(&mut self, data: &AppData, search: &Search, sort: (PluginsSortColumn, bool)) {
+        let Some(node_stats) = data.node_stats() else {
+            return;
+        };
+
+        let pipelines_info = data.node_info().and_then(|info| info.pipelines.as_ref());
+        let mut new_items = Vec::with_capacity(self.items.len());
+        for (pipeline, pipeline_stats) in &node_stats.pipelines {
+            let vertices = pipelines_info
+                .and_then(|p| p.get(pipeline))
+                .map(|p| &p.graph.graph.vertices);
+
+            for (id, (plugin_type, plugin)) in pipeline_stats.plugins.all_with_type() {
+                let vertex = vertices.and_then(|v| v.iter().find(|v| v.id == id));
+                let flow = plugin.flow.as_ref();
+                let new_item = PluginTableItem {
+                    pipeline: pipeline.to_string(),
+                    name: plugin
+                        .name
+                        .clone()
+                        .or_else(|| vertex.map(|v| v.config_name.to_string()))
+                        .unwrap_or_else(|| id.to_string()),
+                    explicit_id: vertex.is_some_and(|v| v.explicit_id),
+                    id,
+                    plugin_type,
+                    events_in: plugin.events.r#in,
+                    events_out: plugin.events.out,
+                    worker_utilization: flow
+                        .and_then(|f| f.worker_utilization.as_ref())
+                        .map(|m| m.current),
+                    worker_millis_per_event: flow
+                        .and_then(|f| f.worker_millis_per_event.as_ref())
+                        .map(|m| m.current),
+                    throughput: flow.and_then(|f| f.throughput.as_ref()).map(|m| m.current),
+                };
+
+                if search.matches(&[&new_item.name, &new_item.id, &new_item.plugin_type]) {
+                    new_items.push(new_item);
+                }
+            }
+        }
+
+        let (column, descending) = sort;
+        new_items.sort_by(|a, b| {
+            let ordering = column.compare(a, b);
+            column
+                .has_value(b)
+                .cmp(&column.has_value(a))
+                .then(if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                })
+                .then_with(|| a.pipeline.cmp(&b.pipeline))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        // Keeps the selected plugin, or selects the first one when it's filtered out
+        let selected_index = self.selected_item().and_then(|selected| {
+            new_items
+                .iter()
+                .position(|p| p.pipeline == selected.pipeline && p.id == selected.id)
+        });
+
+        self.items = new_items;
+        self.select(selected_index.or((!self.items.is_empty()).then_some(0)));
+    }
+}
+
+pub(crate) struct PluginsState {
+    pub plugins_table: StatefulTable<PluginTableItem>,
+    pub sort_column: PluginsSortColumn,
+    pub sort_descending: bool,
+    /// Name, id or type filter of the listed plugins
+    pub search: Search,
+    /// Pipeline and vertex to open on the pipelines tab, requested by `Enter`
+    pub open_requested: Option<(String, String)>,
+}
+
+impl PluginsState {
+    pub(crate) fn new() -> Self {
+        PluginsState {
+            plugins_table: StatefulTable::new(),
+            sort_column: PluginsSortColumn::WorkerUtilization,
+            sort_descending: true,
+            search: Search::default(),
+            open_requested: None,
+        }
+    }
+
+    fn sort_by(&mut self, column: PluginsSortColumn, app_data: &AppData) {
+        if self.sort_column == column {
+            self.sort_descending = !self.sort_descending;
+        } else {
+            self.sort_column = column;
+            self.sort_descending = column.is_numeric();
+        }
+
+        self.update(app_data);
+    }
+}
+
+impl EventsListener for PluginsState {
+    fn update(&mut self, app_data: &AppData) {
+        self.plugins_table.update(
+            app_data,
+            &self.search,
+            (self.sort_column, self.sort_descending),
+        );
+    }
+
+    fn timeline_changed(&mut self, app_data: &AppData) {
+        self.update(app_data);
+    }
+
+    fn reset(&mut self) {
+        self.plugins_table.unselect();
+        self.plugins_table.items.clear();
+        self.open_requested = None;
+    }
+
+    fn search(&self) -> Option<&Search> {
+        Some(&self.search)
+    }
+
+    fn on_enter(&mut self, _: &AppData) {
+        self.open_requested = self
+            .plugins_table
+            .selected_item()
+            .map(|p| (p.pipeline.to_string(), p.id.to_string()));
+    }
+
+    fn on_up(&mut self, _: &AppData) {
+        self.plugins_table.previous();
+    }
+
+    fn on_down(&mut self, _: &AppData) {
+        self.plugins_table.next();
+    }
+
+    fn on_other(&mut self, key_event: KeyEvent, app_data: &AppData) {
+        if self.search.on_key(key_event) {
+            self.update(app_data);
+            return;
+        }
+
+        if let KeyCode::Char(c) = key_event.code {
+            if self.search.is_active() {
+                if c == 'n' {
+                    self.plugins_table.next();
+                } else if c == 'N' {
+                    self.plugins_table.previous();
+                }
+            }
+
+            let column = c
+                .to_digit(10)
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|n| PluginsSortColumn::ALL.get(n as usize));
+
+            if let Some(column) = column {
+                self.sort_by(*column, app_data);
+            }
+        }
+    }
+}
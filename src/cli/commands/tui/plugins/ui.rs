@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Row, Table};
+use ratatui::Frame;
+
+use crate::commands::formatter::NumberFormatter;
+use crate::commands::tui::app::App;
+use crate::commands::tui::plugins::state::{PluginTableItem, PluginsSortColumn};
+use crate::commands::tui::widgets::{
+    alert_marker, utilization_color, TABLE_HEADER_CELL_STYLE, TABLE_HEADER_ROW_STYLE,
+    TABLE_SELECTED_ROW_STYLE, TABLE_SELECTED_ROW_SYMBOL,
+};
+
+pub(crate) fn plugins_tab_shortcuts_help(_: &App) -> HashMap<String, String> {
+    let mut keys = HashMap::with_capacity(4);
+    keys.insert("[↵]".to_string(), "open on pipelines".to_string());
+    keys.insert("[1-8]".to_string(), "sort by column".to_string());
+    keys.insert("[/]".to_string(), "search".to_string());
+    keys.insert("[n/N]".to_string(), "next/previous match".to_string());
+    keys
+}
+
+pub(crate) fn draw_plugins_tab(f: &mut Frame, app: &mut App, area: Rect) {
+    let rows: Vec<Row> = app
+        .plugins_state
+        .plugins_table
+        .items
+        .iter()
+        .map(|item| {
+            Row::new(vec![
+                Cell::from(item.pipeline.to_string()),
+                Cell::from(item.plugin_type.to_string()),
+                create_plugin_name_cell(app, item),
+                Cell::from(item.events_in.format_number()),
+                Cell::from(item.events_out.format_number()),
+                Cell::from(Span::styled(
+                    item.worker_utilization
+                        .map(|v| format!("{}%", v.strip_number_decimals(2)))
+                        .unwrap_or("-".to_string()),
+                    Style::default().fg(utilization_color(item.worker_utilization)),
+                )),
+                Cell::from(
+                    item.worker_millis_per_event
+                        .map(|v| v.strip_number_decimals(4))
+                        .unwrap_or("-".to_string()),
+                ),
+                Cell::from(
+                    item.throughput
+                        .map(|v| format!("{} e/s", v.format_number().trim()))
+                        .unwrap_or("-".to_string()),
+                ),
+            ])
+        })
+        .collect();
+
+    let state = &app.plugins_state;
+    let header_cells = PluginsSortColumn::ALL.iter().map(|column| {
+        let mut header = column.header().to_string();
+        if *column == state.sort_column {
+            header.push_str(if state.sort_descending {
+                " ▼"
+            } else {
+                " ▲"
+            });
+        }
+        Cell::from(header).style(TABLE_HEADER_CELL_STYLE)
+    });
+
+    let header = Row::new(header_cells)
+        .style(TABLE_HEADER_ROW_STYLE)
+        .height(1);
+
+    let widths = vec![
+        Constraint::Percentage(15), // Pipeline
+        Constraint::Length(6),      // Type
+        Constraint::Percentage(25), // Name
+        Constraint::Percentage(9),  // Events in
+        Constraint::Percentage(9),  // Events out
+        Constraint::Percentage(12), // Worker utilization
+        Constraint::Percentage(14), // Worker millis per event
+        Constraint::Percentage(12), // Throughput
+    ];
+
+    let mut title = vec![Span::raw(format!(
+        "Plugins({})",
+        state.plugins_table.items.len()
+    ))];
+
+    title.extend(state.search.title_spans());
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Line::from(title)),
+        )
+        .column_spacing(2)
+        .row_highlight_style(TABLE_SELECTED_ROW_STYLE)
+        .highlight_symbol(TABLE_SELECTED_ROW_SYMBOL);
+
+    f.render_stateful_widget(table, area, &mut app.plugins_state.plugins_table.state);
+}
+
+fn create_plugin_name_cell<'a>(app: &App, item: &PluginTableItem) -> Cell<'a> {
+    let mut spans = vec![Span::raw(item.name.to_string())];
+    if item.explicit_id {
+        spans.push(Span::styled(
+            format!(" ({})", item.id),
+            Style::default().fg(Color::Blue),
+        ));
+    }
+
+    if let Some(severity) = app.alerts_state.plugin_severity(&item.pipeline, &item.id) {
+        spans.push(alert_marker(severity));
+    }
+
+    Cell::from(Line::from(spans))
+}
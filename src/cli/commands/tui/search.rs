@@ -0,0 +1,90 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::style::{Color, Style};
+use ratatui::text::Span;
+use regex::{Regex, RegexBuilder};
+
+/// Incremental search of a table rows, typed after `/`. The query is matched as a case
+/// insensitive regex, or as a plain substring while it isn't a valid regex (e.g. `[main`).
+#[derive(Default)]
+pub(crate) struct Search {
+    pub query: String,
+    /// Whether the prompt is receiving the typed keys
+    pub editing: bool,
+    regex: Option<Regex>,
+}
+
+impl Search {
+    /// Whether the rows are being filtered by a query.
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.query.clear();
+        self.regex = None;
+        self.editing = false;
+    }
+
+    /// Handles the `/` key opening the prompt, the keys typed on it, and the `Esc` key
+    /// clearing the query. Returns whether the key was consumed by the search.
+    pub fn on_key(&mut self, key_event: KeyEvent) -> bool {
+        if !self.editing {
+            return match key_event.code {
+                KeyCode::Char('/') => {
+                    self.editing = true;
+                    true
+                }
+                KeyCode::Esc if self.is_active() => {
+                    self.clear();
+                    true
+                }
+                _ => false,
+            };
+        }
+
+        match key_event.code {
+            KeyCode::Char(c) => self.query.push(c),
+            KeyCode::Backspace => {
+                self.query.pop();
+            }
+            KeyCode::Enter => self.editing = false,
+            KeyCode::Esc => self.clear(),
+            _ => {}
+        }
+
+        self.regex = RegexBuilder::new(&self.query)
+            .case_insensitive(true)
+            .build()
+            .ok();
+
+        true
+    }
+
+    /// Whether any of the row values (names, ids, ...) matches the query.
+    pub fn matches(&self, values: &[&str]) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+
+        let query = self.query.to_lowercase();
+        values.iter().any(|value| match &self.regex {
+            Some(regex) => regex.is_match(value),
+            None => value.to_lowercase().contains(&query),
+        })
+    }
+
+    /// Active filter indicator, appended to the searched table title.
+    pub fn title_spans<'a>(&self) -> Vec<Span<'a>> {
+        if !self.editing && !self.is_active() {
+            return vec![];
+        }
+
+        let style = Style::default().fg(Color::Yellow);
+        let mut spans = vec![Span::styled(format!(" /{}", self.query), style)];
+        if self.editing {
+            spans.push(Span::styled("▏", style));
+        }
+
+        spans
+    }
+}
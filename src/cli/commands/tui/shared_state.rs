@@ -1,10 +1,14 @@
 use crate::api::stats::PipelineStats;
+use crate::commands::analyze::analyzer::{analyze, Analysis};
 use crate::commands::tui::app::AppData;
+use crate::commands::tui::chart_events::{ChartEvent, ChartEvents};
 use crate::commands::tui::charts::{TimestampChartState, DEFAULT_MAX_DATA_POINTS};
 use crate::commands::tui::events::EventsListener;
 use crate::commands::tui::flow_charts::{FlowMetricDataPoint, PluginFlowMetricDataPoint};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+#[derive(Serialize, Deserialize)]
 pub struct PluginFlowChartState {
     pub throughput: TimestampChartState<FlowMetricDataPoint>,
     pub worker_utilization: TimestampChartState<FlowMetricDataPoint>,
@@ -19,8 +23,15 @@ impl PluginFlowChartState {
             worker_millis_per_event: Default::default(),
         }
     }
+
+    fn discard_older_than(&mut self, timestamp: i64) {
+        self.throughput.discard_older_than(timestamp);
+        self.worker_utilization.discard_older_than(timestamp);
+        self.worker_millis_per_event.discard_older_than(timestamp);
+    }
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct PipelineFlowChartState {
     pub plugins_throughput: TimestampChartState<PluginFlowMetricDataPoint>,
     pub input_throughput: TimestampChartState<FlowMetricDataPoint>,
@@ -91,8 +102,22 @@ impl PipelineFlowChartState {
 
         state
     }
+
+    fn discard_older_than(&mut self, timestamp: i64) {
+        self.plugins_throughput.discard_older_than(timestamp);
+        self.input_throughput.discard_older_than(timestamp);
+        self.filter_throughput.discard_older_than(timestamp);
+        self.output_throughput.discard_older_than(timestamp);
+        self.queue_backpressure.discard_older_than(timestamp);
+        self.worker_concurrency.discard_older_than(timestamp);
+        self.queue_persisted_growth_bytes
+            .discard_older_than(timestamp);
+        self.queue_persisted_growth_events
+            .discard_older_than(timestamp);
+    }
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct PipelineChartState {
     pub pipeline: PipelineFlowChartState,
     pub plugins: HashMap<String, PluginFlowChartState>,
@@ -135,19 +160,56 @@ impl PipelineChartState {
             plugins: plugins_states,
         }
     }
+
+    pub(crate) fn discard_older_than(&mut self, timestamp: i64) {
+        self.pipeline.discard_older_than(timestamp);
+        for plugin in self.plugins.values_mut() {
+            plugin.discard_older_than(timestamp);
+        }
+    }
 }
 
 pub struct SharedState {
     pipelines_flows_chart_state: HashMap<String, PipelineChartState>,
+    bottlenecks: Analysis,
+    chart_events: ChartEvents,
 }
 
 impl SharedState {
     pub fn new() -> Self {
         SharedState {
             pipelines_flows_chart_state: Default::default(),
+            bottlenecks: Default::default(),
+            chart_events: Default::default(),
         }
     }
 
+    pub(crate) fn bottlenecks(&self) -> &Analysis {
+        &self.bottlenecks
+    }
+
+    pub(crate) fn chart_events(&self) -> impl Iterator<Item = &ChartEvent> {
+        self.chart_events.events().iter()
+    }
+
+    pub(crate) fn pipelines_chart_states(&self) -> &HashMap<String, PipelineChartState> {
+        &self.pipelines_flows_chart_state
+    }
+
+    pub(crate) fn restore_pipelines_chart_states(
+        &mut self,
+        states: HashMap<String, PipelineChartState>,
+    ) {
+        self.pipelines_flows_chart_state = states;
+    }
+
+    fn update_bottlenecks(&mut self, app_data: &AppData) {
+        self.bottlenecks = match (app_data.node_info(), app_data.node_stats()) {
+            (Some(node_info), Some(node_stats)) => analyze(node_info, node_stats),
+            _ => Analysis::default(),
+        };
+    }
+
     pub(crate) fn pipeline_flows_chart_state(
         &self,
         pipeline: &String,
@@ -277,9 +339,17 @@ impl SharedState {
 impl EventsListener for SharedState {
     fn update(&mut self, app_data: &AppData) {
         self.update_chart_flows_states(app_data);
+        self.update_bottlenecks(app_data);
+        self.chart_events.update(app_data);
+    }
+
+    fn timeline_changed(&mut self, app_data: &AppData) {
+        self.update_bottlenecks(app_data);
     }
 
+    // The chart events are kept, as the node restarts usually happen while disconnected
     fn reset(&mut self) {
         self.pipelines_flows_chart_state.clear();
+        self.bottlenecks = Analysis::default();
     }
 }
@@ -1,11 +1,14 @@
 use std::collections::{HashMap, VecDeque};
 
+use crossterm::event::{KeyCode, KeyEvent};
 use time::format_description::well_known::Iso8601;
 use time::OffsetDateTime;
 
-use crate::commands::tui::app::AppData;
+use crate::commands::tui::app::{AppData, LiveData};
 use crate::commands::tui::events::EventsListener;
+use crate::commands::tui::search::Search;
 use crate::commands::tui::widgets::StatefulTable;
+use crate::commands::watch::capture::HotThreadsCapture;
 
 pub const THREAD_LIST: usize = 0;
 pub const THREAD_TRACES_VIEW: usize = 1;
@@ -18,10 +21,12 @@ pub struct ThreadsState {
     pub threads_table_states_times: VecDeque<OffsetDateTime>,
     pub selected_thread_traces: StatefulTable<String>,
     pub selected_thread_trace_value_offset: usize,
+    pub capture: Option<HotThreadsCapture>,
+    /// Name or id filter of the threads list
+    pub search: Search,
 }
 
 pub struct ThreadTableItem {
-    pub time: String,
     pub name: String,
     pub id: i64,
     pub percent_of_cpu_time: f64,
@@ -30,7 +35,7 @@ pub struct ThreadTableItem {
 }
 
 impl ThreadsState {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(capture: Option<HotThreadsCapture>) -> Self {
         ThreadsState {
             current_focus: THREAD_LIST,
             show_selected_thread: false,
@@ -39,6 +44,8 @@ impl ThreadsState {
             threads_table_states_times: Default::default(),
             selected_thread_traces: StatefulTable::new(),
             selected_thread_trace_value_offset: 0,
+            capture,
+            search: Search::default(),
         }
     }
 
@@ -52,17 +59,20 @@ impl ThreadsState {
 }
 
 impl StatefulTable<ThreadTableItem> {
-    fn update(&mut self, data: &AppData) {
+    fn update(&mut self, data: &AppData, search: &Search) {
         if let Some(threads) = data.hot_threads() {
             let mut new_items = Vec::with_capacity(threads.hot_threads.threads.len());
             for thread in threads.hot_threads.threads.values() {
+                if !search.matches(&[&thread.name, &thread.thread_id.to_string()]) {
+                    continue;
+                }
+
                 let new_item = ThreadTableItem {
                     id: thread.thread_id,
                     name: thread.name.to_string(),
                     percent_of_cpu_time: thread.percent_of_cpu_time,
                     state: thread.state.to_string(),
                     traces: thread.traces.clone(),
-                    time: threads.hot_threads.time.to_string(),
                 };
                 new_items.push(new_item);
             }
@@ -95,21 +105,27 @@ pub(crate) const MAX_THREAD_STATES: usize = 500;
 
 impl EventsListener for ThreadsState {
     fn update(&mut self, app_data: &AppData) {
-        self.threads_table.update(app_data);
+        self.threads_table.update(app_data, &self.search);
 
-        for thread_item in &self.threads_table.items {
-            self.threads_table_states.entry(thread_item.id).or_default();
+        // Filtered out threads states are recorded as well
+        let Some(threads) = app_data.hot_threads() else {
+            return;
+        };
 
-            let states = self.threads_table_states.get_mut(&thread_item.id).unwrap();
+        for thread in threads.hot_threads.threads.values() {
+            let states = self
+                .threads_table_states
+                .entry(thread.thread_id)
+                .or_default();
 
-            if let Ok(time) = OffsetDateTime::parse(&thread_item.time, &Iso8601::DEFAULT) {
+            if let Ok(time) = OffsetDateTime::parse(&threads.hot_threads.time, &Iso8601::DEFAULT) {
                 self.threads_table_states_times.push_back(time);
             } else {
                 self.threads_table_states_times
                     .push_back(OffsetDateTime::now_utc());
             }
 
-            states.push_back(thread_item.state.to_string());
+            states.push_back(thread.state.to_string());
 
             if states.len() > MAX_THREAD_STATES {
                 states.pop_front();
@@ -118,6 +134,20 @@ impl EventsListener for ThreadsState {
         }
     }
 
+    fn live_update(&mut self, live_data: &LiveData) {
+        let (Some(capture), Some(hot_threads)) = (&mut self.capture, live_data.hot_threads) else {
+            return;
+        };
+
+        // Failures are kept on the capture state, and displayed on the threads tab
+        _ = capture.check(live_data.node_stats, hot_threads);
+    }
+
+    fn timeline_changed(&mut self, app_data: &AppData) {
+        self.threads_table.update(app_data, &self.search);
+        self.update_selected_thread_traces(app_data);
+    }
+
     fn reset(&mut self) {
         self.current_focus = THREAD_LIST;
         self.show_selected_thread = false;
@@ -177,4 +207,27 @@ impl EventsListener for ThreadsState {
             self.selected_thread_traces.next();
         }
     }
+
+    fn search(&self) -> Option<&Search> {
+        (self.current_focus == THREAD_LIST).then_some(&self.search)
+    }
+
+    fn on_other(&mut self, key_event: KeyEvent, app_data: &AppData) {
+        if self.current_focus != THREAD_LIST {
+            return;
+        }
+
+        if self.search.on_key(key_event) {
+            self.timeline_changed(app_data);
+            return;
+        }
+
+        if self.search.is_active() {
+            match key_event.code {
+                KeyCode::Char('n') => self.on_down(app_data),
+                KeyCode::Char('N') => self.on_up(app_data),
+                _ => {}
+            }
+        }
+    }
 }
@@ -15,6 +15,7 @@ use crate::commands::tui::widgets::{
     TABLE_HEADER_CELL_STYLE, TABLE_HEADER_ROW_STYLE, TABLE_SELECTED_ROW_STYLE,
     TABLE_SELECTED_ROW_SYMBOL,
 };
+use crate::commands::watch::capture::HotThreadsCapture;
 
 pub(crate) fn draw_threads_tab(f: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
@@ -25,8 +26,10 @@ pub(crate) fn draw_threads_tab(f: &mut Frame, app: &mut App, area: Rect) {
 }
 
 pub(crate) fn threads_tab_shortcuts_help(_: &App) -> HashMap<String, String> {
-    let mut keys = HashMap::with_capacity(1);
+    let mut keys = HashMap::with_capacity(3);
     keys.insert("[↵]".to_string(), "view thread traces".to_string());
+    keys.insert("[/]".to_string(), "search".to_string());
+    keys.insert("[n/N]".to_string(), "next/previous match".to_string());
     keys
 }
 
@@ -186,12 +189,18 @@ fn draw_threads_table(f: &mut Frame, app: &mut App, area: Rect) {
         app.threads_state.threads_table.items.len() as u64
     };
 
+    let mut title = vec![Span::raw(format!("Busiest {} threads", busiest_threads))];
+    title.extend(app.threads_state.search.title_spans());
+    if let Some(capture) = &app.threads_state.capture {
+        title.extend(create_capture_title_spans(capture));
+    }
+
     let threads = Table::new(rows, widths)
         .header(header)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Busiest {} threads", busiest_threads)),
+                .title(Line::from(title)),
         )
         .column_spacing(2)
         .row_highlight_style(TABLE_SELECTED_ROW_STYLE)
@@ -200,6 +209,36 @@ fn draw_threads_table(f: &mut Frame, app: &mut App, area: Rect) {
     f.render_stateful_widget(threads, area, &mut app.threads_state.threads_table.state);
 }
 
+fn create_capture_title_spans<'a>(capture: &HotThreadsCapture) -> Vec<Span<'a>> {
+    let mut spans = vec![Span::styled(
+        format!(
+            " - captures {}/{}",
+            capture.captures.len(),
+            capture.max_captures()
+        ),
+        Style::default().fg(Color::DarkGray),
+    )];
+
+    if let Some(error) = &capture.last_error {
+        spans.push(Span::styled(
+            format!(" - capture failed: {}", error),
+            Style::default().fg(Color::Red),
+        ));
+    } else if let Some(last) = capture.captures.last() {
+        let format = format_description::parse("[hour]:[minute]:[second]").unwrap();
+        spans.push(Span::styled(
+            format!(
+                " - last at {}: {}",
+                last.time.format(&format).unwrap_or_default(),
+                last.path.display()
+            ),
+            Style::default().fg(Color::LightYellow),
+        ));
+    }
+
+    spans
+}
+
 fn create_thread_states_title<'a>(
     threads_state: &ThreadsState,
     line_constraint_percentage: &u16,
@@ -10,24 +10,31 @@ use ratatui::{
     widgets::{Block, Borders, Tabs},
     Frame,
 };
+use time::{format_description, OffsetDateTime, UtcOffset};
 
+use crate::commands::tui::alerts::ui::draw_alerts_tab;
 use crate::commands::tui::app::App;
 use crate::commands::tui::flows::ui::{draw_flows_tab, flows_tab_shortcuts_help};
 use crate::commands::tui::node::ui::draw_node_tab;
 use crate::commands::tui::pipelines::ui::{draw_pipelines_tab, pipelines_tab_shortcuts_help};
+use crate::commands::tui::plugins::ui::{draw_plugins_tab, plugins_tab_shortcuts_help};
 use crate::commands::tui::threads::ui::{draw_threads_tab, threads_tab_shortcuts_help};
+use crate::commands::tui::widgets::alert_marker;
 
 pub(crate) fn draw(f: &mut Frame, app: &mut App) {
+    app.chart_options.timeline_timestamp = app.data.read().unwrap().timeline_timestamp();
+    app.chart_options.events = app.shared_state.chart_events().cloned().collect();
     let last_error_message = app.data.read().unwrap().last_error_message().clone();
-    let constraints = if app.show_help || last_error_message.is_some() {
-        vec![
-            Constraint::Length(3),
-            Constraint::Min(0),
-            Constraint::Length(3),
-        ]
-    } else {
-        vec![Constraint::Length(3), Constraint::Min(0)]
-    };
+    let constraints =
+        if app.show_help || last_error_message.is_some() || app.status_message.is_some() {
+            vec![
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ]
+        } else {
+            vec![Constraint::Length(3), Constraint::Min(0)]
+        };
 
     let chunks = Layout::default()
         .constraints(constraints)
@@ -44,7 +51,7 @@ pub(crate) fn draw(f: &mut Frame, app: &mut App) {
         .flex(Flex::Legacy)
         .constraints(
             [
-                Constraint::Length(37),
+                Constraint::Length(60),
                 Constraint::Percentage(20),
                 Constraint::Percentage(50),
             ]
@@ -83,6 +90,15 @@ pub(crate) fn draw(f: &mut Frame, app: &mut App) {
             ),
             Span::styled("ode", Style::default().add_modifier(Modifier::BOLD)),
         ]),
+        alerts_tab_title(app),
+        Line::from(vec![
+            Span::styled("Plug", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(
+                "i",
+                Style::default().add_modifier(Modifier::UNDERLINED | Modifier::BOLD),
+            ),
+            Span::styled("ns", Style::default().add_modifier(Modifier::BOLD)),
+        ]),
     ];
 
     let tabs = Tabs::new(tab_titles)
@@ -116,15 +132,56 @@ pub(crate) fn draw(f: &mut Frame, app: &mut App) {
         Span::styled("Connected", Style::default().fg(Color::Green))
     };
 
-    let mut status_text_spans = vec![
+    let mut status_text_spans = vec![];
+    let (paused, paused_snapshots, estimated_flow) = {
+        let data = app.data.read().unwrap();
+        (
+            data.paused(),
+            data.paused_snapshots_count(),
+            data.node_stats().is_some_and(|s| s.estimated_flow),
+        )
+    };
+
+    if paused {
+        status_text_spans.push(Span::styled(
+            " PAUSED ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+        if let Some(timestamp) = app.chart_options.timeline_timestamp {
+            status_text_spans.push(Span::styled(
+                format!(" at {}", format_timeline_timestamp(timestamp)),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        status_text_spans.push(Span::styled(
+            format!(" {} buffered | ", paused_snapshots),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    if estimated_flow {
+        status_text_spans.push(Span::styled(
+            "Estimated flow metrics | ",
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    status_text_spans.extend([
         conn_status_span,
         Span::styled(" @ ", Style::default().fg(Color::Gray)),
         Span::from(app.host.as_str()),
-    ];
+    ]);
 
     if let Some(interval) = app.sampling_interval {
         status_text_spans.push(Span::styled(
-            format!(" | Sampling every {}s", interval.as_secs()),
+            format!(
+                " | Sampling every {}s | Charts range {}",
+                interval.as_secs(),
+                app.chart_options.range.as_str()
+            ),
             Style::default().fg(Color::Gray),
         ));
     }
@@ -139,44 +196,72 @@ pub(crate) fn draw(f: &mut Frame, app: &mut App) {
             App::TAB_NODE => draw_node_tab(f, app, chunks[1]),
             App::TAB_FLOWS => draw_flows_tab(f, app, chunks[1]),
             App::TAB_THREADS => draw_threads_tab(f, app, chunks[1]),
+            App::TAB_ALERTS => draw_alerts_tab(f, app, chunks[1]),
+            App::TAB_PLUGINS => draw_plugins_tab(f, app, chunks[1]),
             _ => {}
         };
     }
 
-    if last_error_message.is_some() {
-        draw_error_panel(f, app, chunks[2]);
+    if let Some(error) = &last_error_message {
+        draw_message_panel(f, "Error: ", Color::Red, error, chunks[2]);
     } else if app.show_help {
         let (defaults, shortcuts) = match app.tabs.index {
             App::TAB_PIPELINES => (true, pipelines_tab_shortcuts_help(app)),
             App::TAB_FLOWS => (true, flows_tab_shortcuts_help(app)),
             App::TAB_THREADS => (true, threads_tab_shortcuts_help(app)),
+            App::TAB_PLUGINS => (true, plugins_tab_shortcuts_help(app)),
             _ => (true, Default::default()),
         };
 
         draw_help_panel(f, defaults, shortcuts, chunks[2]);
+    } else if let Some(message) = &app.status_message {
+        draw_message_panel(f, "Warning: ", Color::Yellow, message, chunks[2]);
     }
 }
 
-fn draw_error_panel(f: &mut Frame, app: &App, area: Rect) {
-    let last_error_message = app.data.read().unwrap().last_error_message().clone();
-    if let Some(error) = &last_error_message {
-        f.render_widget(Block::default().borders(Borders::ALL), area);
-
-        let footer_chunks = Layout::default()
-            .constraints([Constraint::Percentage(100)])
-            .direction(Direction::Horizontal)
-            .margin(1)
-            .split(area);
-
-        let w = Paragraph::new(vec![Line::from(vec![
-            Span::styled("Error: ", Style::default().fg(Color::Red)),
-            Span::styled(error, Style::default().fg(Color::DarkGray)),
-        ])])
-        .alignment(Alignment::Left)
-        .wrap(Wrap { trim: true });
+fn format_timeline_timestamp(timestamp: i64) -> String {
+    let format = format_description::parse("[hour]:[minute]:[second]").unwrap();
+    OffsetDateTime::from_unix_timestamp(timestamp)
+        .map(|t| t.to_offset(UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)))
+        .ok()
+        .and_then(|t| t.format(&format).ok())
+        .unwrap_or_default()
+}
+
+fn alerts_tab_title<'a>(app: &App) -> Line<'a> {
+    let mut spans = vec![
+        Span::styled("Al", Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(
+            "e",
+            Style::default().add_modifier(Modifier::UNDERLINED | Modifier::BOLD),
+        ),
+        Span::styled("rts", Style::default().add_modifier(Modifier::BOLD)),
+    ];
 
-        f.render_widget(w, footer_chunks[0]);
+    if let Some(severity) = app.alerts_state.firing().map(|a| a.severity).max() {
+        spans.push(alert_marker(severity));
     }
+
+    Line::from(spans)
+}
+
+fn draw_message_panel(f: &mut Frame, label: &str, color: Color, message: &str, area: Rect) {
+    f.render_widget(Block::default().borders(Borders::ALL), area);
+
+    let footer_chunks = Layout::default()
+        .constraints([Constraint::Percentage(100)])
+        .direction(Direction::Horizontal)
+        .margin(1)
+        .split(area);
+
+    let w = Paragraph::new(vec![Line::from(vec![
+        Span::styled(label, Style::default().fg(color)),
+        Span::styled(message, Style::default().fg(Color::DarkGray)),
+    ])])
+    .alignment(Alignment::Left)
+    .wrap(Wrap { trim: true });
+
+    f.render_widget(w, footer_chunks[0]);
 }
 
 fn draw_help_panel(f: &mut Frame, defaults: bool, shortcuts: HashMap<String, String>, area: Rect) {
@@ -217,12 +302,21 @@ fn default_help_paragraph<'a>(defaults: bool, shortcuts: HashMap<String, String>
     if defaults {
         content.extend(vec![
             separator_span.clone(),
-            Span::styled("[P][F][N] ", Style::default().fg(Color::Yellow)),
+            Span::styled("[P][F][T][N][E][I] ", Style::default().fg(Color::Yellow)),
             Span::styled("switch tabs", Style::default().fg(Color::Gray)),
             separator_span.clone(),
             Span::styled("[▲][▼][◀][▶][Tab] ", Style::default().fg(Color::Yellow)),
             Span::styled("navigate", Style::default().fg(Color::Gray)),
             separator_span.clone(),
+            Span::styled("[Space] ", Style::default().fg(Color::Yellow)),
+            Span::styled("pause", Style::default().fg(Color::Gray)),
+            separator_span.clone(),
+            Span::styled("[,][.] ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "timeline back and forward",
+                Style::default().fg(Color::Gray),
+            ),
+            separator_span.clone(),
             Span::styled("[Esc][Q] ", Style::default().fg(Color::Yellow)),
             Span::styled("exit", Style::default().fg(Color::Gray)),
         ]);
@@ -1,12 +1,77 @@
 use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::text::Span;
 use ratatui::widgets::TableState;
 
+use crate::commands::analyze::analyzer::Severity;
+use crate::commands::formatter::NumberFormatter;
+use crate::commands::tui::alerts::rules::AlertSeverity;
+
 pub(crate) const TABLE_HEADER_CELL_STYLE: Style =
     Style::new().fg(Color::Gray).add_modifier(Modifier::BOLD);
 pub(crate) const TABLE_HEADER_ROW_STYLE: Style = Style::new().bg(Color::DarkGray);
 pub(crate) const TABLE_SELECTED_ROW_STYLE: Style = Style::new().bg(Color::Gray);
 pub(crate) const TABLE_SELECTED_ROW_SYMBOL: &str = "▍";
 
+/// Marker appended to the name of the rows flagged as likely bottlenecks.
+pub(crate) fn bottleneck_marker<'a>(severity: Severity) -> Span<'a> {
+    let color = match severity {
+        Severity::High => Color::Red,
+        Severity::Medium => Color::LightYellow,
+        Severity::Low => Color::DarkGray,
+    };
+
+    Span::styled(" ⚠", Style::default().fg(color))
+}
+
+/// Marker appended to the name of the rows with firing alerts.
+pub(crate) fn alert_marker<'a>(severity: AlertSeverity) -> Span<'a> {
+    let color = match severity {
+        AlertSeverity::Critical => Color::Red,
+        AlertSeverity::Warning => Color::LightYellow,
+    };
+
+    Span::styled(" ●", Style::default().fg(color))
+}
+
+/// Inline sparkline of the values, scaled to their maximum.
+pub(crate) fn sparkline_text(values: impl Iterator<Item = f64>) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let values: Vec<f64> = values.map(|v| v.max(0.0)).collect();
+    let max = values.iter().cloned().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            if max == 0.0 {
+                BARS[0]
+            } else {
+                BARS[((value / max) * (BARS.len() - 1) as f64).round() as usize]
+            }
+        })
+        .collect()
+}
+
+/// Events rate of the last sampling interval, appended to the events counters.
+pub(crate) fn events_rate_span<'a>(events_per_second: Option<f64>) -> Span<'a> {
+    match events_per_second {
+        Some(rate) => Span::styled(
+            format!(" ({} e/s)", rate.format_number().trim()),
+            Style::default().fg(Color::DarkGray),
+        ),
+        None => Span::raw(""),
+    }
+}
+
+/// Color of the worker utilization: green below 50%, yellow below 80%, red otherwise.
+pub(crate) fn utilization_color(utilization: Option<f64>) -> Color {
+    match utilization {
+        None => Color::Gray,
+        Some(u) if u >= 80.0 => Color::Red,
+        Some(u) if u >= 50.0 => Color::Yellow,
+        Some(_) => Color::Green,
+    }
+}
+
 // Tabs
 pub struct TabsState {
     pub index: usize,
@@ -55,6 +120,16 @@ impl<T> StatefulTable<T> {
         self.state.select(None);
     }
 
+    /// Keeps the selected row within the items, e.g. after they're filtered.
+    pub fn clamp_selection(&mut self) {
+        if let Some(index) = self.state.selected() {
+            self.state.select(match self.items.len() {
+                0 => None,
+                len => Some(index.min(len - 1)),
+            });
+        }
+    }
+
     pub fn has_next(&mut self) -> bool {
         if self.items.is_empty() {
             return false;
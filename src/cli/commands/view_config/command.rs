@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use clap::Args;
+
+use crate::api::node::{Graph, GraphDefinition, NodeInfo, PipelineInfo};
+use crate::api::stats::{NodeStats, PipelineStats};
+use crate::commands::traits::RunnableCommand;
+use crate::commands::tui::backend::run_with_static_data;
+use crate::commands::tui::command::TuiArgs;
+use crate::commands::tui::data_fetcher::StaticDataFetcher;
+use crate::commands::view_config::graph::GraphBuilder;
+use crate::commands::view_config::parser::ConfigParser;
+use crate::config::Config;
+use crate::errors::{AnyError, TuiError};
+use crate::output::Output;
+
+#[derive(Args)]
+pub struct ViewConfigArgs {
+    /// Pipeline configuration files. Each file is displayed as a pipeline named after the file
+    #[arg(required = true)]
+    pub files: Vec<String>,
+}
+
+pub struct ViewConfigCommand;
+
+impl RunnableCommand<ViewConfigArgs> for ViewConfigCommand {
+    fn run(&self, _: &mut Output, args: &ViewConfigArgs, _: &Config) -> Result<(), AnyError> {
+        let mut pipelines: HashMap<String, PipelineInfo> = HashMap::new();
+        for file in &args.files {
+            let name = Path::new(file)
+                .file_stem()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or(file.to_string());
+
+            if pipelines.contains_key(&name) {
+                return Err(TuiError::from(
+                    format!("Duplicated pipeline name {} for file {}", name, file).as_str(),
+                )
+                .into());
+            }
+
+            let graph = ViewConfigCommand::parse_file(file)?;
+            pipelines.insert(
+                name,
+                PipelineInfo {
+                    graph: Graph { graph },
+                    ..Default::default()
+                },
+            );
+        }
+
+        let node_stats = NodeStats {
+            pipelines: pipelines
+                .keys()
+                .map(|name| (name.to_string(), PipelineStats::default()))
+                .collect(),
+            ..Default::default()
+        };
+
+        let node_info = NodeInfo {
+            pipelines: Some(pipelines),
+            ..Default::default()
+        };
+
+        let fetcher = StaticDataFetcher::new(node_info, node_stats);
+        run_with_static_data(args.files.join(", "), &fetcher, &TuiArgs::default())
+    }
+}
+
+impl ViewConfigCommand {
+    fn parse_file(file: &str) -> Result<GraphDefinition, AnyError> {
+        let content = fs::read_to_string(file)?;
+        let sections = ConfigParser::parse(&content)
+            .map_err(|e| TuiError::from(format!("{}: {}", file, e).as_str()))?;
+
+        let graph = GraphBuilder::build(&sections, file)
+            .map_err(|e| TuiError::from(format!("{}: {}", file, e).as_str()))?;
+
+        Ok(graph)
+    }
+}
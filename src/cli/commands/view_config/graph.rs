@@ -0,0 +1,275 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::api::node::{Edge, GraphDefinition, Vertex, VertexMeta, VertexMetaSource};
+use crate::commands::view_config::parser::{
+    IfStatement, PluginStatement, Section, SourceLocation, Statement,
+};
+
+const QUEUE_VERTEX_ID: &str = "__QUEUE__";
+
+/// Vertex that the next statement must be connected to, and the `when` value of
+/// that edge if the vertex is an `if` branch.
+type Exit = (String, Option<bool>);
+
+/// Builds the same graph definition returned by the `_node/pipelines?graph=true` API,
+/// where inputs are connected to the queue, and the queue to the filters and outputs.
+pub(crate) struct GraphBuilder<'a> {
+    source_id: &'a str,
+    vertices: Vec<Vertex>,
+    edges: Vec<Edge>,
+    explicit_ids: HashSet<String>,
+}
+
+impl<'a> GraphBuilder<'a> {
+    pub fn build(sections: &[Section], source_id: &'a str) -> Result<GraphDefinition, String> {
+        let mut builder = GraphBuilder {
+            source_id,
+            vertices: vec![],
+            edges: vec![],
+            explicit_ids: HashSet::new(),
+        };
+
+        builder.vertices.push(Vertex {
+            id: QUEUE_VERTEX_ID.to_string(),
+            r#type: "queue".to_string(),
+            ..Default::default()
+        });
+
+        for section in sections.iter().filter(|s| s.plugin_type == "input") {
+            for statement in &section.statements {
+                match statement {
+                    Statement::Plugin(plugin) => {
+                        let id = builder.add_plugin_vertex(plugin, "input")?;
+                        builder.add_edge(&id, QUEUE_VERTEX_ID, None);
+                    }
+                    Statement::If(branch) => {
+                        return Err(format!(
+                            "Conditionals are not supported on the input section at line {}, column {}",
+                            branch.location.line, branch.location.column
+                        ));
+                    }
+                }
+            }
+        }
+
+        let mut exits: Vec<Exit> = vec![(QUEUE_VERTEX_ID.to_string(), None)];
+        for section in sections.iter().filter(|s| s.plugin_type == "filter") {
+            exits = builder.add_statements(&section.statements, "filter", exits)?;
+        }
+
+        for section in sections.iter().filter(|s| s.plugin_type == "output") {
+            builder.add_statements(&section.statements, "output", exits.clone())?;
+        }
+
+        Ok(GraphDefinition {
+            vertices: builder.vertices,
+            edges: builder.edges,
+        })
+    }
+
+    /// Filters are chained one after another, while outputs are composed in parallel,
+    /// each one receiving the events of the same `exits`.
+    fn add_statements(
+        &mut self,
+        statements: &[Statement],
+        plugin_type: &str,
+        mut exits: Vec<Exit>,
+    ) -> Result<Vec<Exit>, String> {
+        if plugin_type == "output" {
+            let mut outputs_exits = vec![];
+            for statement in statements {
+                outputs_exits.extend(self.add_statement(statement, plugin_type, &exits)?);
+            }
+
+            return Ok(outputs_exits);
+        }
+
+        for statement in statements {
+            exits = self.add_statement(statement, plugin_type, &exits)?;
+        }
+
+        Ok(exits)
+    }
+
+    fn add_statement(
+        &mut self,
+        statement: &Statement,
+        plugin_type: &str,
+        exits: &[Exit],
+    ) -> Result<Vec<Exit>, String> {
+        match statement {
+            Statement::Plugin(plugin) => {
+                let id = self.add_plugin_vertex(plugin, plugin_type)?;
+                self.connect(exits, &id);
+                Ok(vec![(id, None)])
+            }
+            Statement::If(branch) => self.add_if(branch, plugin_type, exits),
+        }
+    }
+
+    fn add_if(
+        &mut self,
+        branch: &IfStatement,
+        plugin_type: &str,
+        exits: &[Exit],
+    ) -> Result<Vec<Exit>, String> {
+        let id = format!("if-{}-{}", branch.location.line, branch.location.column);
+        self.vertices.push(Vertex {
+            id: id.to_string(),
+            condition: branch.condition.to_string(),
+            r#type: "if".to_string(),
+            meta: Some(self.vertex_meta(&branch.location)),
+            ..Default::default()
+        });
+        self.connect(exits, &id);
+
+        let mut branch_exits = self.add_statements(
+            &branch.then_statements,
+            plugin_type,
+            vec![(id.to_string(), Some(true))],
+        )?;
+
+        branch_exits.extend(self.add_statements(
+            &branch.else_statements,
+            plugin_type,
+            vec![(id, Some(false))],
+        )?);
+
+        Ok(branch_exits)
+    }
+
+    fn add_plugin_vertex(
+        &mut self,
+        plugin: &PluginStatement,
+        plugin_type: &str,
+    ) -> Result<String, String> {
+        let explicit_id = plugin.id.is_some();
+        let id = match &plugin.id {
+            Some(id) => {
+                if !self.explicit_ids.insert(id.to_string()) {
+                    return Err(format!(
+                        "Duplicate plugin id '{}' at line {}, column {}",
+                        id, plugin.location.line, plugin.location.column
+                    ));
+                }
+                id.to_string()
+            }
+            None => format!(
+                "{}-{}-{}",
+                plugin.name, plugin.location.line, plugin.location.column
+            ),
+        };
+
+        self.vertices.push(Vertex {
+            id: id.to_string(),
+            explicit_id,
+            config_name: plugin.name.to_string(),
+            plugin_type: plugin_type.to_string(),
+            r#type: "plugin".to_string(),
+            meta: Some(self.vertex_meta(&plugin.location)),
+            ..Default::default()
+        });
+
+        Ok(id)
+    }
+
+    fn connect(&mut self, exits: &[Exit], to: &str) {
+        for (from, when) in exits {
+            self.add_edge(from, to, *when);
+        }
+    }
+
+    fn add_edge(&mut self, from: &str, to: &str, when: Option<bool>) {
+        self.edges.push(Edge {
+            id: Uuid::new_v4().to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            r#type: if when.is_some() { "boolean" } else { "plain" }.to_string(),
+            when,
+        });
+    }
+
+    fn vertex_meta(&self, location: &SourceLocation) -> VertexMeta {
+        VertexMeta {
+            source: VertexMetaSource {
+                protocol: "file".to_string(),
+                id: self.source_id.to_string(),
+                line: location.line,
+                column: location.column,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::view_config::parser::ConfigParser;
+
+    fn edges(config: &str) -> Vec<(String, String, Option<bool>)> {
+        let sections = ConfigParser::parse(config).unwrap();
+        let graph = GraphBuilder::build(&sections, "test.conf").unwrap();
+        let mut edges: Vec<_> = graph
+            .edges
+            .into_iter()
+            .map(|e| (e.from, e.to, e.when))
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    fn edge(from: &str, to: &str, when: Option<bool>) -> (String, String, Option<bool>) {
+        (from.to_string(), to.to_string(), when)
+    }
+
+    #[test]
+    fn chains_the_filters_and_composes_the_outputs_in_parallel() {
+        let config = r#"
+input { stdin { id => "in" } }
+filter {
+  mutate { id => "f1" }
+  if [a] { grok { id => "f2" } }
+  ruby { id => "f3" }
+}
+output {
+  elasticsearch { id => "es" }
+  stdout { id => "out" }
+  if [b] { file { id => "file" } http { id => "http" } } else { null { id => "null" } }
+}
+"#;
+        let if_filter = "if-5-3";
+        let if_output = "if-11-3";
+        let mut expected = vec![
+            edge("in", QUEUE_VERTEX_ID, None),
+            edge(QUEUE_VERTEX_ID, "f1", None),
+            edge("f1", if_filter, None),
+            edge(if_filter, "f2", Some(true)),
+            edge("f2", "f3", None),
+            edge(if_filter, "f3", Some(false)),
+            edge("f3", "es", None),
+            edge("f3", "out", None),
+            edge("f3", if_output, None),
+            edge(if_output, "file", Some(true)),
+            edge(if_output, "http", Some(true)),
+            edge(if_output, "null", Some(false)),
+        ];
+        expected.sort();
+
+        assert_eq!(edges(config), expected);
+    }
+
+    #[test]
+    fn connects_the_outputs_to_the_queue_without_filters() {
+        let config = "input { stdin { id => \"in\" } } output { elasticsearch { id => \"es\" } stdout { id => \"out\" } }";
+        let mut expected = vec![
+            edge("in", QUEUE_VERTEX_ID, None),
+            edge(QUEUE_VERTEX_ID, "es", None),
+            edge(QUEUE_VERTEX_ID, "out", None),
+        ];
+        expected.sort();
+
+        assert_eq!(edges(config), expected);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod command;
+mod graph;
+mod parser;
@@ -0,0 +1,600 @@
+/// Position of a statement on the configuration source, both 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SourceLocation {
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug)]
+pub(crate) struct PluginStatement {
+    pub name: String,
+    pub id: Option<String>,
+    pub location: SourceLocation,
+}
+
+/// An `if` branch. `else if` branches are represented as a nested `IfStatement`
+/// being the only statement of `else_statements`.
+#[derive(Debug)]
+pub(crate) struct IfStatement {
+    pub condition: String,
+    pub location: SourceLocation,
+    pub then_statements: Vec<Statement>,
+    pub else_statements: Vec<Statement>,
+}
+
+#[derive(Debug)]
+pub(crate) enum Statement {
+    Plugin(PluginStatement),
+    If(IfStatement),
+}
+
+#[derive(Debug)]
+pub(crate) struct Section {
+    pub plugin_type: String,
+    pub statements: Vec<Statement>,
+}
+
+/// A recursive descent parser for the Logstash pipeline configuration language.
+/// Plugin settings are validated but not kept, except for the plugin `id`.
+pub(crate) struct ConfigParser {
+    chars: Vec<char>,
+    pos: usize,
+    line_starts: Vec<usize>,
+}
+
+impl ConfigParser {
+    const SECTIONS: &'static [&'static str] = &["input", "filter", "output"];
+
+    pub fn parse(content: &str) -> Result<Vec<Section>, String> {
+        let chars: Vec<char> = content.chars().collect();
+        let mut line_starts = vec![0];
+        for (i, c) in chars.iter().enumerate() {
+            if *c == '\n' {
+                line_starts.push(i + 1);
+            }
+        }
+
+        let mut parser = ConfigParser {
+            chars,
+            pos: 0,
+            line_starts,
+        };
+
+        let mut sections = vec![];
+        loop {
+            parser.skip_whitespaces();
+            if parser.peek().is_none() {
+                break;
+            }
+
+            sections.push(parser.parse_section()?);
+        }
+
+        Ok(sections)
+    }
+
+    fn parse_section(&mut self) -> Result<Section, String> {
+        let start = self.pos;
+        let plugin_type = self.parse_bareword()?;
+        if !Self::SECTIONS.contains(&plugin_type.as_str()) {
+            return Err(self.error_at(
+                start,
+                &format!(
+                    "Unknown section '{}', expected 'input', 'filter' or 'output'",
+                    plugin_type
+                ),
+            ));
+        }
+
+        self.skip_whitespaces();
+        self.expect('{')?;
+        let statements = self.parse_statements()?;
+        self.expect('}')?;
+
+        Ok(Section {
+            plugin_type,
+            statements,
+        })
+    }
+
+    /// Parses plugins and branches until the closing `}` of the current block.
+    fn parse_statements(&mut self) -> Result<Vec<Statement>, String> {
+        let mut statements = vec![];
+        loop {
+            self.skip_whitespaces();
+            match self.peek() {
+                None => return Err(self.error("Unexpected end of file, expected '}'")),
+                Some('}') => return Ok(statements),
+                _ => {}
+            }
+
+            let start = self.pos;
+            let name = self.parse_name()?;
+            if name == "if" {
+                statements.push(Statement::If(self.parse_if(start)?));
+            } else if name == "else" {
+                return Err(self.error_at(start, "Unexpected 'else' without a matching 'if'"));
+            } else {
+                statements.push(Statement::Plugin(self.parse_plugin(name, start)?));
+            }
+        }
+    }
+
+    fn parse_block(&mut self) -> Result<Vec<Statement>, String> {
+        self.skip_whitespaces();
+        self.expect('{')?;
+        let statements = self.parse_statements()?;
+        self.expect('}')?;
+        Ok(statements)
+    }
+
+    fn parse_if(&mut self, start: usize) -> Result<IfStatement, String> {
+        let condition = self.parse_condition()?;
+        let then_statements = self.parse_block()?;
+
+        let mut else_statements = vec![];
+        let checkpoint = self.pos;
+        self.skip_whitespaces();
+        if self.consume_keyword("else") {
+            self.skip_whitespaces();
+            let else_if_start = self.pos;
+            if self.consume_keyword("if") {
+                else_statements.push(Statement::If(self.parse_if(else_if_start)?));
+            } else {
+                else_statements = self.parse_block()?;
+            }
+        } else {
+            self.pos = checkpoint;
+        }
+
+        Ok(IfStatement {
+            condition,
+            location: self.location(start),
+            then_statements,
+            else_statements,
+        })
+    }
+
+    /// Reads the raw condition expression up to the opening `{` of the branch block,
+    /// collapsing the whitespaces that are not part of string or regex literals.
+    fn parse_condition(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        let mut condition = String::new();
+        let mut depth = 0;
+
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error_at(start, "Unexpected end of file on the 'if' condition"));
+            };
+
+            match c {
+                '{' if depth == 0 => break,
+                '"' | '\'' | '/' => {
+                    let literal_start = self.pos;
+                    self.skip_quoted(c)?;
+                    condition.extend(&self.chars[literal_start..self.pos]);
+                    continue;
+                }
+                '#' => {
+                    self.skip_comment();
+                    continue;
+                }
+                '[' | '(' => depth += 1,
+                ']' | ')' => depth -= 1,
+                _ => {}
+            }
+
+            if c.is_whitespace() {
+                if !condition.ends_with(' ') {
+                    condition.push(' ');
+                }
+            } else {
+                condition.push(c);
+            }
+
+            self.pos += 1;
+        }
+
+        let condition = condition.trim().to_string();
+        if condition.is_empty() {
+            return Err(self.error_at(start, "Missing 'if' condition"));
+        }
+
+        Ok(condition)
+    }
+
+    fn parse_plugin(&mut self, name: String, start: usize) -> Result<PluginStatement, String> {
+        let id = self.parse_attributes()?;
+        Ok(PluginStatement {
+            name,
+            id,
+            location: self.location(start),
+        })
+    }
+
+    /// Parses the `{ name => value ... }` plugin settings, returning the `id` value if present.
+    fn parse_attributes(&mut self) -> Result<Option<String>, String> {
+        self.skip_whitespaces();
+        self.expect('{')?;
+
+        let mut id = None;
+        loop {
+            self.skip_whitespaces();
+            match self.peek() {
+                None => return Err(self.error("Unexpected end of file, expected '}'")),
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(id);
+                }
+                _ => {}
+            }
+
+            let name = self.parse_name()?;
+            self.skip_whitespaces();
+            self.expect_str("=>")?;
+            self.skip_whitespaces();
+            let value = self.parse_value()?;
+
+            if name == "id" {
+                id = value;
+            }
+        }
+    }
+
+    /// Parses a setting value, returning its content when it's a scalar.
+    fn parse_value(&mut self) -> Result<Option<String>, String> {
+        match self.peek() {
+            Some('"') | Some('\'') => {
+                let start = self.pos;
+                self.skip_quoted(self.chars[start])?;
+                Ok(Some(self.chars[start + 1..self.pos - 1].iter().collect()))
+            }
+            Some('[') => {
+                self.parse_array()?;
+                Ok(None)
+            }
+            Some('{') => {
+                self.parse_hash()?;
+                Ok(None)
+            }
+            Some(c) if Self::is_bareword_char(c) => {
+                let value = self.parse_bareword()?;
+
+                // Plugin values, such as `codec => json { charset => "UTF-8" }`
+                let checkpoint = self.pos;
+                self.skip_whitespaces();
+                if self.peek() == Some('{') {
+                    self.parse_attributes()?;
+                    return Ok(None);
+                }
+
+                self.pos = checkpoint;
+                Ok(Some(value))
+            }
+            _ => Err(self.error("Invalid value")),
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<(), String> {
+        self.expect('[')?;
+        loop {
+            self.skip_whitespaces();
+            match self.peek() {
+                None => return Err(self.error("Unexpected end of file, expected ']'")),
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(',') => self.pos += 1,
+                _ => {
+                    self.parse_value()?;
+                }
+            }
+        }
+    }
+
+    fn parse_hash(&mut self) -> Result<(), String> {
+        self.expect('{')?;
+        loop {
+            self.skip_whitespaces();
+            match self.peek() {
+                None => return Err(self.error("Unexpected end of file, expected '}'")),
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(',') => self.pos += 1,
+                _ => {
+                    self.parse_name()?;
+                    self.skip_whitespaces();
+                    self.expect_str("=>")?;
+                    self.skip_whitespaces();
+                    self.parse_value()?;
+                }
+            }
+        }
+    }
+
+    /// Names can be either barewords or quoted strings.
+    fn parse_name(&mut self) -> Result<String, String> {
+        match self.peek() {
+            Some('"') | Some('\'') => {
+                let start = self.pos;
+                self.skip_quoted(self.chars[start])?;
+                Ok(self.chars[start + 1..self.pos - 1].iter().collect())
+            }
+            _ => self.parse_bareword(),
+        }
+    }
+
+    fn parse_bareword(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        while self.peek().is_some_and(Self::is_bareword_char) {
+            self.pos += 1;
+        }
+
+        if start == self.pos {
+            return match self.peek() {
+                Some(c) => Err(self.error(&format!("Unexpected character '{}'", c))),
+                None => Err(self.error("Unexpected end of file")),
+            };
+        }
+
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn is_bareword_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_' || c == '-' || c == '.' || c == '@'
+    }
+
+    /// Skips a literal delimited by `quote`, honoring backslash escapes.
+    fn skip_quoted(&mut self, quote: char) -> Result<(), String> {
+        let start = self.pos;
+        self.pos += 1;
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if c == '\\' {
+                self.pos += 1;
+            } else if c == quote {
+                return Ok(());
+            }
+        }
+
+        Err(self.error_at(start, "Unterminated literal"))
+    }
+
+    fn skip_comment(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                break;
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                self.skip_comment();
+            } else if c.is_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        let end = self.pos + keyword.len();
+        if end > self.chars.len()
+            || !self.chars[self.pos..end]
+                .iter()
+                .copied()
+                .eq(keyword.chars())
+        {
+            return false;
+        }
+
+        if self
+            .chars
+            .get(end)
+            .is_some_and(|c| Self::is_bareword_char(*c))
+        {
+            return false;
+        }
+
+        self.pos = end;
+        true
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(&format!("Expected '{}' but found '{}'", expected, c))),
+            None => Err(self.error(&format!("Expected '{}' but found end of file", expected))),
+        }
+    }
+
+    fn expect_str(&mut self, expected: &str) -> Result<(), String> {
+        for c in expected.chars() {
+            self.expect(c)?;
+        }
+        Ok(())
+    }
+
+    fn location(&self, pos: usize) -> SourceLocation {
+        let line = self.line_starts.partition_point(|start| *start <= pos);
+        SourceLocation {
+            line: line as u32,
+            column: (pos - self.line_starts[line - 1] + 1) as u32,
+        }
+    }
+
+    fn error(&self, message: &str) -> String {
+        self.error_at(self.pos, message)
+    }
+
+    fn error_at(&self, pos: usize, message: &str) -> String {
+        let location = self.location(pos);
+        format!(
+            "{} at line {}, column {}",
+            message, location.line, location.column
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(statement: &Statement) -> &PluginStatement {
+        match statement {
+            Statement::Plugin(plugin) => plugin,
+            Statement::If(_) => panic!("expected a plugin statement"),
+        }
+    }
+
+    fn branch(statement: &Statement) -> &IfStatement {
+        match statement {
+            Statement::If(branch) => branch,
+            Statement::Plugin(_) => panic!("expected an if statement"),
+        }
+    }
+
+    #[test]
+    fn parses_sections_plugins_and_ids() {
+        let config = r#"
+# Comment before the first section
+input {
+  beats { port => 5044 id => "beats_in" }
+}
+filter {
+  mutate {
+    add_field => { "path" => "C:\\logs\\{app}" "quote" => 'it\'s # not a comment' }
+    remove_field => [ "a", "b" ]
+  }
+  grok { match => { "message" => "%{WORD:w}" } } # trailing comment
+}
+output {
+  elasticsearch { hosts => ["localhost"] codec => json { charset => "UTF-8" } id => 'es' }
+}
+"#;
+        let sections = ConfigParser::parse(config).unwrap();
+        let types: Vec<&str> = sections.iter().map(|s| s.plugin_type.as_str()).collect();
+        assert_eq!(types, ["input", "filter", "output"]);
+
+        let beats = plugin(&sections[0].statements[0]);
+        assert_eq!(beats.name, "beats");
+        assert_eq!(beats.id.as_deref(), Some("beats_in"));
+        assert_eq!(beats.location, SourceLocation { line: 4, column: 3 });
+
+        let filters: Vec<&str> = sections[1]
+            .statements
+            .iter()
+            .map(|s| plugin(s).name.as_str())
+            .collect();
+        assert_eq!(filters, ["mutate", "grok"]);
+        assert_eq!(plugin(&sections[1].statements[0]).id, None);
+
+        let elasticsearch = plugin(&sections[2].statements[0]);
+        assert_eq!(elasticsearch.id.as_deref(), Some("es"));
+        assert_eq!(
+            elasticsearch.location,
+            SourceLocation {
+                line: 14,
+                column: 3
+            }
+        );
+    }
+
+    #[test]
+    fn parses_nested_conditionals() {
+        let config = r#"filter {
+  if [type] == "a{" and [message] =~ /^\/b}/ {
+    if   [tags]
+         and ![x] { drop {} }
+  } else if "c" in [tags] {
+    mutate {}
+  } else {
+    ruby {}
+  }
+}"#;
+        let sections = ConfigParser::parse(config).unwrap();
+        assert_eq!(sections[0].statements.len(), 1);
+
+        let outer = branch(&sections[0].statements[0]);
+        assert_eq!(
+            outer.condition,
+            r#"[type] == "a{" and [message] =~ /^\/b}/"#
+        );
+        assert_eq!(outer.location, SourceLocation { line: 2, column: 3 });
+
+        let inner = branch(&outer.then_statements[0]);
+        assert_eq!(inner.condition, "[tags] and ![x]");
+        assert_eq!(plugin(&inner.then_statements[0]).name, "drop");
+        assert!(inner.else_statements.is_empty());
+
+        let else_if = branch(&outer.else_statements[0]);
+        assert_eq!(outer.else_statements.len(), 1);
+        assert_eq!(else_if.condition, r#""c" in [tags]"#);
+        assert_eq!(
+            else_if.location,
+            SourceLocation {
+                line: 5,
+                column: 10
+            }
+        );
+        assert_eq!(plugin(&else_if.then_statements[0]).name, "mutate");
+        assert_eq!(plugin(&else_if.else_statements[0]).name, "ruby");
+    }
+
+    #[test]
+    fn reports_the_error_positions() {
+        let cases = [
+            (
+                "inputs { stdin {} }",
+                "Unknown section 'inputs', expected 'input', 'filter' or 'output' at line 1, column 1",
+            ),
+            (
+                "input {\n  stdin { tags => \"a }\n}",
+                "Unterminated literal at line 2, column 19",
+            ),
+            (
+                "filter {\n  mutate { a = 1 }\n}",
+                "Expected '>' but found ' ' at line 2, column 15",
+            ),
+            (
+                "filter {\n  else { drop {} }\n}",
+                "Unexpected 'else' without a matching 'if' at line 2, column 3",
+            ),
+            (
+                "filter {\n  if {\n  }\n}",
+                "Missing 'if' condition at line 2, column 5",
+            ),
+            (
+                "output {\n  stdout {}\n",
+                "Unexpected end of file, expected '}' at line 3, column 1",
+            ),
+            (
+                "filter { mutate { a => ; } }",
+                "Invalid value at line 1, column 24",
+            ),
+        ];
+
+        for (config, expected) in cases {
+            assert_eq!(
+                ConfigParser::parse(config).unwrap_err(),
+                expected,
+                "{}",
+                config
+            );
+        }
+    }
+}
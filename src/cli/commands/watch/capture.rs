@@ -0,0 +1,196 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use serde_json::json;
+use time::format_description::well_known::Rfc3339;
+use time::{format_description, OffsetDateTime};
+
+use crate::api::hot_threads::NodeHotThreads;
+use crate::api::stats::NodeStats;
+use crate::errors::{AnyError, TuiError};
+
+#[derive(Args, Clone)]
+pub struct CaptureArgs {
+    /// Directory where the hot threads are saved when the CPU usage crosses the thresholds
+    #[arg(long)]
+    pub capture_dir: Option<PathBuf>,
+
+    /// Process CPU percent that triggers a hot threads capture
+    #[arg(long, default_value_t = 80)]
+    pub capture_cpu_threshold: i64,
+
+    /// Thread CPU time percent that triggers a hot threads capture
+    #[arg(long)]
+    pub capture_thread_threshold: Option<f64>,
+
+    /// Minimum interval in seconds between captures
+    #[arg(long, default_value_t = 300)]
+    pub capture_cooldown: u64,
+
+    /// Maximum number of captures
+    #[arg(long, default_value_t = 10)]
+    pub capture_max: usize,
+}
+
+impl Default for CaptureArgs {
+    fn default() -> Self {
+        CaptureArgs {
+            capture_dir: None,
+            capture_cpu_threshold: 80,
+            capture_thread_threshold: None,
+            capture_cooldown: 300,
+            capture_max: 10,
+        }
+    }
+}
+
+pub struct Capture {
+    pub path: PathBuf,
+    pub reason: String,
+    pub time: OffsetDateTime,
+}
+
+/// Saves the hot threads and the node stats to a timestamped file when the process CPU,
+/// or a thread CPU time, crosses the configured thresholds.
+pub struct HotThreadsCapture {
+    dir: PathBuf,
+    cpu_threshold: i64,
+    thread_threshold: Option<f64>,
+    cooldown: Duration,
+    max_captures: usize,
+    last_capture: Option<Instant>,
+    pub captures: Vec<Capture>,
+    pub last_error: Option<String>,
+}
+
+impl HotThreadsCapture {
+    pub fn new(args: &CaptureArgs) -> Result<Option<Self>, AnyError> {
+        let Some(dir) = &args.capture_dir else {
+            return Ok(None);
+        };
+
+        if args.capture_max == 0 {
+            return Err(TuiError::from("The --capture-max must be greater than 0").into());
+        }
+
+        fs::create_dir_all(dir).map_err(|e| {
+            TuiError::from(
+                format!(
+                    "Failed to create the capture directory {}: {}",
+                    dir.display(),
+                    e
+                )
+                .as_str(),
+            )
+        })?;
+
+        Ok(Some(HotThreadsCapture {
+            dir: dir.clone(),
+            cpu_threshold: args.capture_cpu_threshold,
+            thread_threshold: args.capture_thread_threshold,
+            cooldown: Duration::from_secs(args.capture_cooldown),
+            max_captures: args.capture_max,
+            last_capture: None,
+            captures: vec![],
+            last_error: None,
+        }))
+    }
+
+    pub fn max_captures(&self) -> usize {
+        self.max_captures
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.captures.len() >= self.max_captures
+    }
+
+    fn is_cooling_down(&self) -> bool {
+        self.last_capture
+            .is_some_and(|last| last.elapsed() < self.cooldown)
+    }
+
+    /// Whether the hot threads, a heavy API request, are needed to check the thresholds:
+    /// the process CPU is over its threshold, or a thread threshold is configured.
+    pub fn needs_hot_threads(&self, node_stats: &NodeStats) -> bool {
+        !self.is_exhausted()
+            && !self.is_cooling_down()
+            && (node_stats.process.cpu.percent >= self.cpu_threshold
+                || self.thread_threshold.is_some())
+    }
+
+    /// Checks the thresholds, returning the new capture if the data was saved.
+    pub fn check(
+        &mut self,
+        node_stats: &NodeStats,
+        hot_threads: &NodeHotThreads,
+    ) -> Result<Option<&Capture>, AnyError> {
+        if self.is_exhausted() || self.is_cooling_down() {
+            return Ok(None);
+        }
+
+        let Some(reason) = self.trigger_reason(node_stats, hot_threads) else {
+            return Ok(None);
+        };
+
+        let time = OffsetDateTime::now_local().unwrap_or(OffsetDateTime::now_utc());
+        let format =
+            format_description::parse("[year][month][day]T[hour][minute][second]").unwrap();
+        let timestamp = time.format(&format).unwrap_or_default();
+        let mut path = self.dir.join(format!("hot_threads_{}.json", timestamp));
+        let mut suffix = 1;
+        while path.exists() {
+            path = self
+                .dir
+                .join(format!("hot_threads_{}_{}.json", timestamp, suffix));
+            suffix += 1;
+        }
+
+        let content = json!({
+            "time": time.format(&Rfc3339).unwrap_or_default(),
+            "reason": reason,
+            "node_stats": node_stats,
+            "hot_threads": hot_threads,
+        });
+
+        self.last_capture = Some(Instant::now());
+        if let Err(e) = fs::write(&path, serde_json::to_string_pretty(&content)?) {
+            let message = format!("Failed to write {}: {}", path.display(), e);
+            self.last_error = Some(message.to_string());
+            return Err(TuiError::from(message.as_str()).into());
+        }
+
+        self.last_error = None;
+        self.captures.push(Capture { path, reason, time });
+        Ok(self.captures.last())
+    }
+
+    fn trigger_reason(
+        &self,
+        node_stats: &NodeStats,
+        hot_threads: &NodeHotThreads,
+    ) -> Option<String> {
+        let cpu_percent = node_stats.process.cpu.percent;
+        if cpu_percent >= self.cpu_threshold {
+            return Some(format!(
+                "Process CPU at {}% (threshold {}%)",
+                cpu_percent, self.cpu_threshold
+            ));
+        }
+
+        let threshold = self.thread_threshold?;
+        hot_threads
+            .hot_threads
+            .threads
+            .values()
+            .filter(|t| t.percent_of_cpu_time >= threshold)
+            .max_by(|a, b| a.percent_of_cpu_time.total_cmp(&b.percent_of_cpu_time))
+            .map(|t| {
+                format!(
+                    "Thread '{}' at {}% CPU time (threshold {}%)",
+                    t.name, t.percent_of_cpu_time, threshold
+                )
+            })
+    }
+}
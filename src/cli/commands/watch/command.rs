@@ -0,0 +1,89 @@
+use std::thread;
+use std::time::Duration;
+
+use clap::Args;
+
+use crate::commands::traits::RunnableCommand;
+use crate::commands::watch::capture::{CaptureArgs, HotThreadsCapture};
+use crate::config::Config;
+use crate::errors::{AnyError, TuiError};
+use crate::output::Output;
+
+#[derive(Args)]
+pub struct WatchArgs {
+    /// Polling interval in seconds
+    #[arg(default_value_t = 5, short = 'i', long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+
+    #[command(flatten)]
+    pub capture: CaptureArgs,
+}
+
+pub struct WatchCommand;
+
+impl RunnableCommand<WatchArgs> for WatchCommand {
+    fn run(&self, out: &mut Output, args: &WatchArgs, config: &Config) -> Result<(), AnyError> {
+        if config.diagnostic_path.is_some() {
+            return Err(TuiError::from(
+                "The --diagnostic-path argument is not supported by the watch command",
+            )
+            .into());
+        }
+
+        let Some(mut capture) = HotThreadsCapture::new(&args.capture)? else {
+            return Err(TuiError::from("The --capture-dir argument is required").into());
+        };
+
+        writeln!(
+            out.handle,
+            "Watching {}, up to {} hot threads captures will be saved",
+            config.api.base_url(),
+            capture.max_captures()
+        )?;
+        out.handle.flush()?;
+
+        let interval = Duration::from_secs(args.interval);
+        loop {
+            let data = config.api.get_node_stats(None).and_then(|node_stats| {
+                if !capture.needs_hot_threads(&node_stats) {
+                    return Ok(None);
+                }
+
+                let hot_threads = config.api.get_hot_threads(Some(&[
+                    ("threads", "500"),
+                    ("ignore_idle_threads", "false"),
+                ]))?;
+                Ok(Some((node_stats, hot_threads)))
+            });
+
+            match data {
+                Ok(None) => {}
+                Ok(Some((node_stats, hot_threads))) => {
+                    match capture.check(&node_stats, &hot_threads) {
+                        Ok(Some(saved)) => writeln!(
+                            out.handle,
+                            "Captured hot threads to {}: {}",
+                            saved.path.display(),
+                            saved.reason
+                        )?,
+                        Ok(None) => {}
+                        Err(e) => writeln!(out.handle, "{}", e)?,
+                    }
+                }
+                Err(e) => writeln!(out.handle, "Failed to fetch the node data: {}", e)?,
+            }
+            out.handle.flush()?;
+
+            if capture.is_exhausted() {
+                writeln!(
+                    out.handle,
+                    "Maximum number of captures reached ({}), stopping",
+                    capture.max_captures()
+                )?;
+                return Ok(());
+            }
+
+            thread::sleep(interval);
+        }
+    }
+}
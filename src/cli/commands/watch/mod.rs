@@ -0,0 +1,2 @@
+pub(crate) mod capture;
+pub mod command;
@@ -1,3 +1,4 @@
+use crate::cli::Cli;
 use crate::commands::Command;
 use std::panic;
 use std::process::exit;
@@ -15,40 +16,41 @@ mod output;
 
 type ExitCode = i32;
 
-fn run() -> Result<ExitCode, AnyError> {
-    let cli = cli::build_cli();
-    let username = cli.username;
-    let password = cli.password;
-    let api = api::Client::new(cli.host, username, password, cli.skip_tls_verification)?;
+fn run(cli: &Cli) -> Result<ExitCode, AnyError> {
+    let username = cli.username.clone();
+    let password = cli.password.clone();
+    let api = api::Client::new(
+        cli.host.clone(),
+        username,
+        password,
+        cli.skip_tls_verification,
+    )?;
     let config = Config {
         api,
-        diagnostic_path: cli.diagnostic_path,
+        diagnostic_path: cli.diagnostic_path.clone(),
     };
 
     let stdout = std::io::stdout();
     let mut stdout_lock = stdout.lock();
     let mut out = Output::new(&mut stdout_lock);
 
-    match cli.command {
-        Some(cmd) => {
-            cmd.execute(&mut out, &config)?;
-        }
+    match &cli.command {
+        Some(cmd) => cmd.execute(&mut out, &config),
         None => {
             Command::execute_default_command(&mut out, &config)?;
+            Ok(0)
         }
     }
-
-    Ok(0)
 }
 
 fn main() {
     setup_panic_hook();
 
-    let result = run();
+    let cli = cli::build_cli();
+    let result = run(&cli);
     match result {
         Err(err) => {
-            println!("{}", err);
-            exit(1);
+            exit(Command::report_error(cli.command.as_ref(), &err));
         }
         Ok(exit_code) => {
             exit(exit_code);